@@ -0,0 +1,19 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use wait_free_arena::{HeapAllocator, StressConfig, stress};
+
+fuzz_target!(|data: [u8; 13]| {
+    let [threads, ops, max_size, align_shift, capacity, seed @ ..] = data;
+    let arena = HeapAllocator::new(64 + capacity as usize * 64);
+    let config = StressConfig {
+        threads: 1 + threads as usize % 8,
+        ops_per_thread: ops as usize * 4,
+        max_size: max_size as usize,
+        max_align: 1 << (align_shift % 7),
+        seed: u64::from_le_bytes(seed),
+    };
+    if let Err(violation) = stress(&arena, &config) {
+        panic!("{violation} ({config:?})");
+    }
+});
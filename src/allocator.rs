@@ -1,19 +1,91 @@
 use core::{
     alloc::Layout,
+    fmt,
+    ops::{Bound, RangeBounds},
     ptr::{self, NonNull},
     sync::atomic::{AtomicUsize, Ordering},
 };
-use std::println;
 
 use crate::{AllocError, AllocErrorKind, AllocRes, buffer::Buffer};
+#[cfg(feature = "stats")]
+use crate::{ArenaStats, stats::Counters};
+pub use borrowed_::*;
 #[cfg(feature = "alloc")]
 pub use heap_::*;
 pub use stack_::*;
 
+/// The core allocation interface of all arenas.
+///
+/// The trait is dyn compatible, the generic helpers require `Self: Sized` and are
+/// available on `&dyn ArenaAllocatorImpl` and [`AnyArena`] through their own implementations.
 pub trait ArenaAllocatorImpl {
     fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>>;
     fn dealloc(&self, data: NonNull<u8>, layout: Layout);
+    /// Like [`dealloc`](Self::dealloc), but reports whether the memory was actually reclaimed.
+    ///
+    /// Arenas only reclaim the most recent allocation, freeing any other block is a no-op.
+    /// The default implementation cannot tell and returns `false`.
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.dealloc(data, layout);
+        false
+    }
+    /// Frees the most recent allocation, for callers using the arena as a stack.
+    ///
+    /// Unlike [`dealloc`](Self::dealloc), which is silently best effort, freeing any other block
+    /// is reported as an error. Allocators which cannot tell always report [`NotTail`](crate::NotTail).
+    fn dealloc_last(&self, data: NonNull<u8>, layout: Layout) -> Result<(), crate::NotTail> {
+        if self.try_dealloc(data, layout) {
+            Ok(())
+        } else {
+            Err(crate::NotTail)
+        }
+    }
+    /// Frees every allocation at once, so the buffer can be reused, e.g. per frame or request,
+    /// without allocating a new one.
+    ///
+    /// Taking `&mut self` guarantees no allocation still borrows the arena:
+    ///
+    /// ```compile_fail
+    /// # use wait_free_arena::{ArenaAllocatorImpl, ArenaVec, StackAllocator};
+    /// let mut arena = StackAllocator::<64>::new();
+    /// let mut vec = ArenaVec::new_in(&arena);
+    /// vec.push(1u32).unwrap();
+    /// arena.reset().unwrap();
+    /// vec.push(2).unwrap();
+    /// ```
     fn reset(&mut self) -> AllocRes<()>;
+    /// Whether `ptr` points into memory managed by this allocator.
+    fn contains(&self, ptr: NonNull<u8>) -> bool;
+    /// The number of bytes still available for allocation, as a cheap estimate which may be stale
+    /// under concurrent use. `None` if the allocator cannot tell.
+    fn remaining_hint(&self) -> Option<usize> {
+        None
+    }
+    /// The low-level allocation primitive: a thin pointer to `layout.size()` bytes, which is
+    /// guaranteed to be aligned to `layout.align()`, to build custom typed abstractions on.
+    ///
+    /// If the allocator hands out a misaligned block, it is returned and a block padded by
+    /// `layout.align() - 1` bytes is taken instead. Such a block cannot be freed through
+    /// [`dealloc`](Self::dealloc), only by resetting the arena.
+    #[track_caller]
+    fn alloc_layout(&self, layout: Layout) -> AllocRes<NonNull<u8>> {
+        let block = self.bump_alloc(layout)?.as_non_null_ptr();
+        if block.addr().get().is_multiple_of(layout.align()) {
+            return Ok(block);
+        }
+        self.dealloc(block, layout);
+        let padded = layout
+            .size()
+            .checked_add(layout.align() - 1)
+            .and_then(|size| Layout::from_size_align(size, layout.align()).ok())
+            .ok_or(AllocError::with_message(
+                AllocErrorKind::Other,
+                "padded layout overflows",
+            ))?;
+        let block = self.bump_alloc(padded)?.as_non_null_ptr();
+        Ok(unsafe { block.add(block.align_offset(layout.align())) })
+    }
+    #[track_caller]
     fn bump_alloc_zeroed(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
         let buf_ptr = self.bump_alloc(layout)?;
         let thin = buf_ptr.as_mut_ptr();
@@ -26,81 +98,1254 @@ pub trait ArenaAllocatorImpl {
     }
 
     #[allow(clippy::mut_from_ref)]
-    fn alloc_val<T>(&self, value: T) -> AllocRes<&mut T> {
+    #[track_caller]
+    fn alloc_val<T>(&self, value: T) -> AllocRes<&mut T>
+    where
+        Self: Sized,
+    {
         let space = self.bump_alloc(Layout::new::<T>())?;
         let thin = space.as_mut_ptr() as *mut T;
         unsafe { ptr::write(thin, value) };
         Ok(unsafe { &mut *thin })
     }
+
+    /// Allocates space for a `T` and constructs it in place with `f`.
+    ///
+    /// If `f` panics, the space is handed back to the arena.
+    #[allow(clippy::mut_from_ref)]
+    #[track_caller]
+    fn alloc_with<T>(&self, f: impl FnOnce() -> T) -> AllocRes<&mut T>
+    where
+        Self: Sized,
+    {
+        let layout = Layout::new::<T>();
+        let thin = self.bump_alloc(layout)?.as_mut_ptr() as *mut T;
+        let mut guard = InitGuard::new(self, thin, layout);
+        guard.push(f());
+        Ok(&mut guard.finish()[0])
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    #[track_caller]
+    fn alloc_default<T: Default>(&self) -> AllocRes<&mut T>
+    where
+        Self: Sized,
+    {
+        self.alloc_val(T::default())
+    }
+
+    /// Deep copies `value` into the arena, e.g. to promote borrowed data to the arena's lifetime.
+    #[allow(clippy::mut_from_ref)]
+    #[track_caller]
+    fn alloc_clone_of<T: Clone>(&self, value: &T) -> AllocRes<&mut T>
+    where
+        Self: Sized,
+    {
+        self.alloc_val(value.clone())
+    }
+
+    /// Allocates a slice of `len` copies of `value`, e.g. for lookup tables and pixel buffers.
+    #[allow(clippy::mut_from_ref)]
+    #[track_caller]
+    fn alloc_slice_fill_copy<T: Copy>(&self, len: usize, value: T) -> AllocRes<&mut [T]>
+    where
+        Self: Sized,
+    {
+        let layout = Layout::array::<T>(len).map_err(|_| {
+            AllocError::with_message(AllocErrorKind::Other, "slice layout overflows")
+        })?;
+        let thin = self.bump_alloc(layout)?.as_mut_ptr() as *mut T;
+        // lowered to a memset for single byte values
+        for i in 0..len {
+            unsafe { thin.add(i).write(value) };
+        }
+        Ok(unsafe { &mut *ptr::slice_from_raw_parts_mut(thin, len) })
+    }
+
+    /// Allocates a `rows` by `cols` grid filled with `fill`, stored row-major.
+    #[track_caller]
+    fn alloc_2d<T: Clone>(&self, rows: usize, cols: usize, fill: T) -> AllocRes<crate::Grid<'_, T>>
+    where
+        Self: Sized,
+    {
+        let len = rows.checked_mul(cols).ok_or(AllocError::with_message(
+            AllocErrorKind::Other,
+            "grid size overflows",
+        ))?;
+        let layout = Layout::array::<T>(len).map_err(|_| {
+            AllocError::with_message(AllocErrorKind::Other, "slice layout overflows")
+        })?;
+        let thin = self.bump_alloc(layout)?.as_mut_ptr() as *mut T;
+        let mut guard = InitGuard::new(self, thin, layout);
+        for _ in 0..len {
+            guard.push(fill.clone());
+        }
+        Ok(crate::Grid::new(guard.finish(), rows, cols))
+    }
+
+    /// Renders `value` into the arena, e.g. to build error messages in `no_std`.
+    ///
+    /// The value is formatted twice, once to measure it and once to write it into a block of exactly that size.
+    #[track_caller]
+    fn to_arena_str(&self, value: &impl fmt::Display) -> AllocRes<&str>
+    where
+        Self: Sized,
+    {
+        struct Counter(usize);
+
+        impl fmt::Write for Counter {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0 += s.len();
+                Ok(())
+            }
+        }
+
+        let mut counter = Counter(0);
+        let format_failed = || AllocError::with_message(AllocErrorKind::Other, "formatting failed");
+        fmt::write(&mut counter, format_args!("{value}")).map_err(|_| format_failed())?;
+        let mut out = crate::ArenaString::with_capacity_in(counter.0, self)?;
+        fmt::write(&mut out, format_args!("{value}")).map_err(|_| format_failed())?;
+        Ok(out.into_str())
+    }
+
+    /// Copies `bytes` into the arena, e.g. to keep a token alive after the parser's input buffer is gone.
+    #[track_caller]
+    fn copy_bytes(&self, bytes: &[u8]) -> AllocRes<&[u8]> {
+        let space = self.bump_alloc(Layout::for_value(bytes))?;
+        let thin = space.as_mut_ptr();
+        unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), thin, bytes.len()) };
+        Ok(unsafe { core::slice::from_raw_parts(thin, bytes.len()) })
+    }
+
+    /// Copies `value` into the arena, the copy needs no drop as `T: Copy`.
+    #[track_caller]
+    fn copy_value<T: Copy>(&self, value: &T) -> AllocRes<&T>
+    where
+        Self: Sized,
+    {
+        self.alloc_val(*value).map(|value| &*value)
+    }
+
+    /// Collects `chars` into a string in the arena, e.g. to materialize an unescaped literal while lexing.
+    #[track_caller]
+    fn alloc_str_from_iter(&self, chars: impl IntoIterator<Item = char>) -> AllocRes<&str>
+    where
+        Self: Sized,
+    {
+        let chars = chars.into_iter();
+        let mut builder = crate::StrBuilder::with_capacity_in(chars.size_hint().0, self)?;
+        for c in chars {
+            builder.push(c)?;
+        }
+        Ok(builder.into_str())
+    }
+
+    /// Copies `s` into the arena, e.g. for the many short lived names seen while walking a directory tree.
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn alloc_os_str(&self, s: &std::ffi::OsStr) -> AllocRes<&std::ffi::OsStr> {
+        let bytes = self.copy_bytes(s.as_encoded_bytes())?;
+        // the bytes are an unchanged copy of an `OsStr`
+        Ok(unsafe { std::ffi::OsStr::from_encoded_bytes_unchecked(bytes) })
+    }
+
+    /// Copies `path` into the arena instead of allocating a `PathBuf`.
+    #[cfg(feature = "std")]
+    #[track_caller]
+    fn alloc_path(&self, path: &std::path::Path) -> AllocRes<&std::path::Path> {
+        self.alloc_os_str(path.as_os_str())
+            .map(std::path::Path::new)
+    }
+
+    /// Allocates a value which can refer to itself, like `Rc::new_cyclic`.
+    ///
+    /// `f` receives a slot for the value under construction, which nodes may store
+    /// and resolve once construction has finished, e.g. for parent pointers.
+    #[track_caller]
+    fn alloc_cyclic<'a, T>(
+        &'a self,
+        f: impl FnOnce(&crate::ArenaWeakSlot<'a, T>) -> T,
+    ) -> AllocRes<&'a T>
+    where
+        Self: Sized,
+    {
+        crate::once::alloc_cyclic(self, f)
+    }
+
+    /// Leases `bytes` of zeroed temporary space, which is returned to the arena when the
+    /// [`Scratch`](crate::Scratch) is dropped while still being the most recent allocation.
+    #[track_caller]
+    fn alloc_scratch(&self, bytes: usize) -> AllocRes<crate::Scratch<'_>>
+    where
+        Self: Sized,
+    {
+        crate::Scratch::new_in(bytes, self)
+    }
+
+    /// Reserves space for a `T` which is handed back unless the returned
+    /// [`CommitToken`](crate::CommitToken) is committed.
+    #[track_caller]
+    fn alloc_uncommitted<T>(&self) -> AllocRes<crate::CommitToken<'_, T>>
+    where
+        Self: Sized,
+    {
+        crate::CommitToken::new_in(self)
+    }
+
+    /// Carves a [`ChildArena`](crate::ChildArena) of `bytes` out of this arena,
+    /// which hands the region back when it is dropped.
+    #[track_caller]
+    fn child(&self, bytes: usize) -> AllocRes<crate::ChildArena<'_>>
+    where
+        Self: Sized,
+    {
+        crate::ChildArena::new_in(bytes, self)
+    }
+
+    /// Carves a [`SubArena`](crate::SubArena) of `bytes` aligned to `align` out of this arena,
+    /// whose allocations all keep that alignment.
+    #[track_caller]
+    fn aligned_region(&self, bytes: usize, align: usize) -> AllocRes<crate::SubArena<'_>>
+    where
+        Self: Sized,
+    {
+        crate::SubArena::new_in(bytes, align, self)
+    }
+
+    /// Sets `bytes` aside for error handling paths, allocatable only through the returned
+    /// [`EmergencyToken`](crate::EmergencyToken).
+    #[track_caller]
+    fn reserve_emergency(&self, bytes: usize) -> AllocRes<crate::EmergencyToken<'_>>
+    where
+        Self: Sized,
+    {
+        crate::EmergencyToken::new_in(bytes, self)
+    }
+
+    /// Allocates `N` disjoint blocks with a single cursor bump.
+    /// Each block is placed at an offset satisfying its own layout's alignment,
+    /// assuming the allocator honours the combined alignment of the whole run.
+    #[track_caller]
+    fn bump_alloc_many<const N: usize>(&self, layouts: [Layout; N]) -> AllocRes<[NonNull<[u8]>; N]>
+    where
+        Self: Sized,
+    {
+        let mut combined = Layout::new::<()>();
+        let mut offsets = [0; N];
+        for (offset, layout) in offsets.iter_mut().zip(layouts) {
+            (combined, *offset) = combined.extend(layout).map_err(|_| {
+                AllocError::with_message(AllocErrorKind::Other, "combined layout overflows")
+            })?;
+        }
+        let base = self.bump_alloc(combined)?.as_non_null_ptr();
+        Ok(core::array::from_fn(|i| {
+            NonNull::slice_from_raw_parts(unsafe { base.add(offsets[i]) }, layouts[i].size())
+        }))
+    }
+
+    /// Like [`alloc_val`](Self::alloc_val), but treats running out of memory as fatal
+    /// by calling [`handle_arena_oom`](crate::handle_arena_oom).
+    #[allow(clippy::mut_from_ref)]
+    #[track_caller]
+    fn alloc_val_or_oom<T>(&self, value: T) -> &mut T
+    where
+        Self: Sized,
+    {
+        match self.alloc_val(value) {
+            Ok(value) => value,
+            Err(err) => crate::handle_arena_oom(Layout::new::<T>(), err),
+        }
+    }
+
+    /// Like [`bump_alloc`](Self::bump_alloc), attributing the allocated bytes to `tag`
+    /// in the allocator's stats (if it keeps any).
+    #[track_caller]
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        _ = tag;
+        self.bump_alloc(layout)
+    }
+
+    /// Like [`bump_alloc`](Self::bump_alloc), but leaves the block out of the allocator's stats
+    /// until it is passed to [`record_reserved`](Self::record_reserved), e.g. for a
+    /// [`CommitToken`](crate::CommitToken) which may still be aborted.
+    ///
+    /// The default implementation records the block right away.
+    fn reserve_unrecorded(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.bump_alloc(layout)
+    }
+
+    /// Records a block reserved with [`reserve_unrecorded`](Self::reserve_unrecorded) in the
+    /// allocator's stats (if it keeps any), once it is kept.
+    fn record_reserved(&self, data: NonNull<u8>, layout: Layout) {
+        _ = (data, layout);
+    }
+
+    /// Like [`bump_alloc`](Self::bump_alloc), calling `grown` with the capacity of every chunk
+    /// the allocator adds to serve the request, e.g. for a
+    /// [`GrowableHeapAllocator`](crate::GrowableHeapAllocator).
+    ///
+    /// The default implementation never grows.
+    fn bump_alloc_growing(
+        &self,
+        layout: Layout,
+        grown: &mut dyn FnMut(usize),
+    ) -> AllocRes<NonNull<[u8]>> {
+        _ = grown;
+        self.bump_alloc(layout)
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    #[track_caller]
+    fn alloc_val_tagged<T>(&self, value: T, tag: &'static str) -> AllocRes<&mut T>
+    where
+        Self: Sized,
+    {
+        let space = self.alloc_tagged(Layout::new::<T>(), tag)?;
+        let thin = space.as_mut_ptr() as *mut T;
+        unsafe { ptr::write(thin, value) };
+        Ok(unsafe { &mut *thin })
+    }
 }
 
+impl<A: ArenaAllocatorImpl + ?Sized> ArenaAllocatorImpl for &A {
+    #[track_caller]
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        (**self).bump_alloc(layout)
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        (**self).dealloc(data, layout)
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        (**self).try_dealloc(data, layout)
+    }
+
+    /// An arena cannot be reset through a shared reference, as outstanding allocations may still borrow it.
+    fn reset(&mut self) -> AllocRes<()> {
+        Err(AllocError::with_message(
+            AllocErrorKind::Other,
+            "cannot reset an arena through a shared reference",
+        ))
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        (**self).contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        (**self).remaining_hint()
+    }
+
+    #[track_caller]
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        (**self).alloc_tagged(layout, tag)
+    }
+
+    fn reserve_unrecorded(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        (**self).reserve_unrecorded(layout)
+    }
+
+    fn record_reserved(&self, data: NonNull<u8>, layout: Layout) {
+        (**self).record_reserved(data, layout)
+    }
+
+    fn bump_alloc_growing(
+        &self,
+        layout: Layout,
+        grown: &mut dyn FnMut(usize),
+    ) -> AllocRes<NonNull<[u8]>> {
+        (**self).bump_alloc_growing(layout, grown)
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> ArenaAllocatorImpl for &mut A {
+    #[track_caller]
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        (**self).bump_alloc(layout)
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        (**self).dealloc(data, layout)
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        (**self).try_dealloc(data, layout)
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        (**self).reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        (**self).contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        (**self).remaining_hint()
+    }
+
+    #[track_caller]
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        (**self).alloc_tagged(layout, tag)
+    }
+
+    fn reserve_unrecorded(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        (**self).reserve_unrecorded(layout)
+    }
+
+    fn record_reserved(&self, data: NonNull<u8>, layout: Layout) {
+        (**self).record_reserved(data, layout)
+    }
+
+    fn bump_alloc_growing(
+        &self,
+        layout: Layout,
+        grown: &mut dyn FnMut(usize),
+    ) -> AllocRes<NonNull<[u8]>> {
+        (**self).bump_alloc_growing(layout, grown)
+    }
+}
+
+/// Tracks the initialized prefix of a block under construction.
+///
+/// If an initializer panics, the initialized elements are dropped and the block is handed back
+/// to the arena, so nothing partially constructed can be observed.
+pub(crate) struct InitGuard<'a, T, A: ArenaAllocatorImpl + ?Sized> {
+    alloc: &'a A,
+    ptr: *mut T,
+    init: usize,
+    layout: Layout,
+}
+
+impl<'a, T, A: ArenaAllocatorImpl + ?Sized> InitGuard<'a, T, A> {
+    /// `ptr` must point to a block of `layout` allocated in `alloc`, with room for the elements pushed.
+    pub(crate) fn new(alloc: &'a A, ptr: *mut T, layout: Layout) -> Self {
+        Self {
+            alloc,
+            ptr,
+            init: 0,
+            layout,
+        }
+    }
+
+    pub(crate) fn push(&mut self, value: T) {
+        unsafe { self.ptr.add(self.init).write(value) };
+        self.init += 1;
+    }
+
+    pub(crate) fn finish(self) -> &'a mut [T] {
+        let this = core::mem::ManuallyDrop::new(self);
+        unsafe { &mut *ptr::slice_from_raw_parts_mut(this.ptr, this.init) }
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> Drop for InitGuard<'_, T, A> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(self.ptr, self.init));
+            self.alloc
+                .dealloc(NonNull::new_unchecked(self.ptr).cast(), self.layout);
+        }
+    }
+}
+
+/// A type erased handle to any arena, for passing allocators across plugin or library boundaries without generics.
+#[derive(Clone, Copy)]
+pub struct AnyArena<'a>(&'a dyn ArenaAllocatorImpl);
+
+impl<'a> AnyArena<'a> {
+    pub fn new<A: ArenaAllocatorImpl>(arena: &'a A) -> Self {
+        Self(arena)
+    }
+
+    pub fn as_dyn(&self) -> &'a dyn ArenaAllocatorImpl {
+        self.0
+    }
+}
+
+impl<'a, A: ArenaAllocatorImpl> From<&'a A> for AnyArena<'a> {
+    fn from(arena: &'a A) -> Self {
+        Self::new(arena)
+    }
+}
+
+impl ArenaAllocatorImpl for AnyArena<'_> {
+    #[track_caller]
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.0.bump_alloc(layout)
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.0.dealloc(data, layout)
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.0.try_dealloc(data, layout)
+    }
+
+    /// An arena cannot be reset through a shared handle, see the implementation for `&A`.
+    fn reset(&mut self) -> AllocRes<()> {
+        (&self.0).reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.0.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.0.remaining_hint()
+    }
+
+    #[track_caller]
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.0.alloc_tagged(layout, tag)
+    }
+
+    fn reserve_unrecorded(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.0.reserve_unrecorded(layout)
+    }
+
+    fn record_reserved(&self, data: NonNull<u8>, layout: Layout) {
+        self.0.record_reserved(data, layout)
+    }
+
+    fn bump_alloc_growing(
+        &self,
+        layout: Layout,
+        grown: &mut dyn FnMut(usize),
+    ) -> AllocRes<NonNull<[u8]>> {
+        self.0.bump_alloc_growing(layout, grown)
+    }
+}
+
+/// Inherent methods shared by all arenas wrapping an [`ArenaAllocator`].
+macro_rules! arena_common_impl {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> $ty {
+            /// A snapshot of the arena's counters.
+            #[cfg(feature = "stats")]
+            pub fn stats(&self) -> ArenaStats {
+                self.0.stats()
+            }
+
+            /// Like [`stats`](Self::stats), but rereads the counters until two reads agree, so all
+            /// fields stem from the same moment unless other threads allocate without pause.
+            /// Monitoring loops can subtract consecutive snapshots to get per interval deltas.
+            #[cfg(feature = "stats")]
+            pub fn stats_snapshot(&self) -> ArenaStats {
+                self.0.stats_snapshot()
+            }
+
+            /// Zeroes the counters and tag totals, the high water mark restarts at the current usage.
+            #[cfg(feature = "stats")]
+            pub fn reset_stats(&self) {
+                self.0.counters.reset(self.0.current_offset())
+            }
+
+            /// The number of bytes allocated under each tag passed to
+            /// [`alloc_tagged`](ArenaAllocatorImpl::alloc_tagged) or
+            /// [`with_category`](Self::with_category), in order of first use.
+            #[cfg(feature = "stats")]
+            pub fn tags(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+                self.0.counters.tags()
+            }
+
+            /// Attributes every untagged allocation made while `f` runs to `category`.
+            ///
+            /// The category is arena wide, allocations of other threads during `f` are attributed to it as well.
+            /// Scopes nest, the enclosing category is restored when `f` returns.
+            #[cfg(feature = "stats")]
+            pub fn with_category<R>(&self, category: &'static str, f: impl FnOnce() -> R) -> R {
+                self.0.counters.with_category(category, f)
+            }
+
+            /// The position of the bump cursor, i.e. the number of bytes handed out so far.
+            pub fn current_offset(&self) -> usize {
+                self.0.current_offset()
+            }
+
+            /// The bytes handed out so far, including alignment padding, same as
+            /// [`current_offset`](Self::current_offset).
+            pub fn used(&self) -> usize {
+                self.0.current_offset()
+            }
+
+            /// The bytes left for allocation.
+            pub fn remaining(&self) -> usize {
+                self.capacity() - self.used()
+            }
+
+            /// Size of the backing buffer in bytes.
+            pub fn capacity(&self) -> usize {
+                self.0.capacity()
+            }
+
+            /// The number of successful allocations since creation or the last
+            /// [`reset_stats`](Self::reset_stats).
+            #[cfg(feature = "stats")]
+            pub fn allocation_count(&self) -> usize {
+                self.0.stats().allocations
+            }
+
+            /// The largest number of bytes handed out at once since creation or the last
+            /// [`reset_stats`](Self::reset_stats), e.g. to size the arena for production.
+            #[cfg(feature = "stats")]
+            pub fn high_water(&self) -> usize {
+                self.0.stats().high_water
+            }
+
+            /// Moves the bump cursor to `offset`, e.g. to restore a position saved with
+            /// [`current_offset`](Self::current_offset).
+            ///
+            /// # Safety
+            ///
+            /// `offset` must not exceed the arena's capacity. If it lies below the current offset,
+            /// no allocation made past `offset` may be used afterwards, as its memory is handed out again.
+            pub unsafe fn set_offset(&self, offset: usize) {
+                unsafe { self.0.set_offset(offset) }
+            }
+
+            /// Captures the bump cursor, so everything allocated afterwards can be discarded
+            /// wholesale with [`rewind`](Self::rewind), e.g. the temporaries of a parsing pass.
+            pub fn checkpoint(&self) -> Checkpoint {
+                self.0.checkpoint()
+            }
+
+            /// Frees every allocation made since `checkpoint` was taken.
+            ///
+            /// Fails if the cursor already lies below the checkpoint, i.e. an earlier rewind or a
+            /// [`reset`](ArenaAllocatorImpl::reset) discarded it.
+            ///
+            /// # Safety
+            ///
+            /// No allocation made after `checkpoint` may be used afterwards, as its memory is handed out again.
+            pub unsafe fn rewind(&self, checkpoint: Checkpoint) -> Result<(), crate::StaleCheckpoint> {
+                unsafe { self.0.rewind(checkpoint) }
+            }
+
+            /// Like [`rewind`](Self::rewind), but safe, as the exclusive borrow guarantees no
+            /// allocation is still alive.
+            pub fn rewind_mut(&mut self, checkpoint: Checkpoint) -> Result<(), crate::StaleCheckpoint> {
+                unsafe { self.0.rewind(checkpoint) }
+            }
+
+            /// Opens a scope whose allocations are discarded when it is dropped.
+            pub fn scope(&mut self) -> crate::ArenaScope<'_, Self> {
+                crate::ArenaScope::new(self)
+            }
+
+            /// Touches every page of the buffer, so the page faults of a lazily mapped buffer are
+            /// paid now, e.g. at the startup of a real-time service, instead of during the first
+            /// allocation burst.
+            ///
+            /// Every [`PREFAULT_STRIDE`](crate::PREFAULT_STRIDE) bytes one byte is read and written back, the contents are unchanged.
+            #[doc(alias = "prefault")]
+            pub fn touch_all(&mut self) {
+                self.0.touch_all()
+            }
+
+            /// Writes a human readable summary of the arena's used and unused space to `out`,
+            /// e.g. to log it over a serial line after an OOM.
+            pub fn dump<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+                self.0.dump(out)
+            }
+
+            /// Writes the bytes at the offsets in `range` as an annotated hex dump to `out`,
+            /// e.g. to inspect corrupted arena contents over a serial console.
+            ///
+            /// The range is clamped to the used region. Every line shows the offset and address of
+            /// 16 bytes, the bytes in hex and as ASCII, with the cursor marked on the last line.
+            ///
+            /// # Safety
+            ///
+            /// The dumped bytes must be initialized, e.g. not be padding of a stored value,
+            /// and must not be written to while the dump is taken.
+            pub unsafe fn dump_bytes<W: fmt::Write>(
+                &self,
+                range: impl RangeBounds<usize>,
+                out: &mut W,
+            ) -> fmt::Result {
+                unsafe { self.0.dump_bytes(range, out) }
+            }
+
+            /// Like [`dump_bytes`](Self::dump_bytes), but writes to an [`std::io::Write`].
+            ///
+            /// # Safety
+            ///
+            /// Same as for [`dump_bytes`](Self::dump_bytes).
+            #[cfg(feature = "std")]
+            pub unsafe fn dump_bytes_io<W: std::io::Write>(
+                &self,
+                range: impl RangeBounds<usize>,
+                out: &mut W,
+            ) -> std::io::Result<()> {
+                let mut adapter = crate::util::IoAdapter::new(out);
+                unsafe { self.0.dump_bytes(range, &mut adapter) }.map_err(|_| adapter.into_error())
+            }
+        }
+
+        impl<$($generics)*> crate::Rewind for $ty {
+            fn checkpoint(&self) -> Checkpoint {
+                self.0.checkpoint()
+            }
+
+            unsafe fn rewind(&self, checkpoint: Checkpoint) -> Result<(), crate::StaleCheckpoint> {
+                unsafe { self.0.rewind(checkpoint) }
+            }
+        }
+    };
+}
+
+/// A position of an arena's bump cursor, taken with `checkpoint` and restored with `rewind`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Checkpoint {
+    offset: usize,
+}
+
+impl Checkpoint {
+    /// The captured offset, i.e. the number of bytes handed out when the checkpoint was taken.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// The distance between the bytes touched by `touch_all`, the smallest common page size.
+pub const PREFAULT_STRIDE: usize = 4096;
+
 pub(crate) struct ArenaAllocator<B: Buffer<u8>> {
     buf: B,
     next_free: AtomicUsize,
+    /// The buffer's base must be aligned to this.
+    min_align: usize,
+    /// Whether blocks are aligned to their layout, with the skipped bytes being recorded in `gaps`.
+    packing: bool,
+    gaps: GapList,
+    #[cfg(feature = "stats")]
+    counters: Counters,
+    #[cfg(feature = "sanitize")]
+    passthrough: crate::sanitize::Passthrough,
 }
 
-impl<B: Buffer<u8>> ArenaAllocatorImpl for ArenaAllocator<B> {
-    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
-        let idx = loop {
-            let cur = self.next_free.load(Ordering::Acquire);
-            if layout.size() > self.buf.len() - cur {
-                return Err(AllocError::with_message(
-                    AllocErrorKind::OOM,
-                    "Not enough memory in buffer",
-                ));
-            }
-
-            if let Ok(current) = self.next_free.compare_exchange(
-                cur,
-                cur + layout.size(),
-                Ordering::AcqRel,
-                Ordering::Relaxed,
-            ) {
-                break current;
-            }
+const GAP_SLOTS: usize = 8;
+const GAP_HALF: u32 = usize::BITS / 2;
+
+/// A tiny list of `(offset, len)` byte ranges below the cursor which were skipped to align a block.
+///
+/// Each slot packs a range into a single word, so ranges are claimed with one CAS. A gap
+/// lies in front of the block it aligned, so rewinding the cursor by freeing that block never
+/// hands out the gap's bytes again.
+struct GapList {
+    slots: [AtomicUsize; GAP_SLOTS],
+}
+
+impl GapList {
+    const fn new() -> Self {
+        Self {
+            slots: [const { AtomicUsize::new(0) }; GAP_SLOTS],
+        }
+    }
+
+    fn pack(offset: usize, len: usize) -> Option<usize> {
+        (len != 0 && offset >> GAP_HALF == 0 && len >> GAP_HALF == 0)
+            .then_some(offset << GAP_HALF | len)
+    }
+
+    fn unpack(packed: usize) -> (usize, usize) {
+        (packed >> GAP_HALF, packed & ((1 << GAP_HALF) - 1))
+    }
+
+    /// Remembers a gap, it is dropped if all slots are taken.
+    fn insert(&self, offset: usize, len: usize) {
+        let Some(packed) = Self::pack(offset, len) else {
+            return;
         };
-        println!("al: {}, s:{}", layout.align(), layout.size());
-        let buffer = self.buf.as_mut_ptr();
-        let buffer = unsafe { buffer.add(idx) };
-        let buffer = ptr::slice_from_raw_parts_mut(buffer, layout.size());
+        for slot in &self.slots {
+            if slot
+                .compare_exchange(0, packed, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                return;
+            }
+        }
+    }
 
-        NonNull::new(buffer).ok_or(AllocError::new(AllocErrorKind::InvalidPtr))
+    /// Carves `size` bytes aligned to `align` (relative to address `base`) from the front of a gap.
+    fn claim(&self, base: usize, size: usize, align: usize) -> Option<usize> {
+        for slot in &self.slots {
+            let packed = slot.load(Ordering::Relaxed);
+            if packed == 0 {
+                continue;
+            }
+            let (offset, len) = Self::unpack(packed);
+            let start = (base + offset).next_multiple_of(align) - base;
+            let end = start + size;
+            if end > offset + len {
+                continue;
+            }
+            let rest = Self::pack(end, offset + len - end).unwrap_or(0);
+            if slot
+                .compare_exchange(packed, rest, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                return Some(start);
+            }
+        }
+        None
+    }
+
+    /// Forgets all gaps, they may lie above a rewound cursor.
+    fn clear(&self) {
+        for slot in &self.slots {
+            slot.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<B: Buffer<u8>> ArenaAllocatorImpl for ArenaAllocator<B> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        let block = self.bump(layout, true)?;
+        #[cfg(feature = "stats")]
+        self.counters.record_untagged(layout.size());
+        Ok(block)
     }
 
     fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
-        let cur = self.next_free.load(Ordering::Acquire);
-        if layout.size() > cur {
-            return;
+        #[cfg(feature = "sanitize")]
+        if !self.try_dealloc(data, layout)
+            && let Some(node) = self.passthrough.find(data)
+        {
+            node.release();
         }
-        let last = cur - layout.size();
-        let base_ptr = self.buf.as_ptr();
-        let cur_ptr = unsafe { base_ptr.add(last) };
-        if cur_ptr == data.as_ptr() {
-            // we may try to free the memory, as it seems like the returned object is at the end of the buffer
-            _ = self
-                .next_free
-                .compare_exchange(cur, last, Ordering::AcqRel, Ordering::Relaxed);
+        #[cfg(not(feature = "sanitize"))]
+        self.try_dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        #[cfg(feature = "sanitize")]
+        if let Some(node) = self.passthrough.find(data) {
+            let reclaimed = self.try_reclaim(node.shadow(), layout);
+            if reclaimed {
+                node.release();
+            }
+            return reclaimed;
         }
+        self.try_reclaim(data, layout)
     }
 
     fn reset(&mut self) -> AllocRes<()> {
         self.next_free.store(0, Ordering::Release);
+        self.gaps.clear();
+        #[cfg(feature = "sanitize")]
+        self.passthrough.clear();
         Ok(())
     }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        let base = self.buf.as_ptr().addr();
+        #[cfg(feature = "sanitize")]
+        if self.passthrough.contains(ptr) {
+            return true;
+        }
+        (base..base + self.buf.len()).contains(&ptr.addr().get())
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        Some(
+            self.buf
+                .len()
+                .saturating_sub(self.next_free.load(Ordering::Relaxed)),
+        )
+    }
+
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        let block = self.bump(layout, true)?;
+        #[cfg(feature = "stats")]
+        self.counters.record_tag(tag, layout.size());
+        Ok(block)
+    }
+
+    fn reserve_unrecorded(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.bump(layout, false)
+    }
+
+    #[cfg(feature = "stats")]
+    fn record_reserved(&self, data: NonNull<u8>, layout: Layout) {
+        if layout.size() != 0
+            && let Some(offset) = self.offset_of(data)
+        {
+            self.counters.record_used(offset + self.padded_size(layout));
+        }
+        self.counters.record_allocation();
+        self.counters.record_untagged(layout.size());
+    }
 }
 
+// Every access to the shared buffer goes through `next_free`, which hands out disjoint regions.
+unsafe impl<B: Buffer<u8> + Send> Sync for ArenaAllocator<B> {}
+
 impl<B: Buffer<u8>> ArenaAllocator<B> {
-    pub(crate) fn new_in(buf: B) -> Self {
+    pub(crate) const fn new_in(buf: B) -> Self {
         Self {
             buf,
             next_free: AtomicUsize::new(0),
+            min_align: 1,
+            packing: false,
+            gaps: GapList::new(),
+            #[cfg(feature = "stats")]
+            counters: Counters::new(),
+            #[cfg(feature = "sanitize")]
+            passthrough: crate::sanitize::Passthrough::new(),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn with_packing(mut self, packing: bool) -> Self {
+        self.packing = packing;
+        self
+    }
+
+    /// `min_align` must be a power of two and the buffer's base must be aligned to it.
+    pub(crate) fn with_min_align(mut self, min_align: usize) -> Self {
+        debug_assert!(min_align.is_power_of_two());
+        debug_assert!(self.buf.as_ptr().addr() % min_align == 0);
+        self.min_align = min_align;
+        self
+    }
+
+    #[cfg(feature = "stats")]
+    pub(crate) fn stats(&self) -> ArenaStats {
+        self.counters
+            .snapshot(self.current_offset(), self.buf.len())
+    }
+
+    #[cfg(feature = "stats")]
+    pub(crate) fn stats_snapshot(&self) -> ArenaStats {
+        const ATTEMPTS: usize = 8;
+
+        let mut last = self.stats();
+        for _ in 0..ATTEMPTS {
+            let next = self.stats();
+            if next == last {
+                break;
+            }
+            last = next;
+        }
+        last
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The cursor may briefly overshoot the capacity while a request fails, see `bump_buffer`.
+    pub(crate) fn current_offset(&self) -> usize {
+        self.next_free.load(Ordering::Acquire).min(self.buf.len())
+    }
+
+    /// The offset of the block at `data`, following a `sanitize` heap block to its shadow.
+    pub(crate) fn offset_of(&self, data: NonNull<u8>) -> Option<usize> {
+        #[cfg(feature = "sanitize")]
+        let data = self
+            .passthrough
+            .find(data)
+            .map_or(data, |node| node.shadow());
+        let offset = data.addr().get().checked_sub(self.buf.as_ptr().addr())?;
+        (offset < self.buf.len()).then_some(offset)
+    }
+
+    pub(crate) unsafe fn set_offset(&self, offset: usize) {
+        debug_assert!(offset <= self.buf.len());
+        self.gaps.clear();
+        self.next_free.store(offset, Ordering::Release);
+    }
+
+    pub(crate) fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            offset: self.current_offset(),
+        }
+    }
+
+    pub(crate) unsafe fn rewind(
+        &self,
+        checkpoint: Checkpoint,
+    ) -> Result<(), crate::StaleCheckpoint> {
+        self.next_free
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |cur| {
+                (checkpoint.offset <= cur).then_some(checkpoint.offset)
+            })
+            // gaps may lie past the checkpoint, where the cursor hands their bytes out again
+            .map(|_| self.gaps.clear())
+            .map_err(|_| crate::StaleCheckpoint)
+    }
+
+    pub(crate) fn dump<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        const WIDTH: usize = 64;
+
+        let capacity = self.buf.len();
+        let used = self.next_free.load(Ordering::Acquire).min(capacity);
+        writeln!(
+            out,
+            "arena: {used}/{capacity} bytes used, {} free",
+            capacity - used
+        )?;
+        #[cfg(feature = "stats")]
+        {
+            let stats = self.stats();
+            writeln!(
+                out,
+                "high water: {}, contention: {}, oom: {}, padding: {}",
+                stats.high_water, stats.contention, stats.oom_count, stats.padding_bytes
+            )?;
+            for (tag, bytes) in self.counters.tags() {
+                writeln!(out, "  {tag}: {bytes} bytes")?;
+            }
+        }
+        if capacity == 0 {
+            return Ok(());
+        }
+        let filled = (used * WIDTH).div_ceil(capacity);
+        out.write_char('[')?;
+        for cell in 0..WIDTH {
+            out.write_char(if cell < filled { '#' } else { '.' })?;
+        }
+        out.write_str("]\n")
+    }
+
+    pub(crate) fn touch_all(&mut self) {
+        let base = self.buf.as_mut_ptr().cast::<core::mem::MaybeUninit<u8>>();
+        for offset in (0..self.buf.len()).step_by(PREFAULT_STRIDE) {
+            // written back, so copy on write and zero pages get their own frame
+            unsafe {
+                let byte = base.add(offset);
+                byte.write_volatile(byte.read_volatile());
+            }
+        }
+    }
+
+    pub(crate) unsafe fn dump_bytes<W: fmt::Write>(
+        &self,
+        range: impl RangeBounds<usize>,
+        out: &mut W,
+    ) -> fmt::Result {
+        const ROW: usize = 16;
+
+        let used = self.next_free.load(Ordering::Acquire).min(self.buf.len());
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        }
+        .min(used);
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => used,
+        }
+        .clamp(start, used);
+        let base = self.buf.as_ptr();
+        writeln!(
+            out,
+            "arena at {base:p}: bytes {start:#x}..{end:#x} of {used:#x} used"
+        )?;
+        let bytes = unsafe { core::slice::from_raw_parts(base.add(start), end - start) };
+        for (row, chunk) in bytes.chunks(ROW).enumerate() {
+            let offset = start + row * ROW;
+            write!(out, "{offset:#06x} {:p} ", unsafe { base.add(offset) })?;
+            for idx in 0..ROW {
+                match chunk.get(idx) {
+                    Some(byte) => write!(out, " {byte:02x}")?,
+                    None => out.write_str("   ")?,
+                }
+                if idx == ROW / 2 - 1 {
+                    out.write_char(' ')?;
+                }
+            }
+            out.write_str("  |")?;
+            for &byte in chunk {
+                out.write_char(if byte.is_ascii_graphic() || byte == b' ' {
+                    byte as char
+                } else {
+                    '.'
+                })?;
+            }
+            out.write_char('|')?;
+            if offset + chunk.len() == used {
+                out.write_str(" <- cursor")?;
+            }
+            out.write_char('\n')?;
+        }
+        Ok(())
+    }
+
+    /// Rewinds the cursor if `data` is the most recent block.
+    fn try_reclaim(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        if layout.size() == 0 {
+            return false;
+        }
+        let size = self.padded_size(layout);
+        let cur = self.next_free.load(Ordering::Acquire);
+        if size > cur {
+            return false;
+        }
+        let last = cur - size;
+        let offset = data.addr().get().wrapping_sub(self.buf.as_ptr().addr());
+        // we may only free the memory if the returned object is at the end of the buffer
+        offset == last
+            && self
+                .next_free
+                .compare_exchange(cur, last, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+    }
+
+    /// Moves the cursor without attributing the allocation to a tag. Unless `record` is set, the
+    /// allocation is left out of the allocation count and high water mark.
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+    fn bump(&self, layout: Layout, record: bool) -> AllocRes<NonNull<[u8]>> {
+        let block = self.bump_buffer(layout, record)?;
+        #[cfg(feature = "sanitize")]
+        let block = self
+            .passthrough
+            .alloc(layout.align_to(self.min_align).unwrap_or(layout), block)?;
+        #[cfg(feature = "stats")]
+        if record {
+            self.counters.record_allocation();
+        }
+        Ok(block)
+    }
+
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+    fn bump_buffer(&self, layout: Layout, record: bool) -> AllocRes<NonNull<[u8]>> {
+        crate::check_align(layout.align())?;
+        if layout.size() == 0 {
+            // zero sized blocks take no space, so they never fail and never move the cursor
+            let dangling = layout
+                .align_to(self.min_align)
+                .unwrap_or(layout)
+                .dangling_ptr();
+            return Ok(NonNull::slice_from_raw_parts(dangling, 0));
         }
+        let size = self.padded_size(layout);
+        let base = self.buf.as_ptr().addr();
+        let align = layout.align().max(self.min_align).min(crate::MAX_ALIGN);
+        if self.packing
+            && let Some(start) = self.gaps.claim(base, size, align)
+        {
+            return Ok(self.block_at(start, layout));
+        }
+        let cur = self.next_free.load(Ordering::Relaxed);
+        if cur > self.buf.len() {
+            return Err(self.exhausted(size, cur));
+        }
+        // exact for the cursor seen now, which holds unless another request moves it first
+        let padding = (base + cur).next_multiple_of(align) - (base + cur);
+        let reserve = size
+            .checked_add(padding)
+            .ok_or_else(|| self.exhausted(size, cur))?;
+        let mut prev = self.claim(reserve, size)?;
+        let mut end = prev + reserve;
+        let mut start = (base + prev).next_multiple_of(align) - base;
+        if start + size > end {
+            // the cursor moved in between, claim room for the worst case padding instead,
+            // as it is always a multiple of `min_align`
+            self.release(prev, end);
+            let reserve = size
+                .checked_add(align - self.min_align)
+                .ok_or_else(|| self.exhausted(size, cur))?;
+            prev = self.claim(reserve, size)?;
+            end = prev + reserve;
+            start = (base + prev).next_multiple_of(align) - base;
+        }
+        self.release(start + size, end);
+        if start > prev {
+            if self.packing {
+                self.gaps.insert(prev, start - prev);
+            }
+            #[cfg(feature = "stats")]
+            self.counters.record_padding(start - prev);
+        }
+        #[cfg(feature = "stats")]
+        {
+            if record {
+                self.counters.record_used(start + size);
+            }
+            self.counters.record_padding(size - layout.size());
+        }
+        Ok(self.block_at(start, layout))
+    }
+
+    /// Moves the cursor past `reserve` bytes with a single `fetch_add` and returns the old cursor.
+    ///
+    /// A request losing the race for the last bytes moves the cursor past the capacity. It is moved
+    /// back unless another request followed, otherwise the remaining bytes are lost until the arena
+    /// is reset. Either way the request completes in a bounded number of steps.
+    fn claim(&self, reserve: usize, requested: usize) -> AllocRes<usize> {
+        let len = self.buf.len();
+        let cur = self.next_free.load(Ordering::Relaxed);
+        if reserve > len || cur > len - reserve {
+            return Err(self.exhausted(requested, cur));
+        }
+        let prev = self.next_free.fetch_add(reserve, Ordering::AcqRel);
+        if prev > len - reserve {
+            self.release(prev, prev.wrapping_add(reserve));
+            return Err(self.exhausted(requested, prev));
+        }
+        Ok(prev)
+    }
+
+    /// Hands the claimed bytes `from..to` back by moving the cursor, if no other request followed.
+    /// Otherwise they are kept as a gap in packing mode, and lost without it.
+    fn release(&self, from: usize, to: usize) {
+        if to <= from
+            || self
+                .next_free
+                .compare_exchange(to, from, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+        {
+            return;
+        }
+        if self.packing && to <= self.buf.len() {
+            self.gaps.insert(from, to - from);
+        }
+        #[cfg(feature = "stats")]
+        self.counters.record_contention();
+    }
+
+    fn exhausted(&self, requested: usize, cur: usize) -> AllocError {
+        #[cfg(feature = "stats")]
+        self.counters.record_oom();
+        AllocError::with_message(
+            AllocErrorKind::InsufficientCapacity {
+                requested,
+                available: self.buf.len().saturating_sub(cur),
+            },
+            "Not enough memory in buffer",
+        )
+    }
+
+    fn block_at(&self, offset: usize, layout: Layout) -> NonNull<[u8]> {
+        let buffer = unsafe { self.buf.as_mut_ptr().add(offset) };
+        NonNull::slice_from_raw_parts(unsafe { NonNull::new_unchecked(buffer) }, layout.size())
+    }
+
+    /// Every allocation occupies a multiple of `min_align` bytes, which keeps all offsets `min_align` aligned.
+    fn padded_size(&self, layout: Layout) -> usize {
+        layout.size().next_multiple_of(self.min_align)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn into_buf(self) -> B {
+        self.buf
     }
 }
 
 #[cfg(feature = "alloc")]
 mod heap_ {
+    use alloc::{boxed::Box, vec::Vec};
+
     use crate::buffer::HeapBuf;
+    pub use crate::buffer::{BackingAllocator, GlobalBacking};
 
     use super::*;
 
@@ -108,11 +1353,17 @@ mod heap_ {
     mod alloc_api_ {
         use super::*;
 
+        /// Implements [`Allocator`](alloc::alloc::Allocator) for a type implementing [`ArenaAllocatorImpl`].
+        ///
+        /// Failures are reported as plain [`AllocError`](alloc::alloc::AllocError)s, so infallible
+        /// collection APIs such as `Vec::push` diverge through
+        /// [`handle_alloc_error`](alloc::alloc::handle_alloc_error) with the layout that could not be served,
+        /// while `try_reserve` and friends hand that layout back as `TryReserveErrorKind::AllocError`.
         #[macro_export]
         macro_rules! std_allocator_impl {
             (@impl [$($impl_generics:tt)*] $ty:ty) => {
                 unsafe impl<$($impl_generics)*> ::alloc::alloc::Allocator for $ty {
-                    fn allocate(&self, layout: ::core::alloc::Layout) -> Result<NonNull<[u8]>, ::alloc::alloc::AllocError> {
+                    fn allocate(&self, layout: ::core::alloc::Layout) -> Result<::core::ptr::NonNull<[u8]>, ::alloc::alloc::AllocError> {
                         $crate::ArenaAllocatorImpl::bump_alloc(self, layout).map_err(|e| e.into())
                     }
 
@@ -127,21 +1378,22 @@ mod heap_ {
             };
 
             ($ty:ty) => {
-                std_allocator_impl!(@impl [] $ty);
+                $crate::std_allocator_impl!(@impl [] $ty);
             };
 
             ($ty:ty where [$($generics:tt)*]) => {
-                std_allocator_impl!(@impl [$($generics)*] $ty);
+                $crate::std_allocator_impl!(@impl [$($generics)*] $ty);
             };
         }
 
-        std_allocator_impl!(HeapAllocator);
+        std_allocator_impl!(HeapAllocator<A> where [A: BackingAllocator]);
         std_allocator_impl!(StackAllocator<N> where [const N: usize]);
+        std_allocator_impl!(BorrowedAllocator<'a> where ['a]);
     }
 
-    pub struct HeapAllocator(ArenaAllocator<HeapBuf<u8>>);
+    pub struct HeapAllocator<A: BackingAllocator = GlobalBacking>(ArenaAllocator<HeapBuf<u8, A>>);
 
-    impl ArenaAllocatorImpl for HeapAllocator {
+    impl<A: BackingAllocator> ArenaAllocatorImpl for HeapAllocator<A> {
         fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
             ArenaAllocatorImpl::bump_alloc(&self.0, layout)
         }
@@ -150,14 +1402,279 @@ mod heap_ {
             ArenaAllocatorImpl::dealloc(&self.0, data, layout);
         }
 
+        fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+            ArenaAllocatorImpl::try_dealloc(&self.0, data, layout)
+        }
+
         fn reset(&mut self) -> AllocRes<()> {
             self.0.reset()
         }
+
+        fn contains(&self, ptr: NonNull<u8>) -> bool {
+            self.0.contains(ptr)
+        }
+
+        fn remaining_hint(&self) -> Option<usize> {
+            self.0.remaining_hint()
+        }
+
+        fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+            self.0.alloc_tagged(layout, tag)
+        }
+
+        fn reserve_unrecorded(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+            self.0.reserve_unrecorded(layout)
+        }
+
+        fn record_reserved(&self, data: NonNull<u8>, layout: Layout) {
+            self.0.record_reserved(data, layout)
+        }
     }
 
+    impl<A: ArenaAllocatorImpl + ?Sized> ArenaAllocatorImpl for Box<A> {
+        #[track_caller]
+        fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+            (**self).bump_alloc(layout)
+        }
+
+        fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+            (**self).dealloc(data, layout)
+        }
+
+        fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+            (**self).try_dealloc(data, layout)
+        }
+
+        fn reset(&mut self) -> AllocRes<()> {
+            (**self).reset()
+        }
+
+        fn contains(&self, ptr: NonNull<u8>) -> bool {
+            (**self).contains(ptr)
+        }
+
+        fn remaining_hint(&self) -> Option<usize> {
+            (**self).remaining_hint()
+        }
+
+        #[track_caller]
+        fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+            (**self).alloc_tagged(layout, tag)
+        }
+
+        fn reserve_unrecorded(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+            (**self).reserve_unrecorded(layout)
+        }
+
+        fn record_reserved(&self, data: NonNull<u8>, layout: Layout) {
+            (**self).record_reserved(data, layout)
+        }
+
+        fn bump_alloc_growing(
+            &self,
+            layout: Layout,
+            grown: &mut dyn FnMut(usize),
+        ) -> AllocRes<NonNull<[u8]>> {
+            (**self).bump_alloc_growing(layout, grown)
+        }
+    }
+
+    arena_common_impl!([A: BackingAllocator] HeapAllocator<A>);
+
     impl HeapAllocator {
+        /// Allocates a zeroed buffer of `size` bytes, aligned to 16 bytes like the buffer of a
+        /// [`StackAllocator`].
         pub fn new(size: usize) -> Self {
-            Self(ArenaAllocator::new_in(HeapBuf::new(size)))
+            Self(ArenaAllocator::new_in(HeapBuf::zeroed(size)))
+        }
+
+        /// Uses `buf` as the backing memory of the arena.
+        pub fn from_box(buf: Box<[u8]>) -> Self {
+            Self(ArenaAllocator::new_in(HeapBuf::from_box(buf)))
+        }
+
+        /// Uses the full capacity of `vec` as the backing memory of the arena.
+        /// Bytes past `vec.len()` are zero filled first.
+        pub fn from_vec(vec: Vec<u8>) -> Self {
+            Self(ArenaAllocator::new_in(HeapBuf::from_vec(vec)))
+        }
+
+        /// Tears down the arena and returns its backing memory.
+        ///
+        /// A `Box<[u8]>` must be freed with an alignment of one, so buffers allocated with a larger
+        /// alignment, which includes those of [`new`](Self::new) and the builder, are copied into a
+        /// fresh allocation. Buffers adopted with [`from_box`](Self::from_box) or
+        /// [`from_vec`](Self::from_vec) are returned as is. Use
+        /// [`into_raw_parts`](Self::into_raw_parts) to keep the buffer in place.
+        pub fn into_box(self) -> Box<[u8]> {
+            self.0.into_buf().into_box()
+        }
+
+        /// Tears down the arena and returns its backing memory as a `Vec` with `len == capacity`,
+        /// copied like in [`into_box`](Self::into_box).
+        pub fn into_vec(self) -> Vec<u8> {
+            self.into_box().into_vec()
+        }
+
+        /// Tears down the arena and returns its buffer's base, capacity and alignment and the bump
+        /// cursor, e.g. to store the arena in a kernel structure and reconstitute it with
+        /// [`from_raw_parts`](Self::from_raw_parts). The buffer is never copied.
+        pub fn into_raw_parts(self) -> (NonNull<u8>, usize, usize, usize) {
+            let cursor = self.current_offset();
+            let (ptr, capacity, align) = self.0.into_buf().into_raw_parts();
+            (ptr, capacity, align, cursor)
+        }
+
+        /// Reconstitutes an arena from the parts returned by [`into_raw_parts`](Self::into_raw_parts).
+        ///
+        /// # Safety
+        ///
+        /// `ptr`, `capacity` and `align` must describe a buffer allocated by the global allocator with
+        /// the layout of `[u8; capacity]` aligned to `align`, which is not used otherwise, and `cursor`
+        /// must not exceed `capacity`. Bytes below `cursor` are treated as allocated.
+        pub unsafe fn from_raw_parts(
+            ptr: NonNull<u8>,
+            capacity: usize,
+            align: usize,
+            cursor: usize,
+        ) -> Self {
+            let buf = unsafe { HeapBuf::from_raw_parts(ptr, capacity, align) };
+            let arena = Self(ArenaAllocator::new_in(buf));
+            unsafe { arena.set_offset(cursor) };
+            arena
+        }
+    }
+
+    impl HeapAllocator {
+        /// Starts configuring a [`HeapAllocator`], see [`HeapAllocatorBuilder`].
+        pub fn builder() -> HeapAllocatorBuilder {
+            HeapAllocatorBuilder {
+                capacity: 0,
+                min_align: 1,
+                packing: false,
+                backing: GlobalBacking,
+            }
+        }
+
+        /// A packing arena whose buffer is aligned to `layout.align()` without padding every
+        /// allocation to it, so blocks laid out as in `layout` fit exactly.
+        pub(crate) fn try_for_layout(layout: Layout) -> AllocRes<Self> {
+            HeapBuf::zeroed_in(layout.size(), layout.align(), GlobalBacking)
+                .map(|buf| Self(ArenaAllocator::new_in(buf).with_packing(true)))
+                .ok_or(AllocError::new(AllocErrorKind::BackingExhausted {
+                    requested: layout.size(),
+                }))
+        }
+    }
+
+    impl<A: BackingAllocator> HeapAllocator<A> {
+        /// Carves the arena's buffer of `size` bytes out of `backing`, e.g. another arena.
+        ///
+        /// # Panics
+        ///
+        /// Calls [`handle_alloc_error`](alloc::alloc::handle_alloc_error) if `backing` cannot provide the buffer.
+        pub fn new_in(size: usize, backing: A) -> Self {
+            Self::try_new_in(size, backing).unwrap_or_else(|_| {
+                alloc::alloc::handle_alloc_error(Layout::array::<u8>(size).unwrap())
+            })
+        }
+
+        pub fn try_new_in(size: usize, backing: A) -> AllocRes<Self> {
+            HeapBuf::zeroed_in(size, crate::buffer::BUF_ALIGN, backing)
+                .map(|buf| Self(ArenaAllocator::new_in(buf)))
+                .ok_or(AllocError::new(AllocErrorKind::BackingExhausted {
+                    requested: size,
+                }))
+        }
+    }
+
+    /// Configures a [`HeapAllocator`], or with [`growth`](Self::growth) a
+    /// [`GrowableHeapAllocator`](crate::GrowableHeapAllocator).
+    ///
+    /// ```
+    /// # use wait_free_arena::HeapAllocator;
+    /// let arena = HeapAllocator::builder().capacity(4096).min_align(16).build();
+    /// ```
+    ///
+    /// Stats are no option, every arena keeps them once the `stats` feature is enabled.
+    #[derive(Clone)]
+    pub struct HeapAllocatorBuilder<A: BackingAllocator = GlobalBacking> {
+        capacity: usize,
+        min_align: usize,
+        packing: bool,
+        backing: A,
+    }
+
+    impl<A: BackingAllocator> HeapAllocatorBuilder<A> {
+        /// Size of the backing buffer in bytes, defaults to `0`.
+        pub fn capacity(mut self, capacity: usize) -> Self {
+            self.capacity = capacity;
+            self
+        }
+
+        /// Alignment every allocation is guaranteed to have, defaults to `1`.
+        /// Allocations are padded to a multiple of it.
+        pub fn min_align(mut self, min_align: usize) -> Self {
+            self.min_align = min_align;
+            self
+        }
+
+        /// Serves later requests from the bytes skipped to align blocks to their layout,
+        /// e.g. for workloads interleaving short strings with cache line aligned structs.
+        /// Defaults to `false`.
+        pub fn packing(mut self, packing: bool) -> Self {
+            self.packing = packing;
+            self
+        }
+
+        pub(crate) fn get_min_align(&self) -> usize {
+            self.min_align
+        }
+
+        /// Where the backing buffer is allocated from, defaults to [`GlobalBacking`].
+        pub fn backing<B: BackingAllocator>(self, backing: B) -> HeapAllocatorBuilder<B> {
+            HeapAllocatorBuilder {
+                capacity: self.capacity,
+                min_align: self.min_align,
+                packing: self.packing,
+                backing,
+            }
+        }
+
+        /// # Panics
+        ///
+        /// Panics if `min_align` is not a power of two and calls
+        /// [`handle_alloc_error`](alloc::alloc::handle_alloc_error) if the buffer cannot be allocated.
+        pub fn build(self) -> HeapAllocator<A> {
+            let (capacity, min_align) = (self.capacity, self.min_align);
+            match self.try_build() {
+                Ok(arena) => arena,
+                Err(err) if err.kind().is_out_of_memory() => alloc::alloc::handle_alloc_error(
+                    Layout::from_size_align(capacity, min_align).unwrap(),
+                ),
+                Err(err) => panic!("invalid arena configuration: {err}"),
+            }
+        }
+
+        pub fn try_build(self) -> AllocRes<HeapAllocator<A>> {
+            if !self.min_align.is_power_of_two() {
+                return Err(AllocError::new(AllocErrorKind::AlignmentUnsupported {
+                    align: self.min_align,
+                }));
+            }
+            crate::check_align(self.min_align)?;
+            let align = self.min_align.max(crate::buffer::BUF_ALIGN);
+            HeapBuf::zeroed_in(self.capacity, align, self.backing)
+                .map(|buf| {
+                    HeapAllocator(
+                        ArenaAllocator::new_in(buf)
+                            .with_min_align(self.min_align)
+                            .with_packing(self.packing),
+                    )
+                })
+                .ok_or(AllocError::new(AllocErrorKind::BackingExhausted {
+                    requested: self.capacity,
+                }))
         }
     }
 }
@@ -167,6 +1684,10 @@ mod stack_ {
 
     use super::*;
 
+    /// An arena over an inline buffer of `N` bytes.
+    ///
+    /// The buffer is aligned to 16 bytes, so a fresh arena serves blocks of up to that alignment
+    /// without padding, wherever it is placed.
     pub struct StackAllocator<const N: usize>(ArenaAllocator<StackBuf<N, u8>>);
 
     impl<const N: usize> ArenaAllocatorImpl for StackAllocator<N> {
@@ -178,20 +1699,178 @@ mod stack_ {
             self.0.dealloc(data, layout)
         }
 
+        fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+            self.0.try_dealloc(data, layout)
+        }
+
         fn reset(&mut self) -> AllocRes<()> {
             self.0.reset()
         }
+
+        fn contains(&self, ptr: NonNull<u8>) -> bool {
+            self.0.contains(ptr)
+        }
+
+        fn remaining_hint(&self) -> Option<usize> {
+            self.0.remaining_hint()
+        }
+
+        fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+            self.0.alloc_tagged(layout, tag)
+        }
+
+        fn reserve_unrecorded(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+            self.0.reserve_unrecorded(layout)
+        }
+
+        fn record_reserved(&self, data: NonNull<u8>, layout: Layout) {
+            self.0.record_reserved(data, layout)
+        }
     }
 
+    arena_common_impl!([const N: usize] StackAllocator<N>);
+
     impl<const N: usize> StackAllocator<N> {
-        pub fn new() -> Self {
+        /// Size of the arena's buffer in bytes.
+        pub const CAPACITY: usize = N;
+
+        pub const fn new() -> Self {
             Self(ArenaAllocator::new_in(StackBuf::new()))
         }
     }
 
+    /// Fails compilation if the listed types cannot all be allocated from a fresh arena.
+    ///
+    /// Each type is charged its size plus the worst case alignment padding (`align - 1`),
+    /// as the alignment of the arena's buffer is not known up front.
+    ///
+    /// ```
+    /// # use wait_free_arena::{StackAllocator, static_arena_fits};
+    /// static_arena_fits!(StackAllocator<64>, u8, u32, [u16; 16]);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use wait_free_arena::{StackAllocator, static_arena_fits};
+    /// static_arena_fits!(StackAllocator<16>, u64, [u8; 16]);
+    /// ```
+    #[macro_export]
+    macro_rules! static_arena_fits {
+        ($arena:ty, $($ty:ty),+ $(,)?) => {
+            const _: () = {
+                let mut required = 0usize;
+                $(
+                    required += ::core::mem::size_of::<$ty>() + ::core::mem::align_of::<$ty>() - 1;
+                )+
+                assert!(
+                    required <= <$arena>::CAPACITY,
+                    "the listed types do not fit into the arena"
+                );
+            };
+        };
+    }
+
     impl<const N: usize> Default for StackAllocator<N> {
         fn default() -> Self {
             Self::new()
         }
     }
 }
+
+mod borrowed_ {
+    use core::mem::MaybeUninit;
+
+    use crate::buffer::SliceBuf;
+
+    use super::*;
+
+    /// An arena over memory it does not own, e.g. a long lived `Vec<u8>` reused every frame.
+    pub struct BorrowedAllocator<'a>(ArenaAllocator<SliceBuf<'a, u8>>);
+
+    impl ArenaAllocatorImpl for BorrowedAllocator<'_> {
+        fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+            self.0.bump_alloc(layout)
+        }
+
+        fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+            self.0.dealloc(data, layout)
+        }
+
+        fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+            self.0.try_dealloc(data, layout)
+        }
+
+        fn reset(&mut self) -> AllocRes<()> {
+            self.0.reset()
+        }
+
+        fn contains(&self, ptr: NonNull<u8>) -> bool {
+            self.0.contains(ptr)
+        }
+
+        fn remaining_hint(&self) -> Option<usize> {
+            self.0.remaining_hint()
+        }
+
+        fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+            self.0.alloc_tagged(layout, tag)
+        }
+
+        fn reserve_unrecorded(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+            self.0.reserve_unrecorded(layout)
+        }
+
+        fn record_reserved(&self, data: NonNull<u8>, layout: Layout) {
+            self.0.record_reserved(data, layout)
+        }
+    }
+
+    arena_common_impl!(['a] BorrowedAllocator<'a>);
+
+    impl<'a> BorrowedAllocator<'a> {
+        pub fn from_uninit(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+            Self(ArenaAllocator::new_in(SliceBuf::new(buf)))
+        }
+
+        /// # Safety
+        ///
+        /// `ptr` must be valid for reads and writes of `len` bytes for `'a` and not be accessed
+        /// through any other pointer while the arena is alive. The arena's allocations derive
+        /// their provenance from `ptr`.
+        pub unsafe fn from_raw_parts(ptr: NonNull<u8>, len: usize) -> Self {
+            let buf = NonNull::slice_from_raw_parts(ptr.cast::<MaybeUninit<u8>>(), len);
+            Self::from_uninit(unsafe { &mut *buf.as_ptr() })
+        }
+
+        /// The offset of the block at `data`, see [`ArenaAllocator::offset_of`].
+        pub(crate) fn offset_of(&self, data: NonNull<u8>) -> Option<usize> {
+            self.0.offset_of(data)
+        }
+
+        /// Pads every allocation to a multiple of `min_align`, see [`ArenaAllocator::with_min_align`].
+        pub(crate) fn with_min_align(self, min_align: usize) -> Self {
+            Self(self.0.with_min_align(min_align))
+        }
+
+        /// Tears down the arena and returns its buffer's base, capacity and bump cursor.
+        ///
+        /// The arena can be reconstituted with [`from_raw_parts`](Self::from_raw_parts)
+        /// followed by [`set_offset`](Self::set_offset).
+        pub fn into_raw_parts(self) -> (NonNull<u8>, usize, usize) {
+            let base = unsafe { NonNull::new_unchecked(self.0.buf.as_mut_ptr()) };
+            (base, self.0.buf.len(), self.current_offset())
+        }
+
+        pub fn from_slice(buf: &'a mut [u8]) -> Self {
+            let buf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+            Self::from_uninit(buf)
+        }
+
+        /// Lends the whole capacity of `vec` to the arena.
+        /// `vec` is cleared, its capacity is left untouched so it can be lent again next frame.
+        #[cfg(feature = "alloc")]
+        pub fn borrow_from(vec: &'a mut alloc::vec::Vec<u8>) -> Self {
+            vec.clear();
+            Self::from_uninit(vec.spare_capacity_mut())
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use core::{alloc::Layout, ptr::NonNull};
+
+use crate::{AllocRes, ArenaAllocatorImpl};
+
+/// Spreads allocations over `N` arenas, routing each one to the arena with the most free space.
+///
+/// The free space is sampled through [`remaining_hint`](ArenaAllocatorImpl::remaining_hint),
+/// arenas which cannot report it are only picked once no other arena has room left. If the picked
+/// arena runs out of memory anyway, e.g. as another thread raced for its space, the remaining
+/// arenas are tried in order before the error is returned.
+pub struct Balanced<A, const N: usize> {
+    arenas: [A; N],
+}
+
+impl<A: ArenaAllocatorImpl, const N: usize> Balanced<A, N> {
+    /// Routes over `arenas`, of which there must be at least one, as every request is served from
+    /// one of them:
+    ///
+    /// ```compile_fail
+    /// # use wait_free_arena::{Balanced, StackAllocator};
+    /// let balanced = Balanced::<StackAllocator<8>, 0>::new([]);
+    /// ```
+    pub fn new(arenas: [A; N]) -> Self {
+        const { assert!(N > 0, "a balanced allocator needs at least one arena") };
+        Self { arenas }
+    }
+
+    pub fn arenas(&self) -> &[A; N] {
+        &self.arenas
+    }
+
+    pub fn into_inner(self) -> [A; N] {
+        self.arenas
+    }
+
+    /// The index of the arena with the most free space, ties go to the lower index.
+    fn least_loaded(&self) -> usize {
+        let mut best = (0, None);
+        for (idx, arena) in self.arenas.iter().enumerate() {
+            let remaining = arena.remaining_hint();
+            if remaining > best.1 {
+                best = (idx, remaining);
+            }
+        }
+        best.0
+    }
+
+    fn route(
+        &self,
+        mut alloc: impl FnMut(&A) -> AllocRes<NonNull<[u8]>>,
+    ) -> AllocRes<NonNull<[u8]>> {
+        let first = self.least_loaded();
+        let mut res = alloc(&self.arenas[first]);
+        for (_, arena) in self
+            .arenas
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| *idx != first)
+        {
+            match res {
+                Err(ref err) if err.kind().is_out_of_memory() => res = alloc(arena),
+                _ => break,
+            }
+        }
+        res
+    }
+
+    fn owner(&self, ptr: NonNull<u8>) -> Option<&A> {
+        self.arenas.iter().find(|arena| arena.contains(ptr))
+    }
+}
+
+impl<A: ArenaAllocatorImpl, const N: usize> ArenaAllocatorImpl for Balanced<A, N> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.route(|arena| arena.bump_alloc(layout))
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        if let Some(arena) = self.owner(data) {
+            arena.dealloc(data, layout)
+        }
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.owner(data)
+            .is_some_and(|arena| arena.try_dealloc(data, layout))
+    }
+
+    /// Resets every arena, stopping at the first failure.
+    fn reset(&mut self) -> AllocRes<()> {
+        self.arenas.iter_mut().try_for_each(|arena| arena.reset())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.owner(ptr).is_some()
+    }
+
+    /// The total free space of all arenas which report it.
+    fn remaining_hint(&self) -> Option<usize> {
+        self.arenas
+            .iter()
+            .filter_map(|arena| arena.remaining_hint())
+            .reduce(|total, remaining| total + remaining)
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.route(|arena| arena.alloc_tagged(layout, tag))
+    }
+}
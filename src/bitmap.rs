@@ -0,0 +1,241 @@
+use core::{
+    alloc::Layout,
+    fmt,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl};
+
+const BITS: usize = usize::BITS as usize;
+
+/// An allocator handing out runs of fixed-size blocks, tracked in a lock-free bitmap.
+///
+/// Unlike the bump arenas, every block is reclaimed when it is freed, at the cost of a linear
+/// search over the bitmap per allocation. The bitmap is stored at the front of the lent buffer.
+///
+/// Blocks are claimed with one `fetch_or` per bitmap word, a request racing with another one for
+/// the same bits backs off and continues its search.
+pub struct BitmapAllocator<'a> {
+    map: &'a [AtomicUsize],
+    data: NonNull<u8>,
+    blocks: usize,
+    block_size: usize,
+    _marker: PhantomData<&'a mut [u8]>,
+}
+
+unsafe impl Send for BitmapAllocator<'_> {}
+// Blocks are only handed out after their bits were claimed.
+unsafe impl Sync for BitmapAllocator<'_> {}
+
+impl<'a> BitmapAllocator<'a> {
+    /// Manages `buf` in blocks of `block_size` bytes, every block is aligned to `block_size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `block_size` is not a power of two.
+    pub fn new(buf: &'a mut [MaybeUninit<u8>], block_size: usize) -> Self {
+        assert!(
+            block_size.is_power_of_two(),
+            "the block size must be a power of two"
+        );
+        let base = NonNull::from(&mut *buf).cast::<u8>();
+        let len = buf.len();
+        let addr = base.addr().get();
+        let layout = |blocks: usize| {
+            let map_start = addr.next_multiple_of(align_of::<AtomicUsize>()) - addr;
+            let map_end = map_start + blocks.div_ceil(BITS) * size_of::<AtomicUsize>();
+            let data_start = (addr + map_end).next_multiple_of(block_size) - addr;
+            (map_start.min(len), data_start)
+        };
+        // every block costs one bit of the map on top of its size
+        let mut blocks = len.saturating_mul(8) / (block_size.saturating_mul(8) + 1);
+        while blocks > 0 {
+            let (_, data_start) = layout(blocks);
+            if data_start <= len && blocks * block_size <= len - data_start {
+                break;
+            }
+            blocks -= 1;
+        }
+        let (map_start, data_start) = layout(blocks);
+        let words = blocks.div_ceil(BITS);
+        let map = unsafe { base.add(map_start) }.cast::<AtomicUsize>();
+        for word in 0..words {
+            unsafe { map.add(word).write(AtomicUsize::new(0)) };
+        }
+        let this = Self {
+            map: unsafe { NonNull::slice_from_raw_parts(map, words).as_ref() },
+            data: unsafe { base.add(data_start.min(len)) },
+            blocks,
+            block_size,
+            _marker: PhantomData,
+        };
+        this.mark_tail();
+        this
+    }
+
+    pub fn from_slice(buf: &'a mut [u8], block_size: usize) -> Self {
+        let buf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+        Self::new(buf, block_size)
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block_size
+    }
+
+    /// The number of blocks managed, excluding the space taken by the bitmap.
+    pub fn blocks(&self) -> usize {
+        self.blocks
+    }
+
+    /// Marks the bits past the last block as taken, so they are never claimed.
+    fn mark_tail(&self) {
+        if let Some(last) = self.map.last()
+            && !self.blocks.is_multiple_of(BITS)
+        {
+            last.fetch_or(!0 << (self.blocks % BITS), Ordering::Relaxed);
+        }
+    }
+
+    /// The bits of blocks `start..end` within word `word`.
+    fn mask(word: usize, start: usize, end: usize) -> usize {
+        let lo = start.max(word * BITS) - word * BITS;
+        let hi = end.min((word + 1) * BITS) - word * BITS;
+        (!0 >> (BITS - (hi - lo))) << lo
+    }
+
+    fn is_free(&self, start: usize, end: usize) -> bool {
+        (start / BITS..end.div_ceil(BITS))
+            .all(|word| self.map[word].load(Ordering::Relaxed) & Self::mask(word, start, end) == 0)
+    }
+
+    /// Claims blocks `start..end`, undoing a partial claim if another thread got there first.
+    fn claim(&self, start: usize, end: usize) -> bool {
+        let words = start / BITS..end.div_ceil(BITS);
+        for word in words.clone() {
+            let mask = Self::mask(word, start, end);
+            let prev = self.map[word].fetch_or(mask, Ordering::Acquire);
+            if prev & mask != 0 {
+                self.map[word].fetch_and(!(mask & !prev), Ordering::Relaxed);
+                for claimed in words.start..word {
+                    self.map[claimed]
+                        .fetch_and(!Self::mask(claimed, start, end), Ordering::Relaxed);
+                }
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Claims the first free run of `needed` blocks aligned to `align`, trying the runs
+    /// starting at `first`, `first + stride`, ...
+    pub(crate) fn claim_run(
+        &self,
+        first: usize,
+        stride: usize,
+        needed: usize,
+        align: usize,
+    ) -> Option<NonNull<u8>> {
+        let mut start = first;
+        while start + needed <= self.blocks {
+            let ptr = unsafe { self.data.add(start * self.block_size) };
+            if ptr.addr().get().is_multiple_of(align)
+                && self.is_free(start, start + needed)
+                && self.claim(start, start + needed)
+            {
+                return Some(ptr);
+            }
+            start += stride;
+        }
+        None
+    }
+
+    /// The first block whose address is aligned to `align`, if any.
+    pub(crate) fn first_aligned(&self, align: usize) -> usize {
+        self.data.align_offset(align) / self.block_size
+    }
+
+    pub(crate) fn exhausted(&self, layout: Layout) -> AllocError {
+        AllocError::with_message(
+            AllocErrorKind::InsufficientCapacity {
+                requested: layout.size(),
+                available: self.remaining_hint().unwrap_or(0),
+            },
+            "No free run of blocks large enough",
+        )
+    }
+
+    fn block_range(&self, data: NonNull<u8>, layout: Layout) -> (usize, usize) {
+        let start = (data.addr().get() - self.data.addr().get()) / self.block_size;
+        (start, start + layout.size().div_ceil(self.block_size))
+    }
+}
+
+impl ArenaAllocatorImpl for BitmapAllocator<'_> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        crate::check_align(layout.align())?;
+        if layout.size() == 0 {
+            return Ok(NonNull::slice_from_raw_parts(layout.dangling_ptr(), 0));
+        }
+        let needed = layout.size().div_ceil(self.block_size);
+        // blocks are aligned to `block_size`, larger alignments only fit every `stride` blocks
+        let stride = (layout.align() / self.block_size).max(1);
+        self.claim_run(
+            self.first_aligned(layout.align()),
+            stride,
+            needed,
+            layout.align(),
+        )
+        .map(|ptr| NonNull::slice_from_raw_parts(ptr, layout.size()))
+        .ok_or_else(|| self.exhausted(layout))
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.try_dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        if layout.size() == 0 || !self.contains(data) {
+            return false;
+        }
+        let (start, end) = self.block_range(data, layout);
+        for word in start / BITS..end.div_ceil(BITS) {
+            self.map[word].fetch_and(!Self::mask(word, start, end), Ordering::Release);
+        }
+        true
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        for word in self.map {
+            word.store(0, Ordering::Relaxed);
+        }
+        self.mark_tail();
+        Ok(())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        let base = self.data.addr().get();
+        (base..base + self.blocks * self.block_size).contains(&ptr.addr().get())
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        let free: usize = self
+            .map
+            .iter()
+            .map(|word| word.load(Ordering::Relaxed).count_zeros() as usize)
+            .sum();
+        Some(free * self.block_size)
+    }
+}
+
+impl fmt::Debug for BitmapAllocator<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BitmapAllocator")
+            .field("blocks", &self.blocks)
+            .field("block_size", &self.block_size)
+            .field("free", &self.remaining_hint())
+            .finish()
+    }
+}
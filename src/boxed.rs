@@ -1,33 +1,102 @@
 use core::{
+    alloc::Layout,
     borrow,
     cmp::Ordering,
     fmt,
-    mem::ManuallyDrop,
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
     ops::{Deref, DerefMut},
     pin::Pin,
-    ptr,
+    ptr::{self, NonNull},
 };
 
-use crate::{AllocRes, ArenaAllocatorImpl};
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl, allocator::InitGuard};
 
 pub struct Box<'a, T: ?Sized>(&'a mut T);
 
 impl<'a, T> Box<'a, T> {
+    #[track_caller]
     pub fn new_in<A: ArenaAllocatorImpl>(value: T, alloc: &'a A) -> AllocRes<Self> {
         alloc.alloc_val(value).map(|value_ref| Self(value_ref))
     }
 
+    /// Like [`Box::new_in`], but treats running out of memory as fatal
+    /// by calling [`handle_arena_oom`](crate::handle_arena_oom).
+    #[track_caller]
+    pub fn new_in_unwrap<A: ArenaAllocatorImpl>(value: T, alloc: &'a A) -> Self {
+        Self(alloc.alloc_val_or_oom(value))
+    }
+
+    #[track_caller]
     pub fn pin_in<A: ArenaAllocatorImpl>(value: T, alloc: &'a A) -> AllocRes<Pin<Self>> {
         Self::new_in(value, alloc).map(|boxed| boxed.into())
     }
 
+    /// Constructs the value with knowledge of its final address, e.g. for intrusive structures.
+    ///
+    /// `f` receives a pointer to the still uninitialized slot, which may be stored but
+    /// must not be read from or written to before `f` returns.
+    #[track_caller]
+    pub fn new_with_ptr_in<A: ArenaAllocatorImpl>(
+        f: impl FnOnce(*mut T) -> T,
+        alloc: &'a A,
+    ) -> AllocRes<Self> {
+        let layout = Layout::new::<T>();
+        let thin = alloc.bump_alloc(layout)?.as_mut_ptr() as *mut T;
+        // hands the slot back if `f` panics
+        let mut guard = InitGuard::new(alloc, thin, layout);
+        guard.push(f(thin));
+        Ok(Self(&mut guard.finish()[0]))
+    }
+
     pub fn into_inner(b: Box<'a, T>) -> T {
         let raw = Self::into_raw(b);
         unsafe { ptr::read(raw) }
     }
 }
 
+impl<'a, T> Box<'a, [T]> {
+    /// Allocates a slice of `len` uninitialized elements, to be initialized incrementally
+    /// and converted with [`Box::assume_init`].
+    pub fn new_uninit_slice_in<A: ArenaAllocatorImpl>(
+        len: usize,
+        alloc: &'a A,
+    ) -> AllocRes<Box<'a, [MaybeUninit<T>]>> {
+        let layout = Layout::array::<T>(len).map_err(|_| {
+            AllocError::with_message(AllocErrorKind::Other, "slice layout overflows")
+        })?;
+        let block = alloc.bump_alloc(layout)?;
+        let ptr = ptr::slice_from_raw_parts_mut(block.as_mut_ptr() as *mut MaybeUninit<T>, len);
+        Ok(unsafe { Box::from_raw(ptr) })
+    }
+
+    /// Like [`into_iter`](IntoIterator::into_iter), but hands the slice back to `alloc`, which it
+    /// must have been allocated in, once the iterator is dropped. The space is reused if the slice
+    /// is still the most recent block.
+    pub fn into_iter_in<A: ArenaAllocatorImpl>(b: Self, alloc: &'a A) -> IntoIter<'a, T> {
+        IntoIter {
+            alloc: Some(alloc),
+            ..b.into_iter()
+        }
+    }
+}
+
+impl<'a, T> Box<'a, [MaybeUninit<T>]> {
+    /// # Safety
+    ///
+    /// Every element of the slice must have been initialized.
+    pub unsafe fn assume_init(b: Self) -> Box<'a, [T]> {
+        let raw = Box::into_raw(b);
+        unsafe { Box::from_raw(raw as *mut [T]) }
+    }
+}
+
 impl<'a, T: ?Sized> Box<'a, T> {
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid, initialized `T` that lives for `'a` and is not aliased.
+    /// The returned `Box` takes over dropping the value.
     pub unsafe fn from_raw(ptr: *mut T) -> Self {
         Self(unsafe { &mut *ptr })
     }
@@ -40,6 +109,45 @@ impl<'a, T: ?Sized> Box<'a, T> {
     pub fn leak(b: Box<'a, T>) -> &'a mut T {
         unsafe { &mut *Self::into_raw(b) }
     }
+
+    /// Like [`Box::into_raw`], but returns a [`NonNull`].
+    pub fn into_non_null(b: Box<'a, T>) -> NonNull<T> {
+        NonNull::from(Self::leak(b))
+    }
+
+    /// Reconstructs a box from the pointer returned by [`Box::into_non_null`].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`Box::from_raw`].
+    pub unsafe fn from_non_null(ptr: NonNull<T>) -> Self {
+        unsafe { Self::from_raw(ptr.as_ptr()) }
+    }
+
+    /// A pointer to the value which stays valid for reads and writes while the box is alive,
+    /// as long as the box is not accessed in between.
+    ///
+    /// Like the std counterpart this is an associated function, so it does not shadow methods of `T`.
+    pub fn as_non_null(b: &mut Self) -> NonNull<T> {
+        NonNull::from(&mut *b.0)
+    }
+
+    /// Consumes the box, returning a pointer to the value and the layout of its block,
+    /// e.g. to hand it across FFI and later [`dealloc`](ArenaAllocatorImpl::dealloc) it.
+    pub fn into_parts(b: Box<'a, T>) -> (NonNull<T>, Layout) {
+        let layout = Layout::for_value::<T>(&b);
+        (NonNull::from(Self::leak(b)), layout)
+    }
+
+    /// Reconstructs a box from the parts returned by [`Box::into_parts`].
+    ///
+    /// # Safety
+    ///
+    /// Same as for [`Box::from_raw`], and `layout` must be the layout of the value behind `ptr`.
+    pub unsafe fn from_parts(ptr: NonNull<T>, layout: Layout) -> Self {
+        debug_assert_eq!(layout, Layout::for_value(unsafe { ptr.as_ref() }));
+        unsafe { Self::from_raw(ptr.as_ptr()) }
+    }
 }
 
 impl<'a, 'b, T: ?Sized + PartialEq> PartialEq<Box<'b, T>> for Box<'a, T> {
@@ -177,6 +285,99 @@ impl<'a, T, const N: usize> TryFrom<Box<'a, [T]>> for Box<'a, [T; N]> {
     }
 }
 
+/// Moves the elements out of a boxed slice.
+///
+/// A `Box` does not know the arena it was allocated in, so the memory is only
+/// reclaimed once the arena itself is reset, see [`Box::into_iter_in`] to reclaim it earlier.
+impl<'a, T> IntoIterator for Box<'a, [T]> {
+    type Item = T;
+    type IntoIter = IntoIter<'a, T>;
+
+    fn into_iter(self) -> IntoIter<'a, T> {
+        let len = self.len();
+        let ptr = Box::into_raw(self) as *mut T;
+        IntoIter {
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            start: 0,
+            end: len,
+            len,
+            alloc: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// By-value iterator over a [`Box<[T]>`](Box).
+pub struct IntoIter<'a, T> {
+    ptr: NonNull<T>,
+    start: usize,
+    end: usize,
+    /// The length of the boxed slice, to free it with its layout.
+    len: usize,
+    alloc: Option<&'a dyn ArenaAllocatorImpl>,
+    _marker: PhantomData<&'a mut [T]>,
+}
+
+impl<T> IntoIter<'_, T> {
+    /// The elements which have not been yielded yet.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe {
+            core::slice::from_raw_parts(self.ptr.add(self.start).as_ptr(), self.end - self.start)
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        let value = unsafe { self.ptr.add(self.start).read() };
+        self.start += 1;
+        Some(value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<'_, T> {
+    fn next_back(&mut self) -> Option<T> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(unsafe { self.ptr.add(self.end).read() })
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<'_, T> {}
+
+impl<T> FusedIterator for IntoIter<'_, T> {}
+
+impl<T: fmt::Debug> fmt::Debug for IntoIter<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoIter").field(&self.as_slice()).finish()
+    }
+}
+
+impl<T> Drop for IntoIter<'_, T> {
+    fn drop(&mut self) {
+        let remaining = ptr::slice_from_raw_parts_mut(
+            unsafe { self.ptr.add(self.start).as_ptr() },
+            self.end - self.start,
+        );
+        unsafe { ptr::drop_in_place(remaining) };
+        if let (Some(alloc), Ok(layout)) = (self.alloc, Layout::array::<T>(self.len)) {
+            alloc.try_dealloc(self.ptr.cast(), layout);
+        }
+    }
+}
+
 impl<'a, T: ?Sized> Drop for Box<'a, T> {
     fn drop(&mut self) {
         unsafe {
@@ -185,3 +386,18 @@ impl<'a, T: ?Sized> Drop for Box<'a, T> {
         }
     }
 }
+
+/// Moves a value into an arena, returning an [`AllocRes`] of the [`Box`].
+///
+/// ```
+/// # use wait_free_arena::{StackAllocator, arena_box};
+/// let arena: StackAllocator<16> = StackAllocator::new();
+/// let value = arena_box!([1u8, 2, 3], in &arena).unwrap();
+/// assert_eq!(*value, [1, 2, 3]);
+/// ```
+#[macro_export]
+macro_rules! arena_box {
+    ($value:expr, in $arena:expr $(,)?) => {
+        $crate::boxed::Box::new_in($value, $arena)
+    };
+}
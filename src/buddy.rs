@@ -0,0 +1,96 @@
+use core::{alloc::Layout, fmt, mem::MaybeUninit, ptr::NonNull};
+
+use crate::{AllocRes, ArenaAllocatorImpl, BitmapAllocator};
+
+/// A buddy system allocator, handing out blocks of a power of two multiple of the minimum
+/// block size, placed at offsets which are a multiple of their own size. In a pool which is not
+/// aligned to a request, the offsets count from its first block with the requested alignment.
+///
+/// Rounding every request to a power of two bounds the internal fragmentation to half a block,
+/// and the size aligned placement keeps freed blocks mergeable with their buddy. The blocks are
+/// tracked in the lock-free bitmap of a [`BitmapAllocator`], so merging a freed block with its
+/// buddy needs no extra bookkeeping: it is free as soon as the bits of both halves are clear.
+pub struct BuddyAllocator<'a> {
+    map: BitmapAllocator<'a>,
+}
+
+impl<'a> BuddyAllocator<'a> {
+    /// Manages `buf` with a minimum block size of `min_block` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min_block` is not a power of two.
+    pub fn new(buf: &'a mut [MaybeUninit<u8>], min_block: usize) -> Self {
+        Self {
+            map: BitmapAllocator::new(buf, min_block),
+        }
+    }
+
+    pub fn from_slice(buf: &'a mut [u8], min_block: usize) -> Self {
+        Self {
+            map: BitmapAllocator::from_slice(buf, min_block),
+        }
+    }
+
+    pub fn min_block(&self) -> usize {
+        self.map.block_size()
+    }
+
+    /// The number of minimum sized blocks managed.
+    pub fn blocks(&self) -> usize {
+        self.map.blocks()
+    }
+
+    /// The layout of the buddy block serving `layout`.
+    fn block_layout(&self, layout: Layout) -> Layout {
+        let size = layout.size().next_power_of_two().max(self.map.block_size());
+        unsafe { Layout::from_size_align_unchecked(size, layout.align()) }
+    }
+}
+
+impl ArenaAllocatorImpl for BuddyAllocator<'_> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        crate::check_align(layout.align())?;
+        if layout.size() == 0 {
+            return Ok(NonNull::slice_from_raw_parts(layout.dangling_ptr(), 0));
+        }
+        let block_layout = self.block_layout(layout);
+        let run = block_layout.size() / self.map.block_size();
+        // a pool which is not aligned to `align` has its first fitting run further in
+        let first = self.map.first_aligned(block_layout.align());
+        self.map
+            .claim_run(first, run, run, layout.align())
+            .map(|ptr| NonNull::slice_from_raw_parts(ptr, layout.size()))
+            .ok_or_else(|| self.map.exhausted(layout))
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.try_dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        layout.size() != 0 && self.map.try_dealloc(data, self.block_layout(layout))
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.map.reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.map.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.map.remaining_hint()
+    }
+}
+
+impl fmt::Debug for BuddyAllocator<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BuddyAllocator")
+            .field("blocks", &self.blocks())
+            .field("min_block", &self.min_block())
+            .field("free", &self.remaining_hint())
+            .finish()
+    }
+}
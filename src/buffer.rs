@@ -1,6 +1,9 @@
-use core::{array, cell::UnsafeCell, ptr};
+use core::{cell::UnsafeCell, marker::PhantomData, mem::MaybeUninit, ptr, ptr::NonNull};
 
-pub(crate) use heap_::*;
+#[cfg(feature = "alloc")]
+pub(crate) use heap_::HeapBuf;
+#[cfg(feature = "alloc")]
+pub use heap_::{BackingAllocator, GlobalBacking};
 
 pub(crate) trait Buffer<T> {
     fn as_ptr(&self) -> *const T;
@@ -13,14 +16,24 @@ pub(crate) trait Buffer<T> {
     }
 }
 
+/// Alignment of the buffers arenas allocate themselves, so blocks of the common primitive types
+/// start right at the cursor regardless of where the buffer lands.
+pub(crate) const BUF_ALIGN: usize = 16;
+
+/// Inline storage, kept as `MaybeUninit` as allocations may leave padding bytes
+/// uninitialized, which must not be observed as `T` when the buffer is moved.
+/// Aligned to [`BUF_ALIGN`].
+#[repr(C, align(16))]
 pub(crate) struct StackBuf<const N: usize, T> {
-    inner: UnsafeCell<[T; N]>,
+    inner: UnsafeCell<[MaybeUninit<T>; N]>,
 }
 
-impl<const N: usize, T: Default> StackBuf<N, T> {
-    pub(crate) fn new() -> Self {
+const _: () = assert!(align_of::<StackBuf<0, u8>>() == BUF_ALIGN);
+
+impl<const N: usize> StackBuf<N, u8> {
+    pub(crate) const fn new() -> Self {
         Self {
-            inner: array::from_fn(|_| T::default()).into(),
+            inner: UnsafeCell::new([MaybeUninit::new(0); N]),
         }
     }
 }
@@ -39,23 +52,106 @@ impl<const N: usize, T> Buffer<T> for StackBuf<N, T> {
     }
 }
 
-impl<const N: usize, T: Default> Default for StackBuf<N, T> {
+impl<const N: usize> Default for StackBuf<N, u8> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Memory lent to an arena for the lifetime `'a`.
+pub(crate) struct SliceBuf<'a, T> {
+    ptr: NonNull<[T]>,
+    _marker: PhantomData<&'a mut [MaybeUninit<T>]>,
+}
+
+impl<'a, T> SliceBuf<'a, T> {
+    pub(crate) fn new(slice: &'a mut [MaybeUninit<T>]) -> Self {
+        let len = slice.len();
+        Self {
+            ptr: NonNull::slice_from_raw_parts(NonNull::from(slice).cast(), len),
+            _marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<T: Send> Send for SliceBuf<'_, T> {}
+
+impl<T> Buffer<T> for SliceBuf<'_, T> {
+    fn as_ptr(&self) -> *const T {
+        self.as_mut_ptr()
+    }
+
+    fn as_mut_ptr(&self) -> *mut T {
+        self.ptr.as_ptr() as *mut T
+    }
+
+    fn len(&self) -> usize {
+        self.ptr.len()
+    }
+}
+
 #[cfg(feature = "alloc")]
 mod heap_ {
     use super::*;
-    use alloc::boxed::Box;
-    use core::{alloc::Layout, ptr::NonNull};
+    use alloc::{boxed::Box, vec::Vec};
+    use core::{alloc::Layout, mem::ManuallyDrop};
+
+    /// Source of the memory a [`HeapAllocator`](crate::HeapAllocator) carves its buffer from.
+    ///
+    /// With the `allocator_api` feature this is implemented for every
+    /// [`Allocator`](alloc::alloc::Allocator), so the buffer can come from another arena.
+    ///
+    /// # Safety
+    ///
+    /// `allocate_zeroed` must return either `None` or zeroed memory fitting `layout`,
+    /// which stays valid until it is passed to `deallocate`.
+    pub unsafe trait BackingAllocator {
+        fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+        /// # Safety
+        ///
+        /// `ptr` must have been returned by `allocate_zeroed` of this allocator with the same `layout`.
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+    }
+
+    /// The global allocator, used by default to back a [`HeapAllocator`](crate::HeapAllocator).
+    #[derive(Debug, Default, Clone, Copy)]
+    pub struct GlobalBacking;
+
+    unsafe impl BackingAllocator for GlobalBacking {
+        fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>> {
+            NonNull::new(unsafe { alloc::alloc::alloc_zeroed(layout) })
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { alloc::alloc::dealloc(ptr.as_ptr(), layout) }
+        }
+    }
 
-    pub(crate) struct HeapBuf<T> {
+    #[cfg(feature = "allocator_api")]
+    unsafe impl<A: alloc::alloc::Allocator> BackingAllocator for A {
+        fn allocate_zeroed(&self, layout: Layout) -> Option<NonNull<u8>> {
+            alloc::alloc::Allocator::allocate_zeroed(self, layout)
+                .ok()
+                .map(|ptr| ptr.as_non_null_ptr())
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { alloc::alloc::Allocator::deallocate(self, ptr, layout) }
+        }
+    }
+
+    pub(crate) struct HeapBuf<T, A: BackingAllocator = GlobalBacking> {
         ptr: NonNull<[T]>,
+        /// Alignment the buffer was allocated with, at least that of `T`.
+        align: usize,
+        #[cfg_attr(not(feature = "memory_reuse"), allow(dead_code))]
+        backing: A,
     }
 
-    impl<T> Buffer<T> for HeapBuf<T> {
+    unsafe impl<T: Send, A: BackingAllocator + Send> Send for HeapBuf<T, A> {}
+
+    impl<T, A: BackingAllocator> Buffer<T> for HeapBuf<T, A> {
         fn as_ptr(&self) -> *const T {
             self.as_mut_ptr()
         }
@@ -73,25 +169,96 @@ mod heap_ {
         }
     }
 
-    impl<T: Default> HeapBuf<T> {
-        pub(crate) fn new(size: usize) -> Self {
-            let heap_alloc = (0..size).map(|_| T::default()).collect::<Box<[T]>>();
+    impl<T, A: BackingAllocator> HeapBuf<T, A> {
+        #[cfg_attr(not(feature = "memory_reuse"), allow(dead_code))]
+        fn layout(&self) -> Layout {
+            Layout::array::<T>(self.ptr.len())
+                .and_then(|layout| layout.align_to(self.align))
+                .unwrap()
+        }
+    }
+
+    impl<A: BackingAllocator> HeapBuf<u8, A> {
+        /// Requests already zeroed memory from `backing`, which lets the OS hand out
+        /// zero pages lazily instead of us touching every byte up front.
+        pub(crate) fn zeroed_in(size: usize, align: usize, backing: A) -> Option<Self> {
+            let layout = Layout::from_size_align(size, align).ok()?;
+            let raw = if layout.size() == 0 {
+                layout.dangling_ptr()
+            } else {
+                backing.allocate_zeroed(layout)?
+            };
+            Some(Self {
+                ptr: NonNull::slice_from_raw_parts(raw, size),
+                align,
+                backing,
+            })
+        }
+    }
+
+    impl HeapBuf<u8> {
+        pub(crate) fn zeroed(size: usize) -> Self {
+            Self::zeroed_in(size, BUF_ALIGN, GlobalBacking).unwrap_or_else(|| {
+                alloc::alloc::handle_alloc_error(Layout::array::<u8>(size).unwrap())
+            })
+        }
+
+        /// Hands the allocation back without running the destructor of `HeapBuf`, as its base,
+        /// length and the alignment it was allocated with.
+        pub(crate) fn into_raw_parts(self) -> (NonNull<u8>, usize, usize) {
+            let this = ManuallyDrop::new(self);
+            (this.ptr.as_non_null_ptr(), this.ptr.len(), this.align)
+        }
+
+        /// # Safety
+        ///
+        /// The parts must have been returned by [`into_raw_parts`](Self::into_raw_parts).
+        pub(crate) unsafe fn from_raw_parts(ptr: NonNull<u8>, len: usize, align: usize) -> Self {
+            Self {
+                ptr: NonNull::slice_from_raw_parts(ptr, len),
+                align,
+                backing: GlobalBacking,
+            }
+        }
+
+        /// Adopts the whole capacity of `vec`, zero filling the bytes past its length.
+        pub(crate) fn from_vec(mut vec: Vec<u8>) -> Self {
+            vec.resize(vec.capacity(), 0);
+            Self::from_box(vec.into_boxed_slice())
+        }
+    }
+
+    impl<T> HeapBuf<T> {
+        pub(crate) fn from_box(buf: Box<[T]>) -> Self {
             Self {
-                ptr: NonNull::new(Box::into_raw(heap_alloc)).unwrap(),
+                ptr: NonNull::from(Box::leak(buf)),
+                align: align_of::<T>(),
+                backing: GlobalBacking,
             }
         }
     }
 
+    impl<T: Copy> HeapBuf<T> {
+        /// Hands the buffer back to the caller without running the destructor of `HeapBuf`.
+        /// Over-aligned buffers are copied, as a `Box` must be freed with the layout of `[T]`.
+        pub(crate) fn into_box(self) -> Box<[T]> {
+            if self.align != align_of::<T>() {
+                return Box::from(self.as_slice());
+            }
+            let this = ManuallyDrop::new(self);
+            unsafe { Box::from_raw(this.ptr.as_ptr()) }
+        }
+    }
+
     #[cfg(feature = "memory_reuse")]
-    impl<T> Drop for HeapBuf<T> {
+    impl<T, A: BackingAllocator> Drop for HeapBuf<T, A> {
         /// THIS DOES NO CALL DROP BUT ONLY FREES THE UNDERLYING MEMORY
         fn drop(&mut self) {
-            unsafe {
-                alloc::alloc::dealloc(
-                    self.ptr.as_ptr() as *mut u8,
-                    Layout::array::<T>(self.ptr.len()).unwrap(),
-                )
-            };
+            let layout = self.layout();
+            if layout.size() == 0 {
+                return;
+            }
+            unsafe { self.backing.deallocate(self.ptr.cast(), layout) };
         }
     }
 }
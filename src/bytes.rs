@@ -0,0 +1,62 @@
+//! Zero-copy handoff of arena built payloads to [`bytes::Bytes`].
+//!
+//! `Bytes` may outlive any borrow, so the arena memory it points to must stay valid and unchanged:
+//! - a `Box<'static, [u8]>` points into an arena living in a `static`, which is never freed,
+//! - a [`SharedRegion`] holds a clone of the `Arc` owning the arena. The arena cannot be reset
+//!   (which needs `&mut`) while the clone exists, and the box the region was taken from is
+//!   consumed, so the region is neither handed out again nor written to.
+
+use alloc::sync::Arc;
+use core::ptr::NonNull;
+
+use ::bytes::Bytes;
+
+use crate::{ArenaAllocatorImpl, boxed::Box};
+
+impl From<Box<'static, [u8]>> for Bytes {
+    fn from(b: Box<'static, [u8]>) -> Self {
+        Bytes::from_static(Box::leak(b))
+    }
+}
+
+/// A frozen region of an arena shared through an [`Arc`], which keeps the arena alive for as long
+/// as any [`Bytes`] created from the region.
+pub struct SharedRegion<A> {
+    _arena: Arc<A>,
+    region: NonNull<[u8]>,
+}
+
+// The region is never written to again and the arena is only reachable through the `Arc`.
+unsafe impl<A: Send + Sync> Send for SharedRegion<A> {}
+unsafe impl<A: Send + Sync> Sync for SharedRegion<A> {}
+
+impl<A: ArenaAllocatorImpl> SharedRegion<A> {
+    /// Freezes `region`, which must have been allocated from `arena`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a non-empty `region` does not belong to `arena`.
+    pub fn new(arena: &Arc<A>, region: Box<'_, [u8]>) -> Self {
+        let region = NonNull::from(Box::leak(region));
+        assert!(
+            region.is_empty() || arena.contains(region.as_non_null_ptr()),
+            "the region was not allocated from this arena"
+        );
+        Self {
+            _arena: Arc::clone(arena),
+            region,
+        }
+    }
+}
+
+impl<A> AsRef<[u8]> for SharedRegion<A> {
+    fn as_ref(&self) -> &[u8] {
+        unsafe { self.region.as_ref() }
+    }
+}
+
+impl<A: ArenaAllocatorImpl + Send + Sync + 'static> From<SharedRegion<A>> for Bytes {
+    fn from(region: SharedRegion<A>) -> Self {
+        Bytes::from_owner(region)
+    }
+}
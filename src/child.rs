@@ -0,0 +1,263 @@
+use core::{
+    alloc::Layout,
+    fmt,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl, BorrowedAllocator};
+
+const DONATION_SLOTS: usize = 4;
+
+/// An arena over a region carved out of a parent arena, e.g. for the allocations of a single request.
+///
+/// The region is handed back to the parent as a whole when the child is dropped.
+/// Like any block, it is only reclaimed if it is still the parent's most recent allocation.
+pub struct ChildArena<'a> {
+    arena: BorrowedAllocator<'a>,
+    block: NonNull<[u8]>,
+    parent: &'a dyn ArenaAllocatorImpl,
+    /// Regions donated by siblings, served once `arena` is exhausted.
+    donations: [Donation; DONATION_SLOTS],
+    /// End of the last region donated to a sibling, `reset` never rewinds below it.
+    donated_up_to: AtomicUsize,
+}
+
+impl<'a> ChildArena<'a> {
+    pub(crate) fn new_in(bytes: usize, parent: &'a dyn ArenaAllocatorImpl) -> AllocRes<Self> {
+        let layout = Layout::array::<u8>(bytes).map_err(|_| {
+            AllocError::with_message(AllocErrorKind::Other, "child arena size overflows")
+        })?;
+        let block = parent.bump_alloc(layout)?;
+        // the block is exclusively ours until it is handed back in `drop`
+        let arena = unsafe { BorrowedAllocator::from_raw_parts(block.as_non_null_ptr(), bytes) };
+        Ok(Self {
+            arena,
+            block,
+            parent,
+            donations: [const { Donation::new() }; DONATION_SLOTS],
+            donated_up_to: AtomicUsize::new(0),
+        })
+    }
+
+    /// The arena managing the child's region, e.g. to query its offset or dump it.
+    pub fn arena(&self) -> &BorrowedAllocator<'a> {
+        &self.arena
+    }
+
+    /// Size of the region taken from the parent in bytes.
+    pub fn capacity(&self) -> usize {
+        self.block.len()
+    }
+
+    /// Moves `bytes` of unused capacity from the tail of this child to `other`, e.g. to let a
+    /// coordinator rebalance when one request runs hot.
+    ///
+    /// The bytes are taken with a single allocation, so they are never handed out by both children.
+    /// `other` serves them once its own region is exhausted. Both children must have been carved
+    /// from the same parent, which keeps the donated bytes alive for as long as `other` can use them.
+    /// A child which donated does not hand its region back to the parent when dropped.
+    ///
+    /// Each child can hold a handful of donations, further ones are rejected.
+    pub fn donate_to(&self, other: &ChildArena<'a>, bytes: usize) -> AllocRes<()> {
+        if !ptr::addr_eq(self.parent, other.parent) || ptr::eq(self, other) {
+            return Err(AllocError::with_message(
+                AllocErrorKind::Other,
+                "capacity can only be donated to a sibling",
+            ));
+        }
+        if bytes == 0 {
+            return Ok(());
+        }
+        let slot = other
+            .donations
+            .iter()
+            .find(|slot| slot.claim(bytes))
+            .ok_or(AllocError::with_message(
+                AllocErrorKind::Other,
+                "the receiving arena holds no further donations",
+            ))?;
+        let layout = Layout::array::<u8>(bytes).map_err(|_| {
+            AllocError::with_message(AllocErrorKind::Other, "donation size overflows")
+        })?;
+        match self.arena.bump_alloc(layout) {
+            Ok(region) => {
+                if let Some(start) = self.arena.offset_of(region.as_non_null_ptr()) {
+                    self.donated_up_to
+                        .fetch_max(start + bytes, Ordering::Relaxed);
+                }
+                slot.install(region.as_non_null_ptr());
+                Ok(())
+            }
+            Err(err) => {
+                slot.unclaim();
+                Err(err)
+            }
+        }
+    }
+}
+
+impl ArenaAllocatorImpl for ChildArena<'_> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.arena.bump_alloc(layout).or_else(|err| {
+            self.donations
+                .iter()
+                .find_map(|donation| donation.bump(layout))
+                .ok_or(err)
+        })
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.try_dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        match self
+            .donations
+            .iter()
+            .find(|donation| donation.contains(data))
+        {
+            Some(donation) => donation.try_dealloc(data, layout),
+            None => self.arena.try_dealloc(data, layout),
+        }
+    }
+
+    /// Rewinds the child's own region and all donations it received.
+    /// Bytes donated to siblings stay theirs.
+    fn reset(&mut self) -> AllocRes<()> {
+        self.arena.reset()?;
+        unsafe { self.arena.set_offset(*self.donated_up_to.get_mut()) };
+        for donation in &mut self.donations {
+            *donation.next_free.get_mut() = 0;
+        }
+        Ok(())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.arena.contains(ptr) || self.donations.iter().any(|donation| donation.contains(ptr))
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        let donated: usize = self.donations.iter().map(Donation::remaining).sum();
+        self.arena
+            .remaining_hint()
+            .map(|remaining| remaining + donated)
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.arena.alloc_tagged(layout, tag).or_else(|err| {
+            self.donations
+                .iter()
+                .find_map(|donation| donation.bump(layout))
+                .ok_or(err)
+        })
+    }
+}
+
+impl fmt::Debug for ChildArena<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChildArena")
+            .field("capacity", &self.capacity())
+            .field("used", &self.arena.current_offset())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for ChildArena<'_> {
+    fn drop(&mut self) {
+        // a sibling may still allocate from the bytes donated to it
+        if *self.donated_up_to.get_mut() != 0 {
+            return;
+        }
+        let layout = unsafe { Layout::from_size_align_unchecked(self.block.len(), 1) };
+        self.parent.dealloc(self.block.as_non_null_ptr(), layout);
+    }
+}
+
+/// A region donated by a sibling with its own bump cursor.
+///
+/// A slot is claimed by setting `len`, and becomes visible to allocations once `base` is published.
+struct Donation {
+    base: AtomicPtr<u8>,
+    len: AtomicUsize,
+    next_free: AtomicUsize,
+}
+
+impl Donation {
+    const fn new() -> Self {
+        Self {
+            base: AtomicPtr::new(ptr::null_mut()),
+            len: AtomicUsize::new(0),
+            next_free: AtomicUsize::new(0),
+        }
+    }
+
+    fn claim(&self, len: usize) -> bool {
+        self.len
+            .compare_exchange(0, len, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    fn unclaim(&self) {
+        self.len.store(0, Ordering::Relaxed);
+    }
+
+    fn install(&self, base: NonNull<u8>) {
+        self.base.store(base.as_ptr(), Ordering::Release);
+    }
+
+    fn region(&self) -> Option<(NonNull<u8>, usize)> {
+        let base = NonNull::new(self.base.load(Ordering::Acquire))?;
+        Some((base, self.len.load(Ordering::Relaxed)))
+    }
+
+    fn bump(&self, layout: Layout) -> Option<NonNull<[u8]>> {
+        let (base, len) = self.region()?;
+        let mut cur = self.next_free.load(Ordering::Relaxed);
+        loop {
+            let start =
+                (base.addr().get() + cur).next_multiple_of(layout.align()) - base.addr().get();
+            let end = start.checked_add(layout.size()).filter(|end| *end <= len)?;
+            match self.next_free.compare_exchange_weak(
+                cur,
+                end,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    let ptr = unsafe { base.add(start) };
+                    return Some(NonNull::slice_from_raw_parts(ptr, layout.size()));
+                }
+                Err(actual) => cur = actual,
+            }
+        }
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.region().is_some_and(|(base, len)| {
+            let base = base.addr().get();
+            (base..base + len).contains(&ptr.addr().get())
+        })
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        let Some((base, _)) = self.region() else {
+            return false;
+        };
+        let start = data.addr().get() - base.addr().get();
+        self.next_free
+            .compare_exchange(
+                start + layout.size(),
+                start,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+
+    fn remaining(&self) -> usize {
+        self.region().map_or(0, |(_, len)| {
+            len.saturating_sub(self.next_free.load(Ordering::Relaxed))
+        })
+    }
+}
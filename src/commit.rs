@@ -0,0 +1,59 @@
+use core::{alloc::Layout, fmt, mem::MaybeUninit, ptr::NonNull};
+
+use crate::{AllocRes, ArenaAllocatorImpl};
+
+/// A slot for a `T` which is only kept once committed, e.g. for speculative decoding where
+/// half of the parses are thrown away.
+///
+/// Dropping the token without committing aborts the allocation and hands the block back to the
+/// arena, which reclaims it if nothing was allocated after it. The allocation only shows up in the
+/// arena's stats once it is committed. The slot is only reachable through
+/// the token, so it cannot be written after an abort.
+pub struct CommitToken<'a, T> {
+    slot: NonNull<MaybeUninit<T>>,
+    alloc: &'a dyn ArenaAllocatorImpl,
+}
+
+impl<'a, T> CommitToken<'a, T> {
+    pub(crate) fn new_in(alloc: &'a dyn ArenaAllocatorImpl) -> AllocRes<Self> {
+        let slot = alloc
+            .reserve_unrecorded(Layout::new::<T>())?
+            .as_non_null_ptr()
+            .cast();
+        Ok(Self { slot, alloc })
+    }
+
+    pub fn slot(&mut self) -> &mut MaybeUninit<T> {
+        unsafe { self.slot.as_mut() }
+    }
+
+    /// Keeps the allocation, the slot lives as long as the arena.
+    pub fn commit(self) -> &'a mut MaybeUninit<T> {
+        let mut this = core::mem::ManuallyDrop::new(self);
+        this.alloc
+            .record_reserved(this.slot.cast(), Layout::new::<T>());
+        unsafe { this.slot.as_mut() }
+    }
+
+    /// Writes `value` to the slot and keeps the allocation.
+    pub fn commit_with(self, value: T) -> &'a mut T {
+        self.commit().write(value)
+    }
+
+    /// Discards the allocation, same as dropping the token.
+    pub fn abort(self) {}
+}
+
+impl<T> fmt::Debug for CommitToken<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommitToken")
+            .field("slot", &self.slot)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for CommitToken<'_, T> {
+    fn drop(&mut self) {
+        self.alloc.dealloc(self.slot.cast(), Layout::new::<T>());
+    }
+}
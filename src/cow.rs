@@ -0,0 +1,205 @@
+use core::{alloc::Layout, fmt, ops::Deref};
+
+use crate::{
+    AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl, ArenaString, allocator::InitGuard,
+};
+
+/// Types which can be copied into an arena.
+///
+/// Values copied into an arena are never dropped, just like those allocated with
+/// [`alloc_val`](ArenaAllocatorImpl::alloc_val).
+pub trait ToArena {
+    #[allow(clippy::mut_from_ref)]
+    fn to_arena<'a, A: ArenaAllocatorImpl>(&self, arena: &'a A) -> AllocRes<&'a mut Self>;
+}
+
+impl<T: Clone> ToArena for T {
+    fn to_arena<'a, A: ArenaAllocatorImpl>(&self, arena: &'a A) -> AllocRes<&'a mut Self> {
+        arena.alloc_val(self.clone())
+    }
+}
+
+impl<T: Clone> ToArena for [T] {
+    fn to_arena<'a, A: ArenaAllocatorImpl>(&self, arena: &'a A) -> AllocRes<&'a mut Self> {
+        let layout = Layout::array::<T>(self.len()).map_err(|_| {
+            AllocError::with_message(AllocErrorKind::Other, "slice layout overflows")
+        })?;
+        let thin = arena.bump_alloc(layout)?.as_mut_ptr() as *mut T;
+        let mut guard = InitGuard::new(arena, thin, layout);
+        for elem in self {
+            guard.push(elem.clone());
+        }
+        Ok(guard.finish())
+    }
+}
+
+impl ToArena for str {
+    fn to_arena<'a, A: ArenaAllocatorImpl>(&self, arena: &'a A) -> AllocRes<&'a mut Self> {
+        let bytes = self.as_bytes().to_arena(arena)?;
+        Ok(unsafe { core::str::from_utf8_unchecked_mut(bytes) })
+    }
+}
+
+/// Either a borrowed reference or a copy owned by an arena, e.g. for parsers which mostly
+/// borrow their input but occasionally need to unescape a string.
+pub enum ArenaCow<'a, T: ?Sized> {
+    Borrowed(&'a T),
+    Owned(&'a mut T),
+}
+
+impl<'a, T: ?Sized + ToArena> ArenaCow<'a, T> {
+    /// Copies the value into `arena` if it is still borrowed and returns a mutable reference to the copy.
+    pub fn to_mut<A: ArenaAllocatorImpl>(&mut self, arena: &'a A) -> AllocRes<&mut T> {
+        if let Self::Borrowed(borrowed) = *self {
+            *self = Self::Owned(borrowed.to_arena(arena)?);
+        }
+        match self {
+            Self::Owned(owned) => Ok(owned),
+            Self::Borrowed(_) => unreachable!(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> ArenaCow<'a, T> {
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self, Self::Borrowed(_))
+    }
+
+    pub fn is_owned(&self) -> bool {
+        matches!(self, Self::Owned(_))
+    }
+
+    /// Extracts the reference for the full lifetime `'a`.
+    pub fn into_ref(self) -> &'a T {
+        match self {
+            Self::Borrowed(borrowed) => borrowed,
+            Self::Owned(owned) => owned,
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for ArenaCow<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            Self::Borrowed(borrowed) => borrowed,
+            Self::Owned(owned) => owned,
+        }
+    }
+}
+
+impl<'a, T: ?Sized> From<&'a T> for ArenaCow<'a, T> {
+    fn from(borrowed: &'a T) -> Self {
+        Self::Borrowed(borrowed)
+    }
+}
+
+impl<T: ?Sized + PartialEq> PartialEq for ArenaCow<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for ArenaCow<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for ArenaCow<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
+/// A string which borrows its contents until it is first mutated and then continues as an
+/// [`ArenaString`], e.g. for template engines where most tokens are unmodified slices of the input.
+pub struct ArenaCowStr<'a, A: ArenaAllocatorImpl + ?Sized> {
+    repr: CowStrRepr<'a, A>,
+    alloc: &'a A,
+}
+
+enum CowStrRepr<'a, A: ArenaAllocatorImpl + ?Sized> {
+    Borrowed(&'a str),
+    Owned(ArenaString<'a, A>),
+}
+
+impl<'a, A: ArenaAllocatorImpl + ?Sized> ArenaCowStr<'a, A> {
+    pub fn borrowed(s: &'a str, alloc: &'a A) -> Self {
+        Self {
+            repr: CowStrRepr::Borrowed(s),
+            alloc,
+        }
+    }
+
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.repr, CowStrRepr::Borrowed(_))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.repr {
+            CowStrRepr::Borrowed(borrowed) => borrowed,
+            CowStrRepr::Owned(owned) => owned,
+        }
+    }
+
+    /// Copies the contents into the arena if they are still borrowed.
+    pub fn to_mut(&mut self) -> AllocRes<&mut ArenaString<'a, A>> {
+        if let CowStrRepr::Borrowed(borrowed) = self.repr {
+            self.repr = CowStrRepr::Owned(ArenaString::from_str_in(borrowed, self.alloc)?);
+        }
+        match &mut self.repr {
+            CowStrRepr::Owned(owned) => Ok(owned),
+            CowStrRepr::Borrowed(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the borrowed input or the frozen arena copy.
+    pub fn into_str(self) -> &'a str {
+        match self.repr {
+            CowStrRepr::Borrowed(borrowed) => borrowed,
+            CowStrRepr::Owned(owned) => owned.into_str(),
+        }
+    }
+
+    pub fn push_str(&mut self, s: &str) -> AllocRes<()> {
+        self.to_mut()?.push_str(s)
+    }
+
+    pub fn push(&mut self, c: char) -> AllocRes<()> {
+        self.to_mut()?.push(c)
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> Deref for ArenaCowStr<'_, A> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> PartialEq<str> for ArenaCowStr<'_, A> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> fmt::Write for ArenaCowStr<'_, A> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> fmt::Debug for ArenaCowStr<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> fmt::Display for ArenaCowStr<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
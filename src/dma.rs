@@ -0,0 +1,31 @@
+//! DMA buffer impls for arena allocated boxes.
+//!
+//! A DMA transfer keeps writing to or reading from the buffer's address after the HAL call
+//! returns, so the address must stay valid and unchanged while the transfer runs:
+//! - a [`Box`] only holds a reference into the arena, moving the box does not move its contents,
+//! - the box mutably borrows its block for `'a`, so the block can neither be handed out again
+//!   nor can the arena be reset (which needs `&mut`) while the box is alive,
+//! - the box owns its contents exclusively, so no other code may access them during the transfer.
+//!
+//! HALs additionally require `'static` buffers, as a transfer may outlive a forgotten transfer
+//! handle. This holds for boxes allocated from an arena living in a `static`.
+
+use embedded_dma::{ReadBuffer, ReadTarget, WriteBuffer, WriteTarget};
+
+use crate::boxed::Box;
+
+unsafe impl<T: ReadTarget + ?Sized> ReadBuffer for Box<'_, T> {
+    type Word = T::Word;
+
+    unsafe fn read_buffer(&self) -> (*const Self::Word, usize) {
+        (**self).as_read_buffer()
+    }
+}
+
+unsafe impl<T: WriteTarget + ?Sized> WriteBuffer for Box<'_, T> {
+    type Word = T::Word;
+
+    unsafe fn write_buffer(&mut self) -> (*mut Self::Word, usize) {
+        (**self).as_write_buffer()
+    }
+}
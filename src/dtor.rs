@@ -0,0 +1,139 @@
+use core::{
+    alloc::Layout,
+    cell::Cell,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ptr::{self, NonNull},
+};
+
+use crate::{AllocRes, ArenaAllocatorImpl, allocator::InitGuard};
+
+struct DropEntry {
+    drop_fn: unsafe fn(*mut DropEntry),
+    next: *mut DropEntry,
+}
+
+#[repr(C)]
+struct DropNode<T> {
+    entry: DropEntry,
+    value: T,
+}
+
+unsafe fn drop_node<T>(entry: *mut DropEntry) {
+    unsafe { ptr::drop_in_place(&raw mut (*entry.cast::<DropNode<T>>()).value) };
+}
+
+/// Wraps an arena and runs the destructors of values allocated with
+/// [`alloc_owned`](Self::alloc_owned) and [`alloc_owned_with`](Self::alloc_owned_with) on
+/// [`reset`](ArenaAllocatorImpl::reset) and drop, in reverse allocation order.
+///
+/// Values which need dropping are stored behind a small header linking them into a list
+/// inside the arena. Everything allocated through [`ArenaAllocatorImpl`], e.g. the value of a
+/// [`Box`](crate::boxed::Box), is left to its owner to drop, as with any other arena. As registered values may be `!Send`, the wrapper is neither `Send` nor `Sync`.
+pub struct DropArena<A> {
+    inner: A,
+    head: Cell<*mut DropEntry>,
+    _not_send: PhantomData<*mut ()>,
+}
+
+impl<A: ArenaAllocatorImpl> DropArena<A> {
+    pub fn new(inner: A) -> Self {
+        Self {
+            inner,
+            head: Cell::new(ptr::null_mut()),
+            _not_send: PhantomData,
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// Moves `value` into the arena, its destructor runs once the arena is reset or dropped.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_owned<T>(&self, value: T) -> AllocRes<&mut T> {
+        self.alloc_owned_with(|| value)
+    }
+
+    /// Like [`alloc_owned`](Self::alloc_owned), but constructs the value in place with `f`.
+    ///
+    /// The value is linked only once `f` returned, so a panicking `f` hands its space back
+    /// unlinked and values `f` registers itself are dropped after it.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_owned_with<T>(&self, f: impl FnOnce() -> T) -> AllocRes<&mut T> {
+        if !mem::needs_drop::<T>() {
+            return self.inner.alloc_with(f);
+        }
+        let layout = Layout::new::<DropNode<T>>();
+        let node = self.inner.bump_alloc(layout)?.as_mut_ptr() as *mut DropNode<T>;
+        let mut guard = InitGuard::new(&self.inner, node, layout);
+        let value = f();
+        guard.push(DropNode {
+            entry: DropEntry {
+                drop_fn: drop_node::<T>,
+                next: self.head.get(),
+            },
+            value,
+        });
+        let node = &mut guard.finish()[0];
+        self.head.set(ptr::from_mut(node).cast());
+        Ok(&mut node.value)
+    }
+
+    /// Runs all pending destructors and returns the wrapped arena.
+    pub fn into_inner(self) -> A {
+        let mut this = ManuallyDrop::new(self);
+        this.run_destructors();
+        unsafe { ptr::read(&this.inner) }
+    }
+}
+
+impl<A> DropArena<A> {
+    fn run_destructors(&mut self) {
+        let mut entry = self.head.replace(ptr::null_mut());
+        while !entry.is_null() {
+            unsafe {
+                let next = (*entry).next;
+                ((*entry).drop_fn)(entry);
+                entry = next;
+            }
+        }
+    }
+}
+
+impl<A: ArenaAllocatorImpl> ArenaAllocatorImpl for DropArena<A> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.inner.bump_alloc(layout)
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.inner.dealloc(data, layout)
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.inner.try_dealloc(data, layout)
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.run_destructors();
+        self.inner.reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.inner.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.inner.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.inner.alloc_tagged(layout, tag)
+    }
+}
+
+impl<A> Drop for DropArena<A> {
+    fn drop(&mut self) {
+        self.run_destructors();
+    }
+}
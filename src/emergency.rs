@@ -0,0 +1,83 @@
+use core::{alloc::Layout, fmt, ptr::NonNull};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl, BorrowedAllocator};
+
+/// A region set aside from an arena for error handling paths, e.g. logging a failure or
+/// formatting a panic message once the arena itself is exhausted.
+///
+/// The region is only reachable through the token, so regular allocations from the arena never
+/// eat into it. It is handed back to the arena when the token is dropped, which, like for any
+/// block, only reclaims it if it is still the arena's most recent allocation.
+pub struct EmergencyToken<'a> {
+    arena: BorrowedAllocator<'a>,
+    block: NonNull<[u8]>,
+    parent: &'a dyn ArenaAllocatorImpl,
+}
+
+impl<'a> EmergencyToken<'a> {
+    pub(crate) fn new_in(bytes: usize, parent: &'a dyn ArenaAllocatorImpl) -> AllocRes<Self> {
+        let layout = Layout::from_size_align(bytes, align_of::<usize>()).map_err(|_| {
+            AllocError::with_message(AllocErrorKind::Other, "emergency reserve size overflows")
+        })?;
+        let block = parent.bump_alloc(layout)?;
+        // the block is exclusively ours until it is handed back in `drop`
+        let arena = unsafe { BorrowedAllocator::from_raw_parts(block.as_non_null_ptr(), bytes) };
+        Ok(Self {
+            arena,
+            block,
+            parent,
+        })
+    }
+
+    /// Size of the reserved region in bytes.
+    pub fn capacity(&self) -> usize {
+        self.block.len()
+    }
+}
+
+impl ArenaAllocatorImpl for EmergencyToken<'_> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.arena.bump_alloc(layout)
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.arena.dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.arena.try_dealloc(data, layout)
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.arena.reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.arena.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.arena.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.arena.alloc_tagged(layout, tag)
+    }
+}
+
+impl fmt::Debug for EmergencyToken<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmergencyToken")
+            .field("capacity", &self.capacity())
+            .field("used", &self.arena.current_offset())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for EmergencyToken<'_> {
+    fn drop(&mut self) {
+        let layout =
+            unsafe { Layout::from_size_align_unchecked(self.block.len(), align_of::<usize>()) };
+        self.parent.dealloc(self.block.as_non_null_ptr(), layout);
+    }
+}
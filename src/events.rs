@@ -0,0 +1,207 @@
+use core::{
+    alloc::Layout,
+    fmt,
+    ptr::NonNull,
+    sync::atomic::{AtomicU8, AtomicU32, AtomicUsize, Ordering, fence},
+};
+
+use crate::{AllocRes, ArenaAllocatorImpl, record::thread_id};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Alloc,
+    /// A [`dealloc`](ArenaAllocatorImpl::dealloc), or a
+    /// [`try_dealloc`](ArenaAllocatorImpl::try_dealloc) which reclaimed its block.
+    Dealloc,
+    /// A failed allocation, `addr` is `0`.
+    Oom,
+    /// The allocator added a chunk to serve the following allocation, `addr` is `0` and `size`
+    /// is the chunk's capacity, see [`bump_alloc_growing`](ArenaAllocatorImpl::bump_alloc_growing).
+    Grow,
+}
+
+/// An entry of an [`EventLog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    pub kind: EventKind,
+    /// Address of the block.
+    pub addr: usize,
+    pub size: usize,
+    /// See [`AllocRecord::thread`](crate::AllocRecord::thread).
+    pub thread: u32,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} {} bytes at {:#x} on thread {}",
+            self.kind, self.size, self.addr, self.thread
+        )
+    }
+}
+
+/// Wraps an allocator and keeps its last `N` events in a ring: allocations, frees, failed
+/// allocations and growth, e.g. to dump the recent history from a panic handler when the arena
+/// state looks corrupted.
+///
+/// The ring lives inline, so a log wrapping a [`StackAllocator`](crate::StackAllocator) can be
+/// placed in a `static`. Events are written with plain atomics and may be read while other threads
+/// keep allocating, events overwritten during the read are skipped.
+pub struct EventLog<A, const N: usize> {
+    inner: A,
+    slots: [EventSlot; N],
+    next: AtomicUsize,
+}
+
+/// A seqlock protected event, `seq` is the event's index plus one once it is fully written.
+struct EventSlot {
+    seq: AtomicUsize,
+    kind: AtomicU8,
+    thread: AtomicU32,
+    addr: AtomicUsize,
+    size: AtomicUsize,
+}
+
+impl EventSlot {
+    const fn new() -> Self {
+        Self {
+            seq: AtomicUsize::new(0),
+            kind: AtomicU8::new(0),
+            thread: AtomicU32::new(0),
+            addr: AtomicUsize::new(0),
+            size: AtomicUsize::new(0),
+        }
+    }
+
+    fn write(&self, seq: usize, event: Event) {
+        self.seq.store(0, Ordering::Relaxed);
+        fence(Ordering::Release);
+        self.kind.store(event.kind as u8, Ordering::Relaxed);
+        self.thread.store(event.thread, Ordering::Relaxed);
+        self.addr.store(event.addr, Ordering::Relaxed);
+        self.size.store(event.size, Ordering::Relaxed);
+        self.seq.store(seq, Ordering::Release);
+    }
+
+    fn read(&self, seq: usize) -> Option<Event> {
+        if self.seq.load(Ordering::Acquire) != seq {
+            return None;
+        }
+        let kind = match self.kind.load(Ordering::Relaxed) {
+            0 => EventKind::Alloc,
+            1 => EventKind::Dealloc,
+            2 => EventKind::Oom,
+            _ => EventKind::Grow,
+        };
+        let event = Event {
+            kind,
+            thread: self.thread.load(Ordering::Relaxed),
+            addr: self.addr.load(Ordering::Relaxed),
+            size: self.size.load(Ordering::Relaxed),
+        };
+        fence(Ordering::Acquire);
+        (self.seq.load(Ordering::Relaxed) == seq).then_some(event)
+    }
+}
+
+impl<A: ArenaAllocatorImpl, const N: usize> EventLog<A, N> {
+    pub const fn new(inner: A) -> Self {
+        Self {
+            inner,
+            slots: [const { EventSlot::new() }; N],
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    /// The logged events, oldest first.
+    pub fn events(&self) -> impl Iterator<Item = Event> + '_ {
+        let next = self.next.load(Ordering::Acquire);
+        (next.saturating_sub(N)..next).filter_map(|idx| self.slots[idx % N].read(idx + 1))
+    }
+
+    /// Writes the logged events to `out`, one per line.
+    pub fn dump<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        for event in self.events() {
+            writeln!(out, "{event}")?;
+        }
+        Ok(())
+    }
+
+    fn log(&self, kind: EventKind, addr: usize, size: usize) {
+        if N == 0 {
+            return;
+        }
+        let idx = self.next.fetch_add(1, Ordering::Relaxed);
+        let event = Event {
+            kind,
+            addr,
+            size,
+            thread: thread_id(),
+        };
+        self.slots[idx % N].write(idx + 1, event);
+    }
+
+    fn log_alloc(&self, layout: Layout, res: AllocRes<NonNull<[u8]>>) -> AllocRes<NonNull<[u8]>> {
+        match res {
+            Ok(block) => self.log(EventKind::Alloc, block.addr().get(), layout.size()),
+            Err(_) => self.log(EventKind::Oom, 0, layout.size()),
+        }
+        res
+    }
+}
+
+impl<A: ArenaAllocatorImpl, const N: usize> ArenaAllocatorImpl for EventLog<A, N> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.bump_alloc_growing(layout, &mut |_| {})
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.inner.dealloc(data, layout);
+        self.log(EventKind::Dealloc, data.addr().get(), layout.size());
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        let freed = self.inner.try_dealloc(data, layout);
+        if freed {
+            self.log(EventKind::Dealloc, data.addr().get(), layout.size());
+        }
+        freed
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.inner.reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.inner.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.inner.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.log_alloc(layout, self.inner.alloc_tagged(layout, tag))
+    }
+
+    fn bump_alloc_growing(
+        &self,
+        layout: Layout,
+        grown: &mut dyn FnMut(usize),
+    ) -> AllocRes<NonNull<[u8]>> {
+        let res = self.inner.bump_alloc_growing(layout, &mut |capacity| {
+            self.log(EventKind::Grow, 0, capacity);
+            grown(capacity);
+        });
+        self.log_alloc(layout, res)
+    }
+}
@@ -0,0 +1,144 @@
+use core::{
+    alloc::Layout,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl};
+
+/// Decides which allocations a [`FailingAllocator`] fails.
+///
+/// Implemented for closures taking the number of the call, counting from `1`, and its layout.
+pub trait FailurePolicy {
+    fn should_fail(&self, call: usize, layout: Layout) -> bool;
+}
+
+impl<F: Fn(usize, Layout) -> bool> FailurePolicy for F {
+    fn should_fail(&self, call: usize, layout: Layout) -> bool {
+        self(call, layout)
+    }
+}
+
+/// Fails the `n`th allocation, counting from `1`, and only that one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FailNth(pub usize);
+
+impl FailurePolicy for FailNth {
+    fn should_fail(&self, call: usize, _layout: Layout) -> bool {
+        call == self.0
+    }
+}
+
+/// Fails every allocation with the given probability, drawing from a caller provided RNG
+/// returning uniformly distributed `u32`s, so a seeded RNG reproduces the same failures.
+#[derive(Debug, Clone, Copy)]
+pub struct FailRandomly<R> {
+    threshold: u64,
+    rng: R,
+}
+
+impl<R: Fn() -> u32> FailRandomly<R> {
+    /// `probability` is clamped to `0.0..=1.0`.
+    pub fn new(probability: f32, rng: R) -> Self {
+        let scale = u32::MAX as f32 + 1.0;
+        Self {
+            threshold: (probability.clamp(0.0, 1.0) * scale) as u64,
+            rng,
+        }
+    }
+}
+
+impl<R: Fn() -> u32> FailurePolicy for FailRandomly<R> {
+    fn should_fail(&self, _call: usize, _layout: Layout) -> bool {
+        u64::from((self.rng)()) < self.threshold
+    }
+}
+
+/// Wraps an allocator and makes selected allocations fail as if the arena was exhausted,
+/// to test error handling paths deterministically.
+///
+/// Frees and all other calls are passed through unchanged.
+pub struct FailingAllocator<A, P> {
+    inner: A,
+    policy: P,
+    calls: AtomicUsize,
+    injected: AtomicUsize,
+}
+
+impl<A: ArenaAllocatorImpl, P: FailurePolicy> FailingAllocator<A, P> {
+    pub fn new(inner: A, policy: P) -> Self {
+        Self {
+            inner,
+            policy,
+            calls: AtomicUsize::new(0),
+            injected: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    /// The number of allocations requested so far, including the failed ones.
+    pub fn calls(&self) -> usize {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    /// The number of allocations failed by the policy.
+    pub fn injected(&self) -> usize {
+        self.injected.load(Ordering::Relaxed)
+    }
+
+    fn inject(
+        &self,
+        layout: Layout,
+        alloc: impl FnOnce(&A) -> AllocRes<NonNull<[u8]>>,
+    ) -> AllocRes<NonNull<[u8]>> {
+        let call = self.calls.fetch_add(1, Ordering::Relaxed) + 1;
+        if self.policy.should_fail(call, layout) {
+            self.injected.fetch_add(1, Ordering::Relaxed);
+            return Err(AllocError::with_message(
+                AllocErrorKind::InsufficientCapacity {
+                    requested: layout.size(),
+                    available: self.inner.remaining_hint().unwrap_or(0),
+                },
+                "injected failure",
+            ));
+        }
+        alloc(&self.inner)
+    }
+}
+
+impl<A: ArenaAllocatorImpl, P: FailurePolicy> ArenaAllocatorImpl for FailingAllocator<A, P> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.inject(layout, |inner| inner.bump_alloc(layout))
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.inner.dealloc(data, layout)
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.inner.try_dealloc(data, layout)
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.inner.reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.inner.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.inner.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.inject(layout, |inner| inner.alloc_tagged(layout, tag))
+    }
+}
@@ -0,0 +1,276 @@
+use core::{alloc::Layout, ptr::NonNull};
+
+use crate::{AllocRes, ArenaAllocatorImpl};
+
+/// Serves allocations from `primary` and, once it runs out of memory, from `secondary`,
+/// e.g. a small stack arena for the common case backed by a heap arena or
+/// [`GlobalHeap`] for the rare overflow.
+///
+/// Frees are routed to the allocator [containing](ArenaAllocatorImpl::contains) the block,
+/// and to `secondary` for blocks `primary` does not know.
+#[derive(Debug)]
+pub struct FallbackAllocator<A, B> {
+    primary: A,
+    secondary: B,
+}
+
+impl<A: ArenaAllocatorImpl, B: ArenaAllocatorImpl> FallbackAllocator<A, B> {
+    pub fn new(primary: A, secondary: B) -> Self {
+        Self { primary, secondary }
+    }
+
+    pub fn primary(&self) -> &A {
+        &self.primary
+    }
+
+    pub fn secondary(&self) -> &B {
+        &self.secondary
+    }
+
+    pub fn into_parts(self) -> (A, B) {
+        (self.primary, self.secondary)
+    }
+
+    fn with_fallback(
+        &self,
+        alloc: impl Fn(&dyn ArenaAllocatorImpl) -> AllocRes<NonNull<[u8]>>,
+    ) -> AllocRes<NonNull<[u8]>> {
+        match alloc(&self.primary) {
+            Err(err) if err.kind().is_out_of_memory() => alloc(&self.secondary),
+            res => res,
+        }
+    }
+}
+
+impl<A: ArenaAllocatorImpl, B: ArenaAllocatorImpl> ArenaAllocatorImpl for FallbackAllocator<A, B> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.with_fallback(|alloc| alloc.bump_alloc(layout))
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        if self.primary.contains(data) {
+            self.primary.dealloc(data, layout)
+        } else {
+            self.secondary.dealloc(data, layout)
+        }
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        if self.primary.contains(data) {
+            self.primary.try_dealloc(data, layout)
+        } else {
+            self.secondary.try_dealloc(data, layout)
+        }
+    }
+
+    /// Resets the primary allocator and, if it supports it, the secondary one.
+    fn reset(&mut self) -> AllocRes<()> {
+        self.primary.reset()?;
+        // a secondary which cannot be reset, e.g. a shared handle to another arena, keeps its blocks
+        let _ = self.secondary.reset();
+        Ok(())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.primary.contains(ptr) || self.secondary.contains(ptr)
+    }
+
+    /// The bytes left in the primary allocator, before requests fall back.
+    fn remaining_hint(&self) -> Option<usize> {
+        self.primary.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.with_fallback(|alloc| alloc.alloc_tagged(layout, tag))
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod global_ {
+    use alloc::alloc as global;
+    use core::{
+        cell::UnsafeCell,
+        hint, ptr,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use super::*;
+    use crate::{AllocError, AllocErrorKind};
+
+    /// Precedes every block, linking it into the list of live blocks.
+    #[derive(Clone, Copy)]
+    struct Header {
+        prev: *mut Header,
+        next: *mut Header,
+        /// The layout of the whole allocation, including the header.
+        layout: Layout,
+        /// The distance from the header to the block.
+        offset: usize,
+    }
+
+    impl Header {
+        fn block(this: *mut Header) -> NonNull<u8> {
+            unsafe { NonNull::new_unchecked(this.cast::<u8>().byte_add((*this).offset)) }
+        }
+    }
+
+    /// Serves every block with its own allocation from the global allocator, e.g. as the secondary
+    /// of a [`FallbackAllocator`].
+    ///
+    /// Each block is preceded by a header linking it into a list of live blocks, so pointers handed
+    /// out elsewhere are ignored on free and [`reset`](ArenaAllocatorImpl::reset) frees every block
+    /// still live. Freeing a block unlinks it, so lookups only ever walk the live blocks. The list
+    /// is guarded by a spin lock, which is held for the walk and never across a call into the
+    /// global allocator.
+    pub struct GlobalHeap {
+        locked: AtomicBool,
+        head: UnsafeCell<*mut Header>,
+    }
+
+    // The list is only accessed while holding `locked`, or through `&mut`.
+    unsafe impl Send for GlobalHeap {}
+    unsafe impl Sync for GlobalHeap {}
+
+    impl GlobalHeap {
+        pub const fn new() -> Self {
+            Self {
+                locked: AtomicBool::new(false),
+                head: UnsafeCell::new(ptr::null_mut()),
+            }
+        }
+
+        fn with_blocks<R>(&self, f: impl FnOnce(&mut *mut Header) -> R) -> R {
+            while self
+                .locked
+                .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                hint::spin_loop();
+            }
+            let res = f(unsafe { &mut *self.head.get() });
+            self.locked.store(false, Ordering::Release);
+            res
+        }
+
+        fn headers(head: *mut Header) -> impl Iterator<Item = *mut Header> {
+            let mut node = head;
+            core::iter::from_fn(move || {
+                let current = (!node.is_null()).then_some(node)?;
+                node = unsafe { (*current).next };
+                Some(current)
+            })
+        }
+
+        /// Unlinks the live block starting at `data`, returning its header.
+        fn unlink(&self, data: NonNull<u8>) -> Option<*mut Header> {
+            self.with_blocks(|head| {
+                let node = Self::headers(*head).find(|&node| Header::block(node) == data)?;
+                unsafe {
+                    let Header { prev, next, .. } = *node;
+                    match prev.is_null() {
+                        true => *head = next,
+                        false => (*prev).next = next,
+                    }
+                    if !next.is_null() {
+                        (*next).prev = prev;
+                    }
+                }
+                Some(node)
+            })
+        }
+
+        /// Frees every block still live.
+        fn clear(&mut self) {
+            let mut node = core::mem::replace(self.head.get_mut(), ptr::null_mut());
+            while !node.is_null() {
+                let Header { next, layout, .. } = unsafe { *node };
+                unsafe { global::dealloc(node.cast(), layout) };
+                node = next;
+            }
+        }
+    }
+
+    impl Default for GlobalHeap {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl ArenaAllocatorImpl for GlobalHeap {
+        fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+            if layout.size() == 0 {
+                return Ok(NonNull::slice_from_raw_parts(layout.dangling_ptr(), 0));
+            }
+            let (full, offset) = Layout::new::<Header>().extend(layout).map_err(|_| {
+                AllocError::with_message(AllocErrorKind::Other, "block size overflows")
+            })?;
+            let node = unsafe { global::alloc(full) }.cast::<Header>();
+            if node.is_null() {
+                return Err(AllocError::new(AllocErrorKind::BackingExhausted {
+                    requested: layout.size(),
+                }));
+            }
+            self.with_blocks(|head| unsafe {
+                node.write(Header {
+                    prev: ptr::null_mut(),
+                    next: *head,
+                    layout: full,
+                    offset,
+                });
+                if !head.is_null() {
+                    (**head).prev = node;
+                }
+                *head = node;
+            });
+            Ok(NonNull::slice_from_raw_parts(
+                Header::block(node),
+                layout.size(),
+            ))
+        }
+
+        fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+            self.try_dealloc(data, layout);
+        }
+
+        fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+            if layout.size() == 0 {
+                return false;
+            }
+            let Some(node) = self.unlink(data) else {
+                return false;
+            };
+            unsafe { global::dealloc(node.cast(), (*node).layout) };
+            true
+        }
+
+        fn reset(&mut self) -> AllocRes<()> {
+            self.clear();
+            Ok(())
+        }
+
+        fn contains(&self, ptr: NonNull<u8>) -> bool {
+            self.with_blocks(|head| {
+                Self::headers(*head).any(|node| {
+                    let start = Header::block(node).addr().get();
+                    let end = node.addr() + unsafe { (*node).layout.size() };
+                    (start..end).contains(&ptr.addr().get())
+                })
+            })
+        }
+    }
+
+    impl Drop for GlobalHeap {
+        fn drop(&mut self) {
+            self.clear();
+        }
+    }
+
+    impl core::fmt::Debug for GlobalHeap {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("GlobalHeap").finish_non_exhaustive()
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub use global_::*;
@@ -0,0 +1,103 @@
+use core::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// A row-major two dimensional view over an arena slice, see
+/// [`alloc_2d`](crate::ArenaAllocatorImpl::alloc_2d).
+pub struct Grid<'a, T> {
+    data: &'a mut [T],
+    rows: usize,
+    cols: usize,
+}
+
+impl<'a, T> Grid<'a, T> {
+    /// Views `data` as `rows` rows of `cols` elements each.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data.len() != rows * cols`.
+    pub fn new(data: &'a mut [T], rows: usize, cols: usize) -> Self {
+        assert_eq!(
+            Some(data.len()),
+            rows.checked_mul(cols),
+            "grid shape does not match its data"
+        );
+        Self { data, rows, cols }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        (row < self.rows && col < self.cols).then(|| &self.data[row * self.cols + col])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        (row < self.rows && col < self.cols).then(|| &mut self.data[row * self.cols + col])
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.cols..][..self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.data[row * self.cols..][..self.cols]
+    }
+
+    /// The elements of column `col`, top to bottom.
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(
+            col < self.cols,
+            "column {col} out of bounds for {} columns",
+            self.cols
+        );
+        self.data[col..].iter().step_by(self.cols)
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        self.data
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.data
+    }
+
+    /// The underlying row-major slice.
+    pub fn into_slice(self) -> &'a mut [T] {
+        self.data
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<'_, T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get(row, col).unwrap_or_else(|| {
+            panic!("index ({row}, {col}) out of bounds for a {rows}x{cols} grid")
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<'_, T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        self.get_mut(row, col).unwrap_or_else(|| {
+            panic!("index ({row}, {col}) out of bounds for a {rows}x{cols} grid")
+        })
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Grid<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries((0..self.rows).map(|row| self.row(row)))
+            .finish()
+    }
+}
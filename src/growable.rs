@@ -0,0 +1,264 @@
+use alloc::boxed::Box;
+use core::{
+    alloc::Layout,
+    fmt, mem,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::{
+    AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl, HeapAllocator, HeapAllocatorBuilder,
+};
+
+/// Decides the capacity of the chunks a [`GrowableHeapAllocator`] adds once its newest chunk is
+/// exhausted. The arena makes sure the chunk fits the request it is added for.
+///
+/// Implemented for closures taking the capacity of the newest chunk.
+pub trait GrowthPolicy {
+    fn next_capacity(&self, last: usize) -> usize;
+}
+
+impl<F: Fn(usize) -> usize> GrowthPolicy for F {
+    fn next_capacity(&self, last: usize) -> usize {
+        self(last)
+    }
+}
+
+/// Multiplies the capacity of every new chunk by the given factor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Geometric(pub usize);
+
+impl GrowthPolicy for Geometric {
+    fn next_capacity(&self, last: usize) -> usize {
+        last.saturating_mul(self.0)
+    }
+}
+
+struct Chunk {
+    arena: HeapAllocator,
+    capacity: usize,
+    older: *mut Chunk,
+}
+
+impl Chunk {
+    fn new(arena: HeapAllocator) -> Self {
+        Self {
+            capacity: arena.capacity(),
+            arena,
+            older: ptr::null_mut(),
+        }
+    }
+}
+
+/// A heap arena which never runs out of capacity, for workloads whose peak usage is unknown.
+///
+/// Allocations are bumped from the newest of a chain of [`HeapAllocator`] chunks. Once it is
+/// exhausted, a new chunk sized by the [`GrowthPolicy`] is allocated and pushed onto the chain with
+/// a single CAS, so existing blocks never move. Threads racing to grow both keep their chunk.
+///
+/// Only the most recent block of a chunk is reclaimed on free, chunks are only released by
+/// [`reset`](ArenaAllocatorImpl::reset), which keeps the newest one, and on drop.
+pub struct GrowableHeapAllocator<G = Geometric> {
+    newest: AtomicPtr<Chunk>,
+    growth: G,
+    /// Builds every chunk, with the capacity set per chunk.
+    chunk: HeapAllocatorBuilder,
+}
+
+unsafe impl<G: Send> Send for GrowableHeapAllocator<G> {}
+// Chunks are only pushed while the arena is shared and only released through `&mut`.
+unsafe impl<G: Sync> Sync for GrowableHeapAllocator<G> {}
+
+impl GrowableHeapAllocator {
+    /// Starts with a chunk of `initial` bytes, doubling the capacity of every new chunk.
+    pub fn new(initial: usize) -> Self {
+        Self::with_growth(initial, Geometric(2))
+    }
+}
+
+impl<G: GrowthPolicy> GrowableHeapAllocator<G> {
+    pub fn with_growth(initial: usize, growth: G) -> Self {
+        HeapAllocator::builder()
+            .capacity(initial)
+            .growth(growth)
+            .build()
+    }
+
+    fn from_first(first: HeapAllocator, chunk: HeapAllocatorBuilder, growth: G) -> Self {
+        Self {
+            newest: AtomicPtr::new(Box::into_raw(Box::new(Chunk::new(first)))),
+            growth,
+            chunk,
+        }
+    }
+
+    /// The number of chunks allocated so far.
+    pub fn chunks(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// The total capacity of all chunks.
+    pub fn capacity(&self) -> usize {
+        self.iter().map(|chunk| chunk.capacity).sum()
+    }
+
+    /// The chunks, newest first.
+    fn iter(&self) -> impl Iterator<Item = &Chunk> {
+        let mut next = self.newest.load(Ordering::Acquire);
+        core::iter::from_fn(move || {
+            let chunk = unsafe { next.as_ref()? };
+            next = chunk.older;
+            Some(chunk)
+        })
+    }
+
+    /// Adds a chunk large enough for `layout` and serves the request from it, passing the
+    /// chunk's capacity to `grown` once it is installed.
+    fn grow(
+        &self,
+        newest: &Chunk,
+        layout: Layout,
+        grown: &mut dyn FnMut(usize),
+    ) -> AllocRes<NonNull<[u8]>> {
+        // room for the padded block and for aligning it past the alignment of the chunk's buffer
+        let overflow = AllocError::with_message(AllocErrorKind::Other, "chunk size overflows");
+        let needed = layout
+            .size()
+            .checked_next_multiple_of(self.chunk.get_min_align())
+            .and_then(|size| size.checked_add(layout.align() - 1))
+            .ok_or(overflow)?;
+        let capacity = self.growth.next_capacity(newest.capacity).max(needed);
+        let arena = self.chunk.clone().capacity(capacity).try_build()?;
+        let block = arena.bump_alloc(layout)?;
+        let chunk = Box::into_raw(Box::new(Chunk::new(arena)));
+        let mut older = self.newest.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*chunk).older = older };
+            match self.newest.compare_exchange_weak(
+                older,
+                chunk,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    grown(capacity);
+                    return Ok(block);
+                }
+                Err(actual) => older = actual,
+            }
+        }
+    }
+}
+
+impl HeapAllocatorBuilder {
+    /// Builds a [`GrowableHeapAllocator`] instead, whose first chunk takes the configured
+    /// capacity and whose later chunks are sized by `growth`. Every chunk takes the configured
+    /// alignment and packing.
+    ///
+    /// ```
+    /// # use wait_free_arena::{Geometric, HeapAllocator};
+    /// let arena = HeapAllocator::builder()
+    ///     .capacity(256)
+    ///     .min_align(8)
+    ///     .growth(Geometric(4))
+    ///     .build();
+    /// ```
+    pub fn growth<G: GrowthPolicy>(self, growth: G) -> GrowableHeapAllocatorBuilder<G> {
+        GrowableHeapAllocatorBuilder {
+            chunk: self,
+            growth,
+        }
+    }
+}
+
+/// Configures a [`GrowableHeapAllocator`], see [`HeapAllocatorBuilder::growth`].
+pub struct GrowableHeapAllocatorBuilder<G> {
+    chunk: HeapAllocatorBuilder,
+    growth: G,
+}
+
+impl<G: GrowthPolicy> GrowableHeapAllocatorBuilder<G> {
+    /// # Panics
+    ///
+    /// Same as [`HeapAllocatorBuilder::build`] for the first chunk.
+    pub fn build(self) -> GrowableHeapAllocator<G> {
+        let first = self.chunk.clone().build();
+        GrowableHeapAllocator::from_first(first, self.chunk, self.growth)
+    }
+
+    pub fn try_build(self) -> AllocRes<GrowableHeapAllocator<G>> {
+        let first = self.chunk.clone().try_build()?;
+        Ok(GrowableHeapAllocator::from_first(
+            first,
+            self.chunk,
+            self.growth,
+        ))
+    }
+}
+
+/// Frees `chunk` and all older ones.
+unsafe fn release_chain(mut chunk: *mut Chunk) {
+    while !chunk.is_null() {
+        chunk = unsafe { Box::from_raw(chunk) }.older;
+    }
+}
+
+impl<G: GrowthPolicy> ArenaAllocatorImpl for GrowableHeapAllocator<G> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.bump_alloc_growing(layout, &mut |_| {})
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.try_dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.iter()
+            .find(|chunk| chunk.arena.contains(data))
+            .is_some_and(|chunk| chunk.arena.try_dealloc(data, layout))
+    }
+
+    /// Releases every chunk but the newest, which is reset.
+    fn reset(&mut self) -> AllocRes<()> {
+        let newest = unsafe { &mut **self.newest.get_mut() };
+        unsafe { release_chain(mem::replace(&mut newest.older, ptr::null_mut())) };
+        newest.arena.reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.iter().any(|chunk| chunk.arena.contains(ptr))
+    }
+
+    fn bump_alloc_growing(
+        &self,
+        layout: Layout,
+        grown: &mut dyn FnMut(usize),
+    ) -> AllocRes<NonNull<[u8]>> {
+        let newest = unsafe { &*self.newest.load(Ordering::Acquire) };
+        match newest.arena.bump_alloc(layout) {
+            Err(err) if err.kind().is_out_of_memory() => self.grow(newest, layout, grown),
+            res => res,
+        }
+    }
+
+    /// The bytes left in the newest chunk, before another one is added.
+    fn remaining_hint(&self) -> Option<usize> {
+        self.iter().next()?.arena.remaining_hint()
+    }
+}
+
+impl<G> Drop for GrowableHeapAllocator<G> {
+    fn drop(&mut self) {
+        unsafe { release_chain(*self.newest.get_mut()) }
+    }
+}
+
+impl<G: GrowthPolicy> fmt::Debug for GrowableHeapAllocator<G> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GrowableHeapAllocator")
+            .field("chunks", &self.chunks())
+            .field("capacity", &self.capacity())
+            .field("free", &self.remaining_hint())
+            .finish()
+    }
+}
@@ -0,0 +1,59 @@
+//! Conversions between `heapless` collections and their arena counterparts.
+//!
+//! Elements are moved, so the conversions work for any `T`, not just `Copy` types.
+//! Converting into a `heapless` collection fails if it does not fit, handing back the input.
+
+use crate::{AllocRes, ArenaAllocatorImpl, ArenaString, ArenaVec};
+
+impl<'a, T, A: ArenaAllocatorImpl + ?Sized> ArenaVec<'a, T, A> {
+    /// Moves the elements of `vec` into a new vector in `alloc`.
+    pub fn from_heapless_in<const N: usize>(
+        vec: ::heapless::Vec<T, N>,
+        alloc: &'a A,
+    ) -> AllocRes<Self> {
+        let mut arena_vec = Self::with_capacity_in(vec.len(), alloc)?;
+        for elem in vec {
+            // cannot fail, the capacity is reserved
+            arena_vec.push(elem)?;
+        }
+        Ok(arena_vec)
+    }
+}
+
+impl<'a, T, A: ArenaAllocatorImpl + ?Sized, const N: usize> TryFrom<ArenaVec<'a, T, A>>
+    for ::heapless::Vec<T, N>
+{
+    type Error = ArenaVec<'a, T, A>;
+
+    fn try_from(mut vec: ArenaVec<'a, T, A>) -> Result<Self, Self::Error> {
+        if vec.len() > N {
+            return Err(vec);
+        }
+        let mut out = Self::new();
+        while let Some(elem) = vec.pop() {
+            let _ = out.push(elem);
+        }
+        out.reverse();
+        Ok(out)
+    }
+}
+
+impl<'a, A: ArenaAllocatorImpl + ?Sized> ArenaString<'a, A> {
+    /// Copies `s` into a new string in `alloc`.
+    pub fn from_heapless_in<const N: usize>(
+        s: &::heapless::String<N>,
+        alloc: &'a A,
+    ) -> AllocRes<Self> {
+        Self::from_str_in(s.as_str(), alloc)
+    }
+}
+
+impl<'a, A: ArenaAllocatorImpl + ?Sized, const N: usize> TryFrom<ArenaString<'a, A>>
+    for ::heapless::String<N>
+{
+    type Error = ArenaString<'a, A>;
+
+    fn try_from(s: ArenaString<'a, A>) -> Result<Self, Self::Error> {
+        Self::try_from(s.as_str()).map_err(|()| s)
+    }
+}
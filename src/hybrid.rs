@@ -0,0 +1,77 @@
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    ptr::{self, NonNull},
+};
+use std::alloc::System;
+
+use crate::ArenaAllocatorImpl;
+
+/// A [`GlobalAlloc`] serving allocations of at most `threshold` bytes from an arena
+/// and everything else (including arena OOM) from the [`System`] allocator.
+///
+/// ```no_run
+/// use wait_free_arena::{HybridAllocator, StackAllocator};
+///
+/// #[global_allocator]
+/// static GLOBAL: HybridAllocator<StackAllocator<{ 1 << 20 }>> =
+///     HybridAllocator::new(StackAllocator::new(), 256);
+/// # fn main() {}
+/// ```
+pub struct HybridAllocator<A> {
+    arena: A,
+    threshold: usize,
+}
+
+impl<A> HybridAllocator<A> {
+    pub const fn new(arena: A, threshold: usize) -> Self {
+        Self { arena, threshold }
+    }
+
+    pub fn arena(&self) -> &A {
+        &self.arena
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+unsafe impl<A: ArenaAllocatorImpl + Sync> GlobalAlloc for HybridAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if layout.size() <= self.threshold
+            && let Ok(block) = self.arena.bump_alloc(layout)
+        {
+            return block.as_mut_ptr();
+        }
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if layout.size() <= self.threshold
+            && let Ok(block) = self.arena.bump_alloc_zeroed(layout)
+        {
+            return block.as_mut_ptr();
+        }
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        match NonNull::new(ptr) {
+            Some(data) if self.arena.contains(data) => self.arena.dealloc(data, layout),
+            _ => unsafe { System.dealloc(ptr, layout) },
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let Some(data) = NonNull::new(ptr).filter(|data| self.arena.contains(*data)) else {
+            return unsafe { System.realloc(ptr, layout, new_size) };
+        };
+        let new_layout = unsafe { Layout::from_size_align_unchecked(new_size, layout.align()) };
+        let new_ptr = unsafe { self.alloc(new_layout) };
+        if !new_ptr.is_null() {
+            unsafe { ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size)) };
+            self.arena.dealloc(data, layout);
+        }
+        new_ptr
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{ArenaAllocatorImpl, BorrowedAllocator, StackAllocator};
+
+#[cfg(all(wait_free_arena_isr, feature = "alloc"))]
+compile_error!(
+    "`--cfg wait_free_arena_isr` forbids the global allocator, build with `default-features = false` and without `alloc`"
+);
+
+/// Allocators whose [`bump_alloc`](ArenaAllocatorImpl::bump_alloc) and
+/// [`dealloc`](ArenaAllocatorImpl::dealloc) may be called from interrupt context.
+///
+/// Implementors guarantee that these calls
+/// - never touch the global allocator,
+/// - never panic, failures are reported as errors,
+/// - take no locks and finish in bounded time: the cursor is moved with a bounded number of atomic
+///   operations, none of which is retried in a loop. An interrupt handler therefore never waits on
+///   the code it interrupted.
+///
+/// Building the final binary with `RUSTFLAGS="--cfg wait_free_arena_isr"` turns any build of this
+/// crate with the `alloc` feature into a compile error, so no allocating code path can be reached
+/// by accident. It is a cfg rather than a feature, as features must stay additive.
+///
+/// A library cannot rule out panics at compile time, the absence of panics on the bump and free
+/// path of [`StackAllocator`] is instead proven by the Kani harnesses in `src/verify.rs`, see the
+/// README.
+///
+/// # Safety
+///
+/// Implementors must uphold the guarantees listed above.
+pub unsafe trait IsrSafeAlloc: ArenaAllocatorImpl {}
+
+unsafe impl<const N: usize> IsrSafeAlloc for StackAllocator<N> {}
+unsafe impl IsrSafeAlloc for BorrowedAllocator<'_> {}
+unsafe impl<A: IsrSafeAlloc + ?Sized> IsrSafeAlloc for &A {}
@@ -1,5 +1,6 @@
 #![no_std]
-#![feature(unsafe_cell_access, slice_ptr_get)]
+#![feature(slice_ptr_get, strict_provenance_lints)]
+#![deny(fuzzy_provenance_casts, lossy_provenance_casts)]
 #![cfg_attr(feature = "allocator_api", feature(allocator_api))]
 
 #[cfg(feature = "std")]
@@ -8,19 +9,121 @@ extern crate std;
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+use core::fmt;
+
 mod allocator;
+mod balanced;
+mod bitmap;
 #[cfg(feature = "boxed")]
 pub mod boxed;
+mod buddy;
 mod buffer;
+#[cfg(feature = "bytes")]
+mod bytes;
+mod child;
+mod commit;
+mod cow;
+#[cfg(feature = "embedded-dma")]
+mod dma;
+mod dtor;
+mod emergency;
+mod events;
+mod failing;
+mod fallback;
+mod grid;
+#[cfg(feature = "alloc")]
+mod growable;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "std")]
+mod hybrid;
+mod isr;
+mod mpsc;
+mod once;
+pub mod prelude;
+#[cfg(feature = "std")]
+mod pressure;
+#[cfg(feature = "std")]
+mod profile;
+mod quota;
+mod record;
+mod retry;
+#[cfg(feature = "sanitize")]
+mod sanitize;
+mod scope;
+mod scratch;
+#[cfg(feature = "postcard")]
+mod serialize;
+mod sharded;
+mod slot;
+mod smol_str;
+mod spsc;
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "stress")]
+mod stress;
+mod string;
+mod sub;
+mod tlsf;
+mod track;
+mod tracking;
 mod util;
+mod vec;
+#[cfg(kani)]
+mod verify;
 
 pub use allocator::*;
-use thiserror::Error;
+pub use balanced::*;
+pub use bitmap::*;
+pub use buddy::*;
+#[cfg(feature = "bytes")]
+pub use bytes::*;
+pub use child::*;
+pub use commit::*;
+pub use cow::*;
+pub use dtor::*;
+pub use emergency::*;
+pub use events::*;
+pub use failing::*;
+pub use fallback::*;
+pub use grid::*;
+#[cfg(feature = "alloc")]
+pub use growable::*;
+#[cfg(feature = "std")]
+pub use hybrid::*;
+pub use isr::*;
+pub use mpsc::*;
+pub use once::*;
+#[cfg(feature = "std")]
+pub use pressure::*;
+#[cfg(feature = "std")]
+pub use profile::*;
+pub use quota::*;
+pub use record::*;
+pub use retry::*;
+pub use scope::*;
+pub use scratch::*;
+#[cfg(feature = "postcard")]
+pub use serialize::*;
+pub use sharded::*;
+pub use slot::*;
+pub use smol_str::*;
+pub use spsc::*;
+#[cfg(feature = "stats")]
+pub use stats::*;
+#[cfg(feature = "stress")]
+pub use stress::*;
+pub use string::*;
+pub use sub::*;
+pub use tlsf::*;
+pub use track::*;
+pub use tracking::*;
+pub use util::*;
+pub use vec::*;
 
 pub type AllocRes<T> = Result<T, AllocError>;
 
-#[derive(Error, Debug)]
-#[error("AllocError {} occurred\n {:?}", self.kind, self.msg)]
+#[derive(Debug)]
 pub struct AllocError {
     kind: AllocErrorKind,
     msg: Option<&'static str>,
@@ -37,21 +140,130 @@ impl AllocError {
             msg: Some(msg),
         }
     }
+
+    pub fn kind(&self) -> AllocErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> Option<&'static str> {
+        self.msg
+    }
+
+    /// The stable numeric code of this error's kind, see [`AllocErrorKind::code`].
+    pub fn code(&self) -> u32 {
+        self.kind.code()
+    }
 }
 
-#[cfg(feature = "alloc")]
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AllocError {} occurred\n {:?}", self.kind, self.msg)
+    }
+}
+
+impl core::error::Error for AllocError {}
+
+#[cfg(feature = "embedded-io")]
+impl embedded_io::Error for AllocError {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        if self.kind.is_out_of_memory() {
+            embedded_io::ErrorKind::OutOfMemory
+        } else {
+            embedded_io::ErrorKind::Other
+        }
+    }
+}
+
+#[cfg(feature = "allocator_api")]
 impl From<AllocError> for alloc::alloc::AllocError {
     fn from(_value: AllocError) -> Self {
         alloc::alloc::AllocError
     }
 }
 
-#[derive(Error, Debug)]
+/// Returned by [`dealloc_last`](ArenaAllocatorImpl::dealloc_last) if the block is not the most recent allocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotTail;
+
+impl fmt::Display for NotTail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the block is not the most recent allocation of the arena")
+    }
+}
+
+impl core::error::Error for NotTail {}
+
+/// Returned by `rewind` if the cursor already lies below the checkpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaleCheckpoint;
+
+impl fmt::Display for StaleCheckpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the arena was already rewound past the checkpoint")
+    }
+}
+
+impl core::error::Error for StaleCheckpoint {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum AllocErrorKind {
-    #[error("out of memory to allocate")]
-    OOM,
-    #[error("the passed ptr is invalid")]
+    InsufficientCapacity { requested: usize, available: usize },
     InvalidPtr,
-    #[error("Unknown error")]
+    AlignmentUnsupported { align: usize },
+    QuotaExceeded,
+    ArenaSealed,
+    BackingExhausted { requested: usize },
     Other,
 }
+
+impl fmt::Display for AllocErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InsufficientCapacity {
+                requested,
+                available,
+            } => write!(
+                f,
+                "out of memory to allocate: requested {requested} bytes, {available} available"
+            ),
+            Self::InvalidPtr => f.write_str("the passed ptr is invalid"),
+            Self::AlignmentUnsupported { align } => {
+                write!(f, "the requested alignment of {align} is not supported")
+            }
+            Self::QuotaExceeded => f.write_str("the allocation would exceed the configured quota"),
+            Self::ArenaSealed => f.write_str("the arena is sealed and does not accept allocations"),
+            Self::BackingExhausted { requested } => write!(
+                f,
+                "the backing allocator could not provide {requested} bytes"
+            ),
+            Self::Other => f.write_str("Unknown error"),
+        }
+    }
+}
+
+impl core::error::Error for AllocErrorKind {}
+
+impl AllocErrorKind {
+    /// A stable numeric code identifying the kind, e.g. to pass it across FFI.
+    /// Codes are never reused, `0` is reserved for success.
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::InsufficientCapacity { .. } => 1,
+            Self::InvalidPtr => 2,
+            Self::AlignmentUnsupported { .. } => 3,
+            Self::QuotaExceeded => 4,
+            Self::ArenaSealed => 5,
+            Self::BackingExhausted { .. } => 6,
+            Self::Other => u32::MAX,
+        }
+    }
+
+    /// Whether retrying after memory has been freed could make the allocation succeed.
+    pub fn is_out_of_memory(&self) -> bool {
+        matches!(
+            self,
+            Self::InsufficientCapacity { .. } | Self::QuotaExceeded | Self::BackingExhausted { .. }
+        )
+    }
+}
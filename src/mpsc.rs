@@ -0,0 +1,192 @@
+use core::{
+    alloc::Layout,
+    cell::UnsafeCell,
+    fmt,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicBool, AtomicPtr, Ordering},
+};
+
+use crate::{AllocRes, ArenaAllocatorImpl};
+
+struct Node<T> {
+    next: AtomicPtr<Node<T>>,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+/// A multi-producer single-consumer queue whose nodes are allocated from an arena,
+/// e.g. a message channel for kernels or embedded schedulers without a global allocator.
+///
+/// Pushing is wait-free apart from taking a node: a single `swap` links it into the queue.
+/// Nodes of consumed messages are kept in a pool and reused by later pushes, so a queue with a
+/// bounded number of messages in flight stops allocating once the pool holds enough nodes.
+///
+/// A message whose push is still in progress hides the messages pushed after it from the consumer
+/// until the push completes.
+pub struct MpscQueue<'a, T, A: ArenaAllocatorImpl + ?Sized> {
+    /// The most recently pushed node.
+    head: AtomicPtr<Node<T>>,
+    /// The last consumed node, its value has been taken. Only accessed by the consumer.
+    tail: UnsafeCell<NonNull<Node<T>>>,
+    /// Consumed nodes, linked through `next`.
+    pool: AtomicPtr<Node<T>>,
+    consumer_taken: AtomicBool,
+    alloc: &'a A,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: Send, A: ArenaAllocatorImpl + Sync + ?Sized> Send for MpscQueue<'_, T, A> {}
+// Values are moved between threads, but never shared.
+unsafe impl<T: Send, A: ArenaAllocatorImpl + Sync + ?Sized> Sync for MpscQueue<'_, T, A> {}
+
+impl<'a, T, A: ArenaAllocatorImpl + ?Sized> MpscQueue<'a, T, A> {
+    pub fn new_in(alloc: &'a A) -> AllocRes<Self> {
+        let stub = Self::alloc_node(alloc)?;
+        Ok(Self {
+            head: AtomicPtr::new(stub.as_ptr()),
+            tail: UnsafeCell::new(stub),
+            pool: AtomicPtr::new(ptr::null_mut()),
+            consumer_taken: AtomicBool::new(false),
+            alloc,
+            _marker: PhantomData,
+        })
+    }
+
+    fn alloc_node(alloc: &A) -> AllocRes<NonNull<Node<T>>> {
+        let node = alloc
+            .bump_alloc(Layout::new::<Node<T>>())?
+            .as_non_null_ptr()
+            .cast::<Node<T>>();
+        unsafe {
+            node.write(Node {
+                next: AtomicPtr::new(ptr::null_mut()),
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+            })
+        };
+        Ok(node)
+    }
+
+    pub fn push(&self, value: T) -> AllocRes<()> {
+        let node = match self.take_pooled() {
+            Some(node) => node,
+            None => Self::alloc_node(self.alloc)?,
+        };
+        unsafe {
+            let node = node.as_ref();
+            node.next.store(ptr::null_mut(), Ordering::Relaxed);
+            (*node.value.get()).write(value);
+        }
+        let prev = self.head.swap(node.as_ptr(), Ordering::AcqRel);
+        unsafe { (*prev).next.store(node.as_ptr(), Ordering::Release) };
+        Ok(())
+    }
+
+    /// The consuming end of the queue, `None` while another consumer exists.
+    pub fn consumer(&self) -> Option<MpscConsumer<'_, 'a, T, A>> {
+        self.consumer_taken
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+            .then_some(MpscConsumer { queue: self })
+    }
+
+    /// Takes the next message. Must only be called by the single consumer.
+    unsafe fn pop(&self) -> Option<T> {
+        unsafe {
+            let tail = *self.tail.get();
+            let next = NonNull::new(tail.as_ref().next.load(Ordering::Acquire))?;
+            *self.tail.get() = next;
+            let value = (*next.as_ref().value.get()).assume_init_read();
+            // the producer of `next` is done with `tail` once it linked `next`
+            self.recycle(tail, tail);
+            Some(value)
+        }
+    }
+
+    /// Takes a node from the pool. The whole pool is taken at once, which keeps the
+    /// pool free of ABA races, and the remaining nodes are put back.
+    fn take_pooled(&self) -> Option<NonNull<Node<T>>> {
+        let node = NonNull::new(self.pool.swap(ptr::null_mut(), Ordering::Acquire))?;
+        if let Some(rest) = NonNull::new(unsafe { node.as_ref() }.next.load(Ordering::Relaxed))
+            && self
+                .pool
+                .compare_exchange(
+                    ptr::null_mut(),
+                    rest.as_ptr(),
+                    Ordering::Release,
+                    Ordering::Relaxed,
+                )
+                .is_err()
+        {
+            // the pool was refilled meanwhile, splice the rest in front of it
+            let mut last = rest;
+            while let Some(next) =
+                NonNull::new(unsafe { last.as_ref() }.next.load(Ordering::Relaxed))
+            {
+                last = next;
+            }
+            self.recycle(rest, last);
+        }
+        Some(node)
+    }
+
+    /// Puts the list `first..=last` of consumed nodes into the pool.
+    fn recycle(&self, first: NonNull<Node<T>>, last: NonNull<Node<T>>) {
+        let mut head = self.pool.load(Ordering::Relaxed);
+        loop {
+            unsafe { last.as_ref() }.next.store(head, Ordering::Relaxed);
+            match self.pool.compare_exchange_weak(
+                head,
+                first.as_ptr(),
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(actual) => head = actual,
+            }
+        }
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> fmt::Debug for MpscQueue<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MpscQueue").finish_non_exhaustive()
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> Drop for MpscQueue<'_, T, A> {
+    fn drop(&mut self) {
+        while unsafe { self.pop() }.is_some() {}
+        let layout = Layout::new::<Node<T>>();
+        self.alloc.dealloc(self.tail.get_mut().cast(), layout);
+        let mut node = *self.pool.get_mut();
+        while let Some(free) = NonNull::new(node) {
+            node = unsafe { free.as_ref() }.next.load(Ordering::Relaxed);
+            self.alloc.dealloc(free.cast(), layout);
+        }
+    }
+}
+
+/// The consuming end of an [`MpscQueue`], see [`MpscQueue::consumer`].
+pub struct MpscConsumer<'q, 'a, T, A: ArenaAllocatorImpl + ?Sized> {
+    queue: &'q MpscQueue<'a, T, A>,
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> MpscConsumer<'_, '_, T, A> {
+    /// Takes the oldest message, `None` if the queue is empty or the next push is still in progress.
+    pub fn pop(&mut self) -> Option<T> {
+        unsafe { self.queue.pop() }
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> fmt::Debug for MpscConsumer<'_, '_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MpscConsumer").finish_non_exhaustive()
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> Drop for MpscConsumer<'_, '_, T, A> {
+    fn drop(&mut self) {
+        self.queue.consumer_taken.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,140 @@
+use core::{
+    cell::UnsafeCell,
+    hint,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use crate::{AllocRes, ArenaAllocatorImpl};
+
+const UNINIT: u8 = 0;
+const INITIALIZING: u8 = 1;
+const READY: u8 = 2;
+
+/// A cell which can be written exactly once from any thread and is read lock-free afterwards,
+/// e.g. for late bound configuration living in an arena.
+pub struct ArenaOnce<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Send + Sync> Sync for ArenaOnce<T> {}
+unsafe impl<T: Send> Send for ArenaOnce<T> {}
+
+impl<T> ArenaOnce<T> {
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(UNINIT),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Allocates an empty cell in `alloc`.
+    pub fn new_in<A: ArenaAllocatorImpl>(alloc: &A) -> AllocRes<&Self> {
+        alloc.alloc_val(Self::new()).map(|cell| &*cell)
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        (self.state.load(Ordering::Acquire) == READY)
+            .then(|| unsafe { (*self.value.get()).assume_init_ref() })
+    }
+
+    /// Initializes the cell with `value`, or hands `value` back if it has already been claimed.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        if self
+            .state
+            .compare_exchange(UNINIT, INITIALIZING, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(value);
+        }
+        unsafe { (*self.value.get()).write(value) };
+        self.state.store(READY, Ordering::Release);
+        Ok(())
+    }
+
+    /// Returns the value, initializing it with `f` if the cell is empty.
+    ///
+    /// If another thread is initializing the cell concurrently, this waits for it to finish.
+    /// If the initializing thread panics, a waiting thread takes over with its own `f`.
+    pub fn get_or_init(&self, mut f: impl FnOnce() -> T) -> &T {
+        loop {
+            if let Some(value) = self.get() {
+                return value;
+            }
+            match self.set_with(f) {
+                Ok(()) => return unsafe { (*self.value.get()).assume_init_ref() },
+                Err(unused) => {
+                    f = unused;
+                    hint::spin_loop();
+                }
+            }
+        }
+    }
+
+    /// Hands `f` back if the cell has already been claimed.
+    fn set_with<F: FnOnce() -> T>(&self, f: F) -> Result<(), F> {
+        /// Releases the claim if the initializer panics, so the cell is never observed as initialized.
+        struct Unclaim<'a>(&'a AtomicU8);
+
+        impl Drop for Unclaim<'_> {
+            fn drop(&mut self) {
+                self.0.store(UNINIT, Ordering::Release);
+            }
+        }
+
+        if self
+            .state
+            .compare_exchange(UNINIT, INITIALIZING, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            return Err(f);
+        }
+        let unclaim = Unclaim(&self.state);
+        unsafe { (*self.value.get()).write(f()) };
+        core::mem::forget(unclaim);
+        self.state.store(READY, Ordering::Release);
+        Ok(())
+    }
+}
+
+/// A handle to an arena value which is still under construction, see
+/// [`alloc_cyclic`](ArenaAllocatorImpl::alloc_cyclic).
+pub struct ArenaWeakSlot<'a, T>(&'a ArenaOnce<T>);
+
+impl<'a, T> ArenaWeakSlot<'a, T> {
+    /// The value once construction has finished, `None` while it is still being built.
+    pub fn get(&self) -> Option<&'a T> {
+        self.0.get()
+    }
+}
+
+impl<T> Clone for ArenaWeakSlot<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ArenaWeakSlot<'_, T> {}
+
+pub(crate) fn alloc_cyclic<'a, T, A: ArenaAllocatorImpl>(
+    alloc: &'a A,
+    f: impl FnOnce(&ArenaWeakSlot<'a, T>) -> T,
+) -> AllocRes<&'a T> {
+    let cell = ArenaOnce::new_in(alloc)?;
+    Ok(cell.get_or_init(|| f(&ArenaWeakSlot(cell))))
+}
+
+impl<T> Default for ArenaOnce<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for ArenaOnce<T> {
+    fn drop(&mut self) {
+        if *self.state.get_mut() == READY {
+            unsafe { self.value.get_mut().assume_init_drop() };
+        }
+    }
+}
@@ -0,0 +1,21 @@
+//! The commonly used traits, allocators, collections and macros, for a single glob import:
+//!
+//! ```
+//! use wait_free_arena::prelude::*;
+//!
+//! let arena: StackAllocator<64> = StackAllocator::new();
+//! let mut vec = ArenaVec::with_capacity_in(4, &arena).unwrap();
+//! vec.push(1u8).unwrap();
+//! ```
+
+#[cfg(feature = "allocator_api")]
+pub use crate::std_allocator_impl;
+pub use crate::{
+    AllocError, AllocErrorKind, AllocRes, AnyArena, ArenaAllocatorImpl, ArenaScope, ArenaSmolStr,
+    ArenaString, ArenaVec, BitmapAllocator, BorrowedAllocator, BuddyAllocator, Rewind,
+    StackAllocator, TlsfAllocator, arena_vec, static_arena_fits,
+};
+#[cfg(feature = "alloc")]
+pub use crate::{GrowableHeapAllocator, HeapAllocator, HeapAllocatorBuilder};
+#[cfg(feature = "boxed")]
+pub use crate::{arena_box, boxed::Box};
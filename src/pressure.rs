@@ -0,0 +1,187 @@
+use core::{
+    alloc::Layout,
+    fmt,
+    future::Future,
+    pin::Pin,
+    ptr::NonNull,
+    task::{Context, Poll, Waker},
+};
+use std::{sync::Mutex, vec::Vec};
+
+use crate::{AllocRes, ArenaAllocatorImpl};
+
+/// Wraps an allocator and lets async tasks wait until it has room again, e.g. for producers
+/// sharing a bounded arena which should apply backpressure instead of spinning on OOM errors.
+///
+/// Waiting tasks are woken whenever a free reclaims its block or the allocator is reset,
+/// and check [`remaining_hint`](ArenaAllocatorImpl::remaining_hint) again. Space released without
+/// going through the watcher, e.g. by rewinding the wrapped arena through another handle while
+/// futures borrow the watcher, is announced with [`notify_reset`](Self::notify_reset).
+pub struct PressureWatcher<A> {
+    inner: A,
+    waiters: Mutex<Vec<Waker>>,
+}
+
+impl<A: ArenaAllocatorImpl> PressureWatcher<A> {
+    pub fn new(inner: A) -> Self {
+        Self {
+            inner,
+            waiters: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    /// Resolves once the wrapped allocator reports at least `bytes` of remaining capacity.
+    ///
+    /// Allocators which do not report their remaining capacity resolve immediately.
+    /// Capacity is not reserved, another allocation may take it before the waiting task runs.
+    pub fn wait_for_capacity(&self, bytes: usize) -> WaitForCapacity<'_, A> {
+        WaitForCapacity {
+            watcher: self,
+            bytes,
+        }
+    }
+
+    /// Allocates `layout`, waiting for a free or reset whenever the wrapped allocator is out of
+    /// memory, e.g. for pipeline stages using a bounded arena as a natural throttle.
+    ///
+    /// Errors other than running out of memory complete the future right away.
+    /// Growth of the wrapped allocator does not wake the future, only the next free or reset does.
+    pub fn bump_alloc_async(&self, layout: Layout) -> AllocFuture<'_, A> {
+        AllocFuture {
+            watcher: self,
+            layout,
+        }
+    }
+
+    /// Wakes every waiting task to check the remaining capacity again, after the wrapped
+    /// allocator was reset, rewound or otherwise freed through another handle.
+    pub fn notify_reset(&self) {
+        self.notify();
+    }
+
+    fn has_capacity(&self, bytes: usize) -> bool {
+        self.inner
+            .remaining_hint()
+            .is_none_or(|remaining| remaining >= bytes)
+    }
+
+    /// Registers `waker` to be woken by the next free or reset.
+    fn register(&self, waker: &Waker) {
+        let mut waiters = self.waiters.lock().unwrap_or_else(|e| e.into_inner());
+        if !waiters.iter().any(|waiter| waiter.will_wake(waker)) {
+            waiters.push(waker.clone());
+        }
+    }
+
+    fn notify(&self) {
+        let waiters = core::mem::take(&mut *self.waiters.lock().unwrap_or_else(|e| e.into_inner()));
+        for waker in waiters {
+            waker.wake();
+        }
+    }
+}
+
+/// The future returned by [`PressureWatcher::wait_for_capacity`].
+pub struct WaitForCapacity<'a, A> {
+    watcher: &'a PressureWatcher<A>,
+    bytes: usize,
+}
+
+impl<A: ArenaAllocatorImpl> Future for WaitForCapacity<'_, A> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.watcher.has_capacity(self.bytes) {
+            return Poll::Ready(());
+        }
+        self.watcher.register(cx.waker());
+        // a free between the check and the registration would not have woken us
+        if self.watcher.has_capacity(self.bytes) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+impl<A> fmt::Debug for WaitForCapacity<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitForCapacity")
+            .field("bytes", &self.bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The future returned by [`PressureWatcher::bump_alloc_async`].
+pub struct AllocFuture<'a, A> {
+    watcher: &'a PressureWatcher<A>,
+    layout: Layout,
+}
+
+impl<A: ArenaAllocatorImpl> Future for AllocFuture<'_, A> {
+    type Output = AllocRes<NonNull<[u8]>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.watcher.inner.bump_alloc(self.layout) {
+            Err(err) if err.kind().is_out_of_memory() => {}
+            res => return Poll::Ready(res),
+        }
+        self.watcher.register(cx.waker());
+        match self.watcher.inner.bump_alloc(self.layout) {
+            Err(err) if err.kind().is_out_of_memory() => Poll::Pending,
+            res => Poll::Ready(res),
+        }
+    }
+}
+
+impl<A> fmt::Debug for AllocFuture<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllocFuture")
+            .field("layout", &self.layout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: ArenaAllocatorImpl> ArenaAllocatorImpl for PressureWatcher<A> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.inner.bump_alloc(layout)
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.try_dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        let freed = self.inner.try_dealloc(data, layout);
+        if freed {
+            self.notify();
+        }
+        freed
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.inner.reset()?;
+        self.notify();
+        Ok(())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.inner.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.inner.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.inner.alloc_tagged(layout, tag)
+    }
+}
@@ -0,0 +1,182 @@
+use core::{alloc::Layout, cmp::Reverse, panic::Location, ptr::NonNull};
+use std::{collections::HashMap, io, sync::Mutex, vec::Vec};
+
+use crate::{AllocRes, ArenaAllocatorImpl};
+
+/// Allocation totals attributed to a single call site.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SiteTotals {
+    pub allocations: usize,
+    pub bytes: usize,
+}
+
+/// Wraps an allocator and attributes every successful allocation to the location of its caller.
+///
+/// Call sites are propagated through `#[track_caller]`, so allocations made via
+/// [`alloc_val`](ArenaAllocatorImpl::alloc_val) or [`Box::new_in`](crate::boxed::Box::new_in)
+/// are attributed to the user's code.
+/// Recording takes a lock, this is a profiling aid and not meant for production hot paths.
+pub struct CallSiteProfiler<A> {
+    inner: A,
+    sites: Mutex<HashMap<&'static Location<'static>, SiteTotals>>,
+}
+
+impl<A> CallSiteProfiler<A> {
+    pub fn new(inner: A) -> Self {
+        Self {
+            inner,
+            sites: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    /// The totals recorded so far, sorted by bytes in descending order.
+    pub fn sites(&self) -> Vec<(&'static Location<'static>, SiteTotals)> {
+        let mut sites: Vec<_> = self
+            .sites
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(location, totals)| (*location, *totals))
+            .collect();
+        sites.sort_by_key(|(_, totals)| Reverse(totals.bytes));
+        sites
+    }
+
+    /// Writes the recorded totals as an uncompressed pprof protobuf profile
+    /// with the sample types `alloc_objects/count` and `alloc_space/bytes`.
+    pub fn write_pprof<W: io::Write>(&self, out: &mut W) -> io::Result<()> {
+        let sites = self.sites();
+        let mut strings = StringTable::default();
+        let mut profile = Vec::new();
+
+        for (kind, unit) in [("alloc_objects", "count"), ("alloc_space", "bytes")] {
+            let mut value_type = Vec::new();
+            field_varint(&mut value_type, 1, strings.index(kind));
+            field_varint(&mut value_type, 2, strings.index(unit));
+            field_bytes(&mut profile, 1, &value_type);
+        }
+
+        for (id, (location, totals)) in (1u64..).zip(sites.iter()) {
+            let mut sample = Vec::new();
+            field_varint(&mut sample, 1, id);
+            field_varint(&mut sample, 2, totals.allocations as u64);
+            field_varint(&mut sample, 2, totals.bytes as u64);
+            field_bytes(&mut profile, 2, &sample);
+
+            let mut line = Vec::new();
+            field_varint(&mut line, 1, id);
+            field_varint(&mut line, 2, location.line() as u64);
+            let mut loc = Vec::new();
+            field_varint(&mut loc, 1, id);
+            field_bytes(&mut loc, 4, &line);
+            field_bytes(&mut profile, 4, &loc);
+
+            let name = strings.index(&std::format!("{location}"));
+            let mut function = Vec::new();
+            field_varint(&mut function, 1, id);
+            field_varint(&mut function, 2, name);
+            field_varint(&mut function, 3, name);
+            field_varint(&mut function, 4, strings.index(location.file()));
+            field_bytes(&mut profile, 5, &function);
+        }
+
+        for string in &strings.0 {
+            field_bytes(&mut profile, 6, string.as_bytes());
+        }
+
+        out.write_all(&profile)
+    }
+
+    #[track_caller]
+    fn record(&self, layout: Layout) {
+        let mut sites = self.sites.lock().unwrap_or_else(|e| e.into_inner());
+        let totals = sites.entry(Location::caller()).or_default();
+        totals.allocations += 1;
+        totals.bytes += layout.size();
+    }
+}
+
+impl<A: ArenaAllocatorImpl> ArenaAllocatorImpl for CallSiteProfiler<A> {
+    #[track_caller]
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        let block = self.inner.bump_alloc(layout)?;
+        self.record(layout);
+        Ok(block)
+    }
+
+    #[track_caller]
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        let block = self.inner.alloc_tagged(layout, tag)?;
+        self.record(layout);
+        Ok(block)
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.inner.dealloc(data, layout)
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.inner.try_dealloc(data, layout)
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.inner.reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.inner.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.inner.remaining_hint()
+    }
+}
+
+/// pprof requires the first entry of the string table to be the empty string.
+struct StringTable(Vec<std::string::String>);
+
+impl Default for StringTable {
+    fn default() -> Self {
+        Self(std::vec![std::string::String::new()])
+    }
+}
+
+impl StringTable {
+    fn index(&mut self, s: &str) -> u64 {
+        let idx = match self.0.iter().position(|existing| existing == s) {
+            Some(idx) => idx,
+            None => {
+                self.0.push(s.into());
+                self.0.len() - 1
+            }
+        };
+        idx as u64
+    }
+}
+
+fn varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+fn field_varint(buf: &mut Vec<u8>, field: u64, value: u64) {
+    varint(buf, field << 3);
+    varint(buf, value);
+}
+
+fn field_bytes(buf: &mut Vec<u8>, field: u64, bytes: &[u8]) {
+    varint(buf, (field << 3) | 2);
+    varint(buf, bytes.len() as u64);
+    buf.extend_from_slice(bytes);
+}
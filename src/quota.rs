@@ -0,0 +1,143 @@
+use core::{
+    alloc::Layout,
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl};
+
+/// Wraps an allocator and limits the bytes it hands out, e.g. to give each tenant of a shared
+/// arena its own budget. Allocations past the quota fail with
+/// [`QuotaExceeded`](AllocErrorKind::QuotaExceeded).
+///
+/// Bytes count against the quota until a free reclaims them or the allocator is reset.
+/// Once [sealed](Self::seal), every allocation fails with
+/// [`ArenaSealed`](AllocErrorKind::ArenaSealed), e.g. after building a structure which must not
+/// grow any further. Frees are still passed through.
+pub struct QuotaAllocator<A> {
+    inner: A,
+    quota: usize,
+    used: AtomicUsize,
+    sealed: AtomicBool,
+}
+
+impl<A: ArenaAllocatorImpl> QuotaAllocator<A> {
+    pub const fn new(inner: A, quota: usize) -> Self {
+        Self {
+            inner,
+            quota,
+            used: AtomicUsize::new(0),
+            sealed: AtomicBool::new(false),
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    pub fn quota(&self) -> usize {
+        self.quota
+    }
+
+    /// The bytes currently counted against the quota.
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Rejects all further allocations, the seal is kept across [`reset`](ArenaAllocatorImpl::reset).
+    pub fn seal(&self) {
+        self.sealed.store(true, Ordering::Release);
+    }
+
+    pub fn is_sealed(&self) -> bool {
+        self.sealed.load(Ordering::Acquire)
+    }
+
+    /// Charges `layout` against the quota and serves it with `alloc`, refunding it on failure.
+    fn charge(
+        &self,
+        layout: Layout,
+        alloc: impl FnOnce(&A) -> AllocRes<NonNull<[u8]>>,
+    ) -> AllocRes<NonNull<[u8]>> {
+        if self.is_sealed() {
+            return Err(AllocError::new(AllocErrorKind::ArenaSealed));
+        }
+        let size = layout.size();
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(size).filter(|&used| used <= self.quota)
+            })
+            .map_err(|_| AllocError::new(AllocErrorKind::QuotaExceeded))?;
+        alloc(&self.inner).inspect_err(|_| {
+            self.used.fetch_sub(size, Ordering::Relaxed);
+        })
+    }
+}
+
+impl<A: ArenaAllocatorImpl> ArenaAllocatorImpl for QuotaAllocator<A> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.charge(layout, |inner| inner.bump_alloc(layout))
+    }
+
+    /// Refunds the block only if the free reclaims it.
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        if !self.try_dealloc(data, layout) {
+            self.inner.dealloc(data, layout)
+        }
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        let freed = self.inner.try_dealloc(data, layout);
+        if freed {
+            self.used.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+        freed
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.inner.reset()?;
+        *self.used.get_mut() = 0;
+        Ok(())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.inner.contains(ptr)
+    }
+
+    /// The smaller of the inner allocator's hint and the bytes left in the quota.
+    fn remaining_hint(&self) -> Option<usize> {
+        let left = match self.is_sealed() {
+            true => 0,
+            false => self.quota.saturating_sub(self.used()),
+        };
+        Some(
+            self.inner
+                .remaining_hint()
+                .map_or(left, |hint| hint.min(left)),
+        )
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.charge(layout, |inner| inner.alloc_tagged(layout, tag))
+    }
+
+    fn reserve_unrecorded(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.charge(layout, |inner| inner.reserve_unrecorded(layout))
+    }
+
+    fn record_reserved(&self, data: NonNull<u8>, layout: Layout) {
+        self.inner.record_reserved(data, layout)
+    }
+
+    fn bump_alloc_growing(
+        &self,
+        layout: Layout,
+        grown: &mut dyn FnMut(usize),
+    ) -> AllocRes<NonNull<[u8]>> {
+        self.charge(layout, |inner| inner.bump_alloc_growing(layout, grown))
+    }
+}
@@ -0,0 +1,166 @@
+use core::{
+    alloc::Layout,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr::NonNull,
+    slice,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{AllocRes, ArenaAllocatorImpl};
+
+/// A single allocation request seen by a [`Recorder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocRecord {
+    pub size: usize,
+    pub align: usize,
+    /// A small id of the requesting thread, numbered in order of first allocation.
+    /// Always `0` without the `std` feature.
+    pub thread: u32,
+    /// Whether the request failed.
+    pub failed: bool,
+}
+
+impl AllocRecord {
+    pub fn layout(&self) -> Layout {
+        Layout::from_size_align(self.size, self.align).expect("recorded from a valid layout")
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) fn thread_id() -> u32 {
+    use core::sync::atomic::AtomicU32;
+
+    static NEXT: AtomicU32 = AtomicU32::new(0);
+    std::thread_local! {
+        static ID: u32 = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+    ID.with(|id| *id)
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn thread_id() -> u32 {
+    0
+}
+
+/// Wraps an allocator and logs every allocation request into a caller provided buffer,
+/// to reproduce ordering dependent bugs or benchmark configurations with real traces via [`replay`].
+///
+/// Requests past the end of the buffer are counted in [`dropped`](Self::dropped) but not logged.
+pub struct Recorder<'b, A> {
+    inner: A,
+    log: NonNull<[MaybeUninit<AllocRecord>]>,
+    next: AtomicUsize,
+    _marker: PhantomData<&'b mut [MaybeUninit<AllocRecord>]>,
+}
+
+unsafe impl<A: Send> Send for Recorder<'_, A> {}
+// Every slot of the log is claimed by exactly one thread through `next`.
+unsafe impl<A: Sync> Sync for Recorder<'_, A> {}
+
+impl<'b, A: ArenaAllocatorImpl> Recorder<'b, A> {
+    pub fn new(inner: A, log: &'b mut [MaybeUninit<AllocRecord>]) -> Self {
+        Self {
+            inner,
+            log: NonNull::from(log),
+            next: AtomicUsize::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    /// The requests logged so far, in the order their slots were claimed.
+    pub fn records(&mut self) -> &[AllocRecord] {
+        let len = self.logged();
+        unsafe { slice::from_raw_parts(self.log.as_ptr().cast(), len) }
+    }
+
+    /// The number of requests which did not fit into the log.
+    pub fn dropped(&self) -> usize {
+        self.next
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.log.len())
+    }
+
+    /// Stops recording and returns the wrapped allocator and the log.
+    pub fn into_parts(mut self) -> (A, &'b [AllocRecord]) {
+        let len = self.logged();
+        let log = unsafe { slice::from_raw_parts(self.log.as_ptr().cast(), len) };
+        (self.inner, log)
+    }
+
+    fn logged(&mut self) -> usize {
+        (*self.next.get_mut()).min(self.log.len())
+    }
+
+    fn record(&self, layout: Layout, res: AllocRes<NonNull<[u8]>>) -> AllocRes<NonNull<[u8]>> {
+        let idx = self.next.fetch_add(1, Ordering::Relaxed);
+        if idx < self.log.len() {
+            let record = AllocRecord {
+                size: layout.size(),
+                align: layout.align(),
+                thread: thread_id(),
+                failed: res.is_err(),
+            };
+            unsafe { (*self.log.as_ptr())[idx].write(record) };
+        }
+        res
+    }
+}
+
+impl<A: ArenaAllocatorImpl> ArenaAllocatorImpl for Recorder<'_, A> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.record(layout, self.inner.bump_alloc(layout))
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.inner.dealloc(data, layout)
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.inner.try_dealloc(data, layout)
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.inner.reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.inner.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.inner.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.record(layout, self.inner.alloc_tagged(layout, tag))
+    }
+}
+
+/// The outcome of a [`replay`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayReport {
+    pub allocations: usize,
+    pub failures: usize,
+    /// Index of the first record which failed during the replay.
+    pub first_failure: Option<usize>,
+}
+
+/// Reissues `records` against `arena` from the calling thread, in log order.
+pub fn replay<A: ArenaAllocatorImpl>(records: &[AllocRecord], arena: &A) -> ReplayReport {
+    let mut report = ReplayReport::default();
+    for (idx, record) in records.iter().enumerate() {
+        match arena.bump_alloc(record.layout()) {
+            Ok(_) => report.allocations += 1,
+            Err(_) => {
+                report.failures += 1;
+                report.first_failure.get_or_insert(idx);
+            }
+        }
+    }
+    report
+}
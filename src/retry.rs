@@ -0,0 +1,98 @@
+use core::{alloc::Layout, ptr::NonNull};
+
+use crate::{AllocError, AllocRes, ArenaAllocatorImpl};
+
+/// What a [`RetryAllocator`] should do after its callback handled an OOM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OomAction {
+    /// Memory may have been freed, try the allocation again.
+    Retry,
+    /// Give up and return the error to the caller.
+    Fail,
+}
+
+/// Wraps an allocator and invokes `on_oom` whenever an allocation runs out of memory.
+///
+/// The callback may free memory (flush a cache, reset a scratch arena, ...) and ask for the
+/// allocation to be retried, at most `max_retries` times per allocation.
+pub struct RetryAllocator<A, F> {
+    inner: A,
+    on_oom: F,
+    max_retries: usize,
+}
+
+impl<A, F> RetryAllocator<A, F>
+where
+    A: ArenaAllocatorImpl,
+    F: Fn(&A, Layout, &AllocError) -> OomAction,
+{
+    pub fn new(inner: A, max_retries: usize, on_oom: F) -> Self {
+        Self {
+            inner,
+            on_oom,
+            max_retries,
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    fn with_retries(
+        &self,
+        layout: Layout,
+        mut alloc: impl FnMut(&A) -> AllocRes<NonNull<[u8]>>,
+    ) -> AllocRes<NonNull<[u8]>> {
+        let mut attempt = 0;
+        loop {
+            match alloc(&self.inner) {
+                Err(err)
+                    if err.kind().is_out_of_memory()
+                        && attempt < self.max_retries
+                        && (self.on_oom)(&self.inner, layout, &err) == OomAction::Retry =>
+                {
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+}
+
+impl<A, F> ArenaAllocatorImpl for RetryAllocator<A, F>
+where
+    A: ArenaAllocatorImpl,
+    F: Fn(&A, Layout, &AllocError) -> OomAction,
+{
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.with_retries(layout, |inner| inner.bump_alloc(layout))
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.inner.dealloc(data, layout)
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.inner.try_dealloc(data, layout)
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.inner.reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.inner.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.inner.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.with_retries(layout, |inner| inner.alloc_tagged(layout, tag))
+    }
+}
@@ -0,0 +1,130 @@
+//! Passthrough of arena allocations to the global allocator, for sanitizers.
+//!
+//! With the `sanitize` feature every non-empty block is served by its own allocation from the
+//! global allocator with the exact requested layout, so ASan and Miri see each block with its own
+//! redzones and report out of bounds accesses and uses after free precisely.
+//!
+//! The arena still moves its cursor as usual, so capacity, OOM behaviour and reclaiming the most
+//! recent block are unchanged. The arena's own buffer is never written to. Freeing a block
+//! returns it to the global allocator right away, even if the arena cannot reclaim its space,
+//! resetting or dropping the arena frees the rest.
+
+use alloc::{alloc as global, boxed::Box};
+use core::{
+    alloc::Layout,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicBool, AtomicPtr, Ordering},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes};
+
+pub(crate) struct Node {
+    block: NonNull<u8>,
+    layout: Layout,
+    /// The block the arena handed out, used for its bookkeeping.
+    shadow: NonNull<u8>,
+    freed: AtomicBool,
+    next: *mut Node,
+}
+
+/// The passthrough blocks of an arena, most recent first.
+///
+/// Nodes are only pushed while the arena is shared and only removed through `&mut`.
+pub(crate) struct Passthrough {
+    head: AtomicPtr<Node>,
+}
+
+impl Node {
+    /// The block the arena handed out for this one.
+    pub(crate) fn shadow(&self) -> NonNull<u8> {
+        self.shadow
+    }
+
+    /// Returns the block to the global allocator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the block was already freed.
+    pub(crate) fn release(&self) {
+        let freed = self.freed.swap(true, Ordering::AcqRel);
+        assert!(!freed, "double free of the arena block at {:p}", self.block);
+        unsafe { global::dealloc(self.block.as_ptr(), self.layout) };
+    }
+}
+
+impl Passthrough {
+    pub(crate) const fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    /// Replaces the arena's `shadow` block with a fresh global allocation.
+    pub(crate) fn alloc(&self, layout: Layout, shadow: NonNull<[u8]>) -> AllocRes<NonNull<[u8]>> {
+        if layout.size() == 0 {
+            return Ok(shadow);
+        }
+        let block = NonNull::new(unsafe { global::alloc(layout) }).ok_or(AllocError::new(
+            AllocErrorKind::BackingExhausted {
+                requested: layout.size(),
+            },
+        ))?;
+        let node = Box::into_raw(Box::new(Node {
+            block,
+            layout,
+            shadow: shadow.as_non_null_ptr(),
+            freed: AtomicBool::new(false),
+            next: self.head.load(Ordering::Relaxed),
+        }));
+        let mut head = unsafe { (*node).next };
+        while let Err(actual) =
+            self.head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+        {
+            head = actual;
+            unsafe { (*node).next = head };
+        }
+        Ok(NonNull::slice_from_raw_parts(block, layout.size()))
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = &Node> {
+        let mut node = self.head.load(Ordering::Acquire);
+        core::iter::from_fn(move || {
+            let current = unsafe { node.as_ref()? };
+            node = current.next;
+            Some(current)
+        })
+    }
+
+    /// The most recent passthrough block starting at `ptr`, `None` if `ptr` was not handed out by
+    /// the passthrough.
+    pub(crate) fn find(&self, ptr: NonNull<u8>) -> Option<&Node> {
+        self.nodes().find(|node| node.block == ptr)
+    }
+
+    pub(crate) fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.nodes().any(|node| {
+            let start = node.block.addr().get();
+            !node.freed.load(Ordering::Relaxed)
+                && (start..start + node.layout.size()).contains(&ptr.addr().get())
+        })
+    }
+
+    /// Frees all passthrough blocks which are still live.
+    pub(crate) fn clear(&mut self) {
+        let mut node = core::mem::replace(self.head.get_mut(), ptr::null_mut());
+        while !node.is_null() {
+            let current = unsafe { Box::from_raw(node) };
+            if !current.freed.load(Ordering::Relaxed) {
+                unsafe { global::dealloc(current.block.as_ptr(), current.layout) };
+            }
+            node = current.next;
+        }
+    }
+}
+
+impl Drop for Passthrough {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
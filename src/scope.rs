@@ -0,0 +1,119 @@
+use core::{alloc::Layout, fmt, ptr::NonNull};
+
+use crate::{AllocRes, ArenaAllocatorImpl, Checkpoint, StaleCheckpoint};
+
+/// An arena whose bump cursor can be captured and restored, see [`ArenaScope`].
+pub trait Rewind: ArenaAllocatorImpl {
+    /// Captures the bump cursor.
+    fn checkpoint(&self) -> Checkpoint;
+    /// Frees every allocation made since `checkpoint` was taken, failing if the cursor already
+    /// lies below it.
+    ///
+    /// # Safety
+    ///
+    /// No allocation made after `checkpoint` may be used afterwards.
+    unsafe fn rewind(&self, checkpoint: Checkpoint) -> Result<(), StaleCheckpoint>;
+}
+
+/// A temporary allocation region of an arena, rewinding the arena to where the scope was opened
+/// when it is dropped.
+///
+/// The scope borrows the arena exclusively, so every allocation made while it is open comes from
+/// the scope and cannot outlive it. Scopes nest like a frame stack:
+///
+/// ```
+/// # use wait_free_arena::{ArenaVec, StackAllocator};
+/// let mut arena = StackAllocator::<64>::new();
+/// let mut frame = arena.scope();
+/// let mut outer = ArenaVec::new_in(&frame);
+/// outer.push(1u8).unwrap();
+/// drop(outer);
+/// {
+///     let inner = frame.scope();
+///     ArenaVec::<u8, _>::with_capacity_in(8, &inner).unwrap();
+/// }
+/// drop(frame);
+/// assert_eq!(arena.current_offset(), 0);
+/// ```
+pub struct ArenaScope<'a, A: Rewind> {
+    arena: &'a mut A,
+    checkpoint: Checkpoint,
+}
+
+impl<'a, A: Rewind> ArenaScope<'a, A> {
+    pub fn new(arena: &'a mut A) -> Self {
+        let checkpoint = arena.checkpoint();
+        Self { arena, checkpoint }
+    }
+
+    /// The position the arena is rewound to when the scope is dropped.
+    pub fn checkpoint(&self) -> Checkpoint {
+        self.checkpoint
+    }
+
+    /// Opens a nested scope, which is rewound independently of this one.
+    pub fn scope(&mut self) -> ArenaScope<'_, Self> {
+        ArenaScope::new(self)
+    }
+}
+
+impl<A: Rewind> ArenaAllocatorImpl for ArenaScope<'_, A> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.arena.bump_alloc(layout)
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.arena.dealloc(data, layout)
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.arena.try_dealloc(data, layout)
+    }
+
+    /// Frees every allocation made in the scope, leaving the scope open.
+    fn reset(&mut self) -> AllocRes<()> {
+        unsafe { self.arena.rewind(self.checkpoint) }
+            .expect("the arena cannot be rewound past an open scope");
+        Ok(())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.arena.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.arena.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.arena.alloc_tagged(layout, tag)
+    }
+}
+
+impl<A: Rewind> Rewind for ArenaScope<'_, A> {
+    fn checkpoint(&self) -> Checkpoint {
+        self.arena.checkpoint()
+    }
+
+    unsafe fn rewind(&self, checkpoint: Checkpoint) -> Result<(), StaleCheckpoint> {
+        if checkpoint < self.checkpoint {
+            return Err(StaleCheckpoint);
+        }
+        unsafe { self.arena.rewind(checkpoint) }
+    }
+}
+
+impl<A: Rewind> Drop for ArenaScope<'_, A> {
+    fn drop(&mut self) {
+        // the scope borrows the arena exclusively, nothing can have moved the cursor below it
+        let _ = unsafe { self.arena.rewind(self.checkpoint) };
+    }
+}
+
+impl<A: Rewind> fmt::Debug for ArenaScope<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArenaScope")
+            .field("checkpoint", &self.checkpoint.offset())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,203 @@
+use core::{
+    alloc::Layout,
+    fmt,
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl};
+
+/// A zeroed block of temporary memory, handed back to the arena on drop.
+///
+/// The space is only reclaimed if the block is still the most recent allocation,
+/// which holds for the common case of scratch space used within a single call.
+pub struct Scratch<'a> {
+    block: NonNull<[u8]>,
+    alloc: &'a dyn ArenaAllocatorImpl,
+}
+
+impl<'a> Scratch<'a> {
+    pub(crate) fn new_in(bytes: usize, alloc: &'a dyn ArenaAllocatorImpl) -> AllocRes<Self> {
+        let layout = Layout::array::<u8>(bytes).map_err(|_| {
+            AllocError::with_message(AllocErrorKind::Other, "scratch size overflows")
+        })?;
+        alloc
+            .bump_alloc_zeroed(layout)
+            .map(|block| Self { block, alloc })
+    }
+}
+
+impl Deref for Scratch<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { self.block.as_ref() }
+    }
+}
+
+impl DerefMut for Scratch<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { self.block.as_mut() }
+    }
+}
+
+impl fmt::Debug for Scratch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scratch")
+            .field("len", &self.block.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for Scratch<'_> {
+    fn drop(&mut self) {
+        let layout =
+            Layout::array::<u8>(self.block.len()).expect("byte arrays never overflow isize");
+        self.alloc.dealloc(self.block.as_non_null_ptr(), layout);
+    }
+}
+
+const NO_FRAME: usize = usize::MAX;
+
+/// A region carved out of an arena with its own cursor, supporting nested temporary levels.
+///
+/// [`push`](Self::push) opens a level and [`pop`](Self::pop) frees everything allocated since,
+/// without affecting the arena the region was taken from. Each level stores its parent's
+/// position in a `usize` at its start, so nesting depth is only limited by the region's size.
+pub struct TempStack<'a> {
+    base: NonNull<u8>,
+    capacity: usize,
+    next_free: AtomicUsize,
+    frame: usize,
+    _marker: PhantomData<&'a mut [u8]>,
+}
+
+unsafe impl Send for TempStack<'_> {}
+unsafe impl Sync for TempStack<'_> {}
+
+impl<'a> TempStack<'a> {
+    /// Takes `capacity` bytes from `alloc` for the stack.
+    pub fn new_in<A: ArenaAllocatorImpl>(capacity: usize, alloc: &'a A) -> AllocRes<Self> {
+        let layout = Layout::from_size_align(capacity, mem::align_of::<usize>()).map_err(|_| {
+            AllocError::with_message(AllocErrorKind::Other, "temp stack size overflows")
+        })?;
+        let region = alloc.bump_alloc(layout)?;
+        Ok(Self {
+            base: region.as_non_null_ptr(),
+            capacity,
+            next_free: AtomicUsize::new(0),
+            frame: NO_FRAME,
+            _marker: PhantomData,
+        })
+    }
+
+    /// The number of open levels.
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut frame = self.frame;
+        while frame != NO_FRAME {
+            depth += 1;
+            frame = unsafe { self.base.add(frame).cast::<usize>().read() };
+        }
+        depth
+    }
+
+    /// Opens a new level.
+    pub fn push(&mut self) -> AllocRes<()> {
+        let slot = self.bump_alloc(Layout::new::<usize>())?.as_non_null_ptr();
+        unsafe { slot.cast::<usize>().write(self.frame) };
+        self.frame = slot.addr().get() - self.base.addr().get();
+        Ok(())
+    }
+
+    /// Closes the innermost level, freeing everything allocated since the matching [`push`](Self::push).
+    /// Returns `false` if no level is open.
+    pub fn pop(&mut self) -> bool {
+        if self.frame == NO_FRAME {
+            return false;
+        }
+        let parent = unsafe { self.base.add(self.frame).cast::<usize>().read() };
+        *self.next_free.get_mut() = self.frame;
+        self.frame = parent;
+        true
+    }
+}
+
+impl ArenaAllocatorImpl for TempStack<'_> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        let base = self.base.addr().get();
+        let mut cur = self.next_free.load(Ordering::Relaxed);
+        loop {
+            let start = (base + cur).next_multiple_of(layout.align()) - base;
+            let end = start
+                .checked_add(layout.size())
+                .filter(|end| *end <= self.capacity)
+                .ok_or(AllocError::new(AllocErrorKind::InsufficientCapacity {
+                    requested: layout.size(),
+                    available: self.capacity - cur,
+                }))?;
+            match self.next_free.compare_exchange_weak(
+                cur,
+                end,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    let ptr = unsafe { self.base.add(start) };
+                    return Ok(NonNull::slice_from_raw_parts(ptr, layout.size()));
+                }
+                Err(actual) => cur = actual,
+            }
+        }
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.try_dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        if !self.contains(data) {
+            return false;
+        }
+        let start = data.addr().get() - self.base.addr().get();
+        self.next_free
+            .compare_exchange(
+                start + layout.size(),
+                start,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        *self.next_free.get_mut() = 0;
+        self.frame = NO_FRAME;
+        Ok(())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        let base = self.base.addr().get();
+        (base..base + self.capacity).contains(&ptr.addr().get())
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        Some(
+            self.capacity
+                .saturating_sub(self.next_free.load(Ordering::Relaxed)),
+        )
+    }
+}
+
+impl fmt::Debug for TempStack<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TempStack")
+            .field("capacity", &self.capacity)
+            .field("used", &self.next_free.load(Ordering::Relaxed))
+            .field("depth", &self.depth())
+            .finish()
+    }
+}
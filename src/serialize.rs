@@ -0,0 +1,36 @@
+use postcard::ser_flavors::Flavor;
+use serde::Serialize;
+
+use crate::{ArenaAllocatorImpl, ArenaVec};
+
+/// Lets `postcard` serialize straight into the arena, running out of memory is reported as
+/// [`SerializeBufferFull`](postcard::Error::SerializeBufferFull).
+impl<'a, A: ArenaAllocatorImpl + ?Sized> Flavor for ArenaVec<'a, u8, A> {
+    type Output = &'a mut [u8];
+
+    fn try_push(&mut self, data: u8) -> postcard::Result<()> {
+        self.push(data)
+            .map_err(|_| postcard::Error::SerializeBufferFull)
+    }
+
+    fn try_extend(&mut self, data: &[u8]) -> postcard::Result<()> {
+        self.extend_from_slice(data)
+            .map_err(|_| postcard::Error::SerializeBufferFull)
+    }
+
+    fn finalize(self) -> postcard::Result<&'a mut [u8]> {
+        Ok(self.leak())
+    }
+}
+
+/// Serializes `value` with `postcard` into a buffer in `arena`.
+///
+/// The serialized size is not known up front, the buffer grows as needed. Growing hands the old
+/// block back, so while nothing else allocates from `arena` the buffer keeps reusing its tail.
+pub fn serialize_into_arena<'a, T, A>(value: &T, arena: &'a A) -> postcard::Result<&'a [u8]>
+where
+    T: Serialize + ?Sized,
+    A: ArenaAllocatorImpl + ?Sized,
+{
+    postcard::serialize_with_flavor(value, ArenaVec::new_in(arena)).map(|buf| &*buf)
+}
@@ -0,0 +1,130 @@
+use core::{alloc::Layout, fmt, mem, mem::MaybeUninit, ptr::NonNull};
+
+use crate::{AllocRes, ArenaAllocatorImpl, BorrowedAllocator};
+
+/// A shard on its own cache line, so the cursors of neighbouring shards do not share one.
+#[repr(align(128))]
+struct Shard<'a>(BorrowedAllocator<'a>);
+
+/// An arena split into `SHARDS` regions with their own bump cursors, to spread the cursor
+/// updates of many threads allocating at once over several cache lines.
+///
+/// Every thread starts at its own shard and overflows into the following ones once it is
+/// exhausted. Threads are told apart with the `std` feature only, without it every thread starts
+/// at the first shard, code knowing its CPU can allocate from a [`shard`](Self::shard) directly.
+pub struct ShardedArena<'a, const SHARDS: usize> {
+    shards: [Shard<'a>; SHARDS],
+}
+
+impl<'a, const SHARDS: usize> ShardedArena<'a, SHARDS> {
+    /// Splits `buf` into `SHARDS` equally sized regions, the last one takes the remaining bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `SHARDS` is zero.
+    pub fn new(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        assert!(SHARDS > 0, "a sharded arena needs at least one shard");
+        let len = buf.len() / SHARDS;
+        let mut rest = buf;
+        let shards = core::array::from_fn(|idx| {
+            let region = if idx + 1 == SHARDS {
+                mem::take(&mut rest)
+            } else {
+                let (region, tail) = mem::take(&mut rest).split_at_mut(len);
+                rest = tail;
+                region
+            };
+            Shard(BorrowedAllocator::from_uninit(region))
+        });
+        Self { shards }
+    }
+
+    pub fn from_slice(buf: &'a mut [u8]) -> Self {
+        let buf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+        Self::new(buf)
+    }
+
+    /// The arena of shard `idx`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is not below `SHARDS`.
+    pub fn shard(&self, idx: usize) -> &BorrowedAllocator<'a> {
+        &self.shards[idx].0
+    }
+
+    /// The shard the calling thread starts at.
+    fn home(&self) -> usize {
+        crate::record::thread_id() as usize % SHARDS
+    }
+
+    fn route(
+        &self,
+        alloc: impl Fn(&BorrowedAllocator<'a>) -> AllocRes<NonNull<[u8]>>,
+    ) -> AllocRes<NonNull<[u8]>> {
+        let home = self.home();
+        let mut res = alloc(&self.shards[home].0);
+        for step in 1..SHARDS {
+            match res {
+                Err(ref err) if err.kind().is_out_of_memory() => {
+                    res = alloc(&self.shards[(home + step) % SHARDS].0)
+                }
+                _ => break,
+            }
+        }
+        res
+    }
+
+    fn owner(&self, ptr: NonNull<u8>) -> Option<&BorrowedAllocator<'a>> {
+        self.shards
+            .iter()
+            .map(|shard| &shard.0)
+            .find(|arena| arena.contains(ptr))
+    }
+}
+
+impl<const SHARDS: usize> ArenaAllocatorImpl for ShardedArena<'_, SHARDS> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.route(|arena| arena.bump_alloc(layout))
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        if let Some(arena) = self.owner(data) {
+            arena.dealloc(data, layout)
+        }
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.owner(data)
+            .is_some_and(|arena| arena.try_dealloc(data, layout))
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.shards.iter_mut().try_for_each(|shard| shard.0.reset())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.owner(ptr).is_some()
+    }
+
+    /// The total free space of all shards.
+    fn remaining_hint(&self) -> Option<usize> {
+        self.shards
+            .iter()
+            .map(|shard| shard.0.remaining_hint())
+            .sum()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.route(|arena| arena.alloc_tagged(layout, tag))
+    }
+}
+
+impl<const SHARDS: usize> fmt::Debug for ShardedArena<'_, SHARDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShardedArena")
+            .field("shards", &SHARDS)
+            .field("free", &self.remaining_hint())
+            .finish()
+    }
+}
@@ -0,0 +1,235 @@
+use core::{
+    alloc::Layout,
+    cell::UnsafeCell,
+    fmt,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr::NonNull,
+    sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl};
+
+/// A handle to a value in a [`SlotArena`].
+///
+/// Keys stay valid until their value is removed, a key of a removed value never matches the
+/// slot's later occupants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SlotKey {
+    index: u32,
+    generation: u32,
+}
+
+impl SlotKey {
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+struct Slot<T> {
+    value: UnsafeCell<MaybeUninit<T>>,
+    /// Bumped whenever the slot's value is removed.
+    generation: AtomicU32,
+    occupied: AtomicBool,
+}
+
+/// A fixed number of slots carved from an arena, addressed by small copyable [`SlotKey`]s,
+/// e.g. for graphs or entity tables which need stable handles and removal.
+///
+/// Inserting takes `&self` and is wait-free: removed slots are handed out by a single
+/// `fetch_add` on the free list, which only grows in [`remove`](Self::remove), taking `&mut self`.
+/// Once no removed slot is left, unused slots are handed out by another `fetch_add`.
+pub struct SlotArena<'a, T, A: ArenaAllocatorImpl + ?Sized> {
+    slots: NonNull<Slot<T>>,
+    /// Indices of removed slots, the top `free_taken` of `free[..free_len]` are taken.
+    free: NonNull<u32>,
+    free_len: usize,
+    free_taken: AtomicUsize,
+    /// Slots at or past `fresh` have never been used.
+    fresh: AtomicUsize,
+    capacity: usize,
+    len: AtomicUsize,
+    alloc: &'a A,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: Send, A: ArenaAllocatorImpl + Sync + ?Sized> Send for SlotArena<'_, T, A> {}
+// Every slot is written by the single insert claiming it, shared access only reads.
+unsafe impl<T: Send + Sync, A: ArenaAllocatorImpl + Sync + ?Sized> Sync for SlotArena<'_, T, A> {}
+
+impl<'a, T, A: ArenaAllocatorImpl + ?Sized> SlotArena<'a, T, A> {
+    /// Carves `capacity` slots out of `alloc`.
+    pub fn with_capacity_in(capacity: usize, alloc: &'a A) -> AllocRes<Self> {
+        if u32::try_from(capacity).is_err() {
+            return Err(AllocError::with_message(
+                AllocErrorKind::Other,
+                "slot indices are limited to u32",
+            ));
+        }
+        let slots = alloc
+            .bump_alloc(Self::slots_layout(capacity)?)?
+            .as_non_null_ptr()
+            .cast::<Slot<T>>();
+        let free = match alloc.bump_alloc(Self::free_layout(capacity)?) {
+            Ok(free) => free.as_non_null_ptr().cast::<u32>(),
+            Err(err) => {
+                alloc.dealloc(slots.cast(), Self::slots_layout(capacity)?);
+                return Err(err);
+            }
+        };
+        for idx in 0..capacity {
+            let slot = Slot {
+                value: UnsafeCell::new(MaybeUninit::uninit()),
+                generation: AtomicU32::new(0),
+                occupied: AtomicBool::new(false),
+            };
+            unsafe { slots.add(idx).write(slot) };
+        }
+        Ok(Self {
+            slots,
+            free,
+            free_len: 0,
+            free_taken: AtomicUsize::new(0),
+            fresh: AtomicUsize::new(0),
+            capacity,
+            len: AtomicUsize::new(0),
+            alloc,
+            _marker: PhantomData,
+        })
+    }
+
+    fn slots_layout(capacity: usize) -> AllocRes<Layout> {
+        Layout::array::<Slot<T>>(capacity).map_err(|_| capacity_overflow())
+    }
+
+    fn free_layout(capacity: usize) -> AllocRes<Layout> {
+        Layout::array::<u32>(capacity).map_err(|_| capacity_overflow())
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of values stored.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stores `value` in a free slot and returns its key.
+    pub fn insert(&self, value: T) -> AllocRes<SlotKey> {
+        let index = self.claim().ok_or(AllocError::with_message(
+            AllocErrorKind::InsufficientCapacity {
+                requested: size_of::<T>(),
+                available: 0,
+            },
+            "All slots are occupied",
+        ))?;
+        let slot = self.slot(index);
+        unsafe { (*slot.value.get()).write(value) };
+        slot.occupied.store(true, Ordering::Release);
+        self.len.fetch_add(1, Ordering::Relaxed);
+        Ok(SlotKey {
+            index,
+            generation: slot.generation.load(Ordering::Relaxed),
+        })
+    }
+
+    pub fn get(&self, key: SlotKey) -> Option<&T> {
+        let slot = self.occupied(key)?;
+        Some(unsafe { (*slot.value.get()).assume_init_ref() })
+    }
+
+    pub fn get_mut(&mut self, key: SlotKey) -> Option<&mut T> {
+        let slot = self.occupied(key)?;
+        Some(unsafe { (*slot.value.get()).assume_init_mut() })
+    }
+
+    pub fn contains_key(&self, key: SlotKey) -> bool {
+        self.occupied(key).is_some()
+    }
+
+    /// Takes the value out of its slot, invalidating `key`. The slot is reused by later inserts.
+    pub fn remove(&mut self, key: SlotKey) -> Option<T> {
+        self.settle();
+        let slot = self.occupied(key)?;
+        slot.occupied.store(false, Ordering::Relaxed);
+        slot.generation.fetch_add(1, Ordering::Relaxed);
+        let value = unsafe { (*slot.value.get()).assume_init_read() };
+        unsafe { self.free.add(self.free_len).write(key.index) };
+        self.free_len += 1;
+        *self.len.get_mut() -= 1;
+        Some(value)
+    }
+
+    fn slot(&self, index: u32) -> &Slot<T> {
+        unsafe { self.slots.add(index as usize).as_ref() }
+    }
+
+    fn occupied(&self, key: SlotKey) -> Option<&Slot<T>> {
+        if key.index as usize >= self.capacity {
+            return None;
+        }
+        let slot = self.slot(key.index);
+        (slot.occupied.load(Ordering::Acquire)
+            && slot.generation.load(Ordering::Relaxed) == key.generation)
+            .then_some(slot)
+    }
+
+    /// Takes a removed slot, or an unused one if none is left.
+    fn claim(&self) -> Option<u32> {
+        let taken = self.free_taken.fetch_add(1, Ordering::Relaxed);
+        if taken < self.free_len {
+            return Some(unsafe { self.free.add(self.free_len - 1 - taken).read() });
+        }
+        let fresh = self.fresh.fetch_add(1, Ordering::Relaxed);
+        (fresh < self.capacity).then_some(fresh as u32)
+    }
+
+    /// Drops the taken entries from the free list, and clamps the counters bumped by failed inserts.
+    fn settle(&mut self) {
+        let taken = self.free_taken.get_mut();
+        self.free_len -= (*taken).min(self.free_len);
+        *taken = 0;
+        let fresh = self.fresh.get_mut();
+        *fresh = (*fresh).min(self.capacity);
+    }
+}
+
+fn capacity_overflow() -> AllocError {
+    AllocError::with_message(AllocErrorKind::Other, "capacity overflow")
+}
+
+impl<T: fmt::Debug, A: ArenaAllocatorImpl + ?Sized> fmt::Debug for SlotArena<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SlotArena")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> Drop for SlotArena<'_, T, A> {
+    fn drop(&mut self) {
+        self.settle();
+        for idx in 0..*self.fresh.get_mut() {
+            let slot = unsafe { self.slots.add(idx).as_mut() };
+            if *slot.occupied.get_mut() {
+                unsafe { slot.value.get_mut().assume_init_drop() };
+            }
+        }
+        let capacity = self.capacity;
+        // both layouts were valid when the slots were carved out
+        if let (Ok(slots), Ok(free)) = (Self::slots_layout(capacity), Self::free_layout(capacity)) {
+            self.alloc.dealloc(self.free.cast(), free);
+            self.alloc.dealloc(self.slots.cast(), slots);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use core::{borrow::Borrow, cmp::Ordering, fmt, hash, ops::Deref, str};
+
+use crate::{AllocRes, ArenaAllocatorImpl, ArenaString};
+
+/// The longest string stored inline by an [`ArenaSmolStr`].
+pub const INLINE_CAP: usize = 22;
+
+/// A `Copy` string handle for identifier heavy workloads (compilers, config parsers), storing
+/// strings of up to [`INLINE_CAP`] bytes inline and longer ones in an arena.
+///
+/// The handle is 24 bytes large, short identifiers cost no arena space and are compared
+/// without chasing a pointer.
+#[derive(Clone, Copy)]
+pub struct ArenaSmolStr<'a>(Repr<'a>);
+
+#[derive(Clone, Copy)]
+enum Repr<'a> {
+    Inline { len: u8, buf: [u8; INLINE_CAP] },
+    Arena(&'a str),
+}
+
+impl<'a> ArenaSmolStr<'a> {
+    /// Stores `s` inline if it fits, otherwise copies it into `alloc`.
+    pub fn new_in<A: ArenaAllocatorImpl + ?Sized>(s: &str, alloc: &'a A) -> AllocRes<Self> {
+        match Self::new_inline(s) {
+            Some(inline) => Ok(inline),
+            None => Ok(Self(Repr::Arena(
+                ArenaString::from_str_in(s, alloc)?.into_str(),
+            ))),
+        }
+    }
+
+    /// Stores `s` inline, `None` if it is longer than [`INLINE_CAP`].
+    pub const fn new_inline(s: &str) -> Option<Self> {
+        if s.len() > INLINE_CAP {
+            return None;
+        }
+        let mut buf = [0; INLINE_CAP];
+        buf.split_at_mut(s.len()).0.copy_from_slice(s.as_bytes());
+        Some(Self(Repr::Inline {
+            len: s.len() as u8,
+            buf,
+        }))
+    }
+
+    /// Wraps a string which already lives long enough, without copying it.
+    pub const fn from_str_ref(s: &'a str) -> Self {
+        Self(Repr::Arena(s))
+    }
+
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            Repr::Inline { len, buf } => unsafe { str::from_utf8_unchecked(&buf[..*len as usize]) },
+            Repr::Arena(s) => s,
+        }
+    }
+
+    pub fn is_inline(&self) -> bool {
+        matches!(self.0, Repr::Inline { .. })
+    }
+}
+
+impl Deref for ArenaSmolStr<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for ArenaSmolStr<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for ArenaSmolStr<'_> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq for ArenaSmolStr<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for ArenaSmolStr<'_> {}
+
+impl PartialEq<str> for ArenaSmolStr<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ArenaSmolStr<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialOrd for ArenaSmolStr<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ArenaSmolStr<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl hash::Hash for ArenaSmolStr<'_> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl fmt::Display for ArenaSmolStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Debug for ArenaSmolStr<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
@@ -0,0 +1,200 @@
+use core::{
+    alloc::Layout,
+    fmt,
+    mem::MaybeUninit,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl};
+
+/// A fixed capacity single-producer single-consumer ring allocated from an arena,
+/// e.g. to stream from a driver to a task with both buffer and indices in a designated region.
+///
+/// [`split`](Self::split) hands out the two ends. `head` and `tail` are the positions of the next
+/// pop and push, each is only written by its own end, so neither end ever waits for the other.
+pub struct SpscRing<'a, T, A: ArenaAllocatorImpl + ?Sized> {
+    buf: NonNull<MaybeUninit<T>>,
+    capacity: usize,
+    /// Positions run modulo `2 * capacity`, which tells a full ring from an empty one.
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    alloc: &'a A,
+}
+
+unsafe impl<T: Send, A: ArenaAllocatorImpl + Sync + ?Sized> Send for SpscRing<'_, T, A> {}
+// Every slot is owned by exactly one end at a time.
+unsafe impl<T: Send, A: ArenaAllocatorImpl + Sync + ?Sized> Sync for SpscRing<'_, T, A> {}
+
+impl<'a, T, A: ArenaAllocatorImpl + ?Sized> SpscRing<'a, T, A> {
+    /// Takes room for `capacity` elements from `alloc`.
+    pub fn with_capacity_in(capacity: usize, alloc: &'a A) -> AllocRes<Self> {
+        if capacity == 0 || capacity > usize::MAX / 2 {
+            return Err(AllocError::with_message(
+                AllocErrorKind::Other,
+                "a ring holds between one and usize::MAX / 2 elements",
+            ));
+        }
+        let buf = alloc
+            .bump_alloc(Self::layout(capacity)?)?
+            .as_non_null_ptr()
+            .cast();
+        Ok(Self {
+            buf,
+            capacity,
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            alloc,
+        })
+    }
+
+    fn layout(capacity: usize) -> AllocRes<Layout> {
+        Layout::array::<T>(capacity)
+            .map_err(|_| AllocError::with_message(AllocErrorKind::Other, "capacity overflow"))
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of queued elements, may be outdated once the ring is split.
+    pub fn len(&self) -> usize {
+        self.distance(
+            self.head.load(Ordering::Acquire),
+            self.tail.load(Ordering::Acquire),
+        )
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The producing and consuming end, which may be moved to different threads.
+    pub fn split(&mut self) -> (RingProducer<'_, 'a, T, A>, RingConsumer<'_, 'a, T, A>) {
+        (RingProducer { ring: self }, RingConsumer { ring: self })
+    }
+
+    fn slot(&self, pos: usize) -> NonNull<MaybeUninit<T>> {
+        unsafe { self.buf.add(pos % self.capacity) }
+    }
+
+    fn advance(&self, pos: usize, by: usize) -> usize {
+        (pos + by) % (2 * self.capacity)
+    }
+
+    /// The number of elements between the positions `head` and `tail`.
+    fn distance(&self, head: usize, tail: usize) -> usize {
+        (tail + 2 * self.capacity - head) % (2 * self.capacity)
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> fmt::Debug for SpscRing<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpscRing")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> Drop for SpscRing<'_, T, A> {
+    fn drop(&mut self) {
+        let head = *self.head.get_mut();
+        for offset in 0..self.len() {
+            unsafe {
+                self.slot(self.advance(head, offset))
+                    .as_mut()
+                    .assume_init_drop()
+            };
+        }
+        if let Ok(layout) = Self::layout(self.capacity) {
+            self.alloc.dealloc(self.buf.cast(), layout);
+        }
+    }
+}
+
+/// The producing end of an [`SpscRing`].
+pub struct RingProducer<'r, 'a, T, A: ArenaAllocatorImpl + ?Sized> {
+    ring: &'r SpscRing<'a, T, A>,
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> RingProducer<'_, '_, T, A> {
+    /// Queues `value`, handing it back if the ring is full.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        let ring = self.ring;
+        let tail = ring.tail.load(Ordering::Relaxed);
+        if ring.distance(ring.head.load(Ordering::Acquire), tail) == ring.capacity {
+            return Err(value);
+        }
+        unsafe { ring.slot(tail).write(MaybeUninit::new(value)) };
+        ring.tail.store(ring.advance(tail, 1), Ordering::Release);
+        Ok(())
+    }
+
+    /// Queues as many elements of `values` as fit and returns their number.
+    pub fn push_slice(&mut self, values: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let ring = self.ring;
+        let tail = ring.tail.load(Ordering::Relaxed);
+        let free = ring.capacity - ring.distance(ring.head.load(Ordering::Acquire), tail);
+        let count = free.min(values.len());
+        for (offset, value) in values[..count].iter().enumerate() {
+            unsafe {
+                ring.slot(ring.advance(tail, offset))
+                    .write(MaybeUninit::new(*value))
+            };
+        }
+        ring.tail
+            .store(ring.advance(tail, count), Ordering::Release);
+        count
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> fmt::Debug for RingProducer<'_, '_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingProducer").finish_non_exhaustive()
+    }
+}
+
+/// The consuming end of an [`SpscRing`].
+pub struct RingConsumer<'r, 'a, T, A: ArenaAllocatorImpl + ?Sized> {
+    ring: &'r SpscRing<'a, T, A>,
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> RingConsumer<'_, '_, T, A> {
+    pub fn pop(&mut self) -> Option<T> {
+        let ring = self.ring;
+        let head = ring.head.load(Ordering::Relaxed);
+        if ring.tail.load(Ordering::Acquire) == head {
+            return None;
+        }
+        let value = unsafe { ring.slot(head).read().assume_init() };
+        ring.head.store(ring.advance(head, 1), Ordering::Release);
+        Some(value)
+    }
+
+    /// Takes up to `out.len()` elements into `out` and returns their number.
+    pub fn pop_slice(&mut self, out: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let ring = self.ring;
+        let head = ring.head.load(Ordering::Relaxed);
+        let queued = ring.distance(head, ring.tail.load(Ordering::Acquire));
+        let count = queued.min(out.len());
+        for (offset, out) in out[..count].iter_mut().enumerate() {
+            *out = unsafe { ring.slot(ring.advance(head, offset)).read().assume_init() };
+        }
+        ring.head
+            .store(ring.advance(head, count), Ordering::Release);
+        count
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> fmt::Debug for RingConsumer<'_, '_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingConsumer").finish_non_exhaustive()
+    }
+}
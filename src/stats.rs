@@ -0,0 +1,297 @@
+use core::{
+    cell::UnsafeCell,
+    hint,
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
+};
+
+/// Number of distinct tags an arena accounts for, bytes of any further tags are not attributed.
+pub const MAX_TAGS: usize = 16;
+
+/// A snapshot of the counters of an arena.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ArenaStats {
+    /// Bytes currently handed out.
+    pub used: usize,
+    /// Size of the backing buffer in bytes.
+    pub capacity: usize,
+    /// The largest value `used` has reached.
+    pub high_water: usize,
+    /// Number of successful allocations, including zero sized ones.
+    pub allocations: usize,
+    /// Number of times an allocation could not hand back claimed bytes, because another thread
+    /// moved the cursor in the meantime.
+    pub contention: usize,
+    /// Number of allocations which failed due to insufficient memory.
+    pub oom_count: usize,
+    /// Bytes lost to alignment padding by all allocations so far, not included in the payload sizes of tags.
+    pub padding_bytes: usize,
+}
+
+/// The counters are maintained with relaxed atomics, they are purely informational.
+pub(crate) struct Counters {
+    high_water: AtomicUsize,
+    allocations: AtomicUsize,
+    contention: AtomicUsize,
+    oom_count: AtomicUsize,
+    padding: AtomicUsize,
+    tags: TagTable,
+    /// Index of the tag untagged allocations are attributed to, see [`Counters::with_category`].
+    category: AtomicUsize,
+}
+
+impl Counters {
+    pub(crate) const fn new() -> Self {
+        Self {
+            high_water: AtomicUsize::new(0),
+            allocations: AtomicUsize::new(0),
+            contention: AtomicUsize::new(0),
+            oom_count: AtomicUsize::new(0),
+            padding: AtomicUsize::new(0),
+            tags: TagTable::new(),
+            category: AtomicUsize::new(NO_CATEGORY),
+        }
+    }
+
+    pub(crate) fn record_tag(&self, tag: &'static str, bytes: usize) {
+        if let Some(slot) = self.tags.slot(tag) {
+            slot.bytes.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn record_untagged(&self, bytes: usize) {
+        let category = self.category.load(Ordering::Relaxed);
+        if let Some(slot) = self.tags.slots.get(category) {
+            slot.bytes.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    /// Attributes all untagged allocations to `category` until `f` returns.
+    /// The previous category is restored afterwards, even if `f` panics.
+    pub(crate) fn with_category<R>(&self, category: &'static str, f: impl FnOnce() -> R) -> R {
+        struct Restore<'a>(&'a AtomicUsize, usize);
+
+        impl Drop for Restore<'_> {
+            fn drop(&mut self) {
+                self.0.store(self.1, Ordering::Relaxed);
+            }
+        }
+
+        let idx = self.tags.index(category).unwrap_or(NO_CATEGORY);
+        let _restore = Restore(&self.category, self.category.swap(idx, Ordering::Relaxed));
+        f()
+    }
+
+    pub(crate) fn tags(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+        self.tags.iter()
+    }
+
+    pub(crate) fn record_used(&self, used: usize) {
+        self.high_water.fetch_max(used, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_allocation(&self) {
+        self.allocations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_contention(&self) {
+        self.contention.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_oom(&self) {
+        self.oom_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_padding(&self, bytes: usize) {
+        if bytes != 0 {
+            self.padding.fetch_add(bytes, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn snapshot(&self, used: usize, capacity: usize) -> ArenaStats {
+        ArenaStats {
+            used,
+            capacity,
+            high_water: self.high_water.load(Ordering::Relaxed),
+            allocations: self.allocations.load(Ordering::Relaxed),
+            contention: self.contention.load(Ordering::Relaxed),
+            oom_count: self.oom_count.load(Ordering::Relaxed),
+            padding_bytes: self.padding.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Zeroes the counters and tag totals, the high water mark restarts at `used`.
+    /// Tags keep their slots.
+    pub(crate) fn reset(&self, used: usize) {
+        self.high_water.store(used, Ordering::Relaxed);
+        self.allocations.store(0, Ordering::Relaxed);
+        self.contention.store(0, Ordering::Relaxed);
+        self.oom_count.store(0, Ordering::Relaxed);
+        self.padding.store(0, Ordering::Relaxed);
+        for slot in &self.tags.slots {
+            slot.bytes.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+const NO_CATEGORY: usize = usize::MAX;
+
+const EMPTY: u8 = 0;
+const CLAIMED: u8 = 1;
+const READY: u8 = 2;
+
+struct TagSlot {
+    state: AtomicU8,
+    name: UnsafeCell<&'static str>,
+    bytes: AtomicUsize,
+}
+
+impl TagSlot {
+    const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(EMPTY),
+            name: UnsafeCell::new(""),
+            bytes: AtomicUsize::new(0),
+        }
+    }
+
+    /// Waits for a concurrent claim of this slot to publish its name.
+    /// The claiming thread only performs a single store in between.
+    fn name(&self) -> &'static str {
+        while self.state.load(Ordering::Acquire) != READY {
+            hint::spin_loop();
+        }
+        unsafe { *self.name.get() }
+    }
+}
+
+/// Append only table mapping tags to the number of bytes allocated under them.
+struct TagTable {
+    slots: [TagSlot; MAX_TAGS],
+}
+
+impl TagTable {
+    const fn new() -> Self {
+        Self {
+            slots: [const { TagSlot::new() }; MAX_TAGS],
+        }
+    }
+
+    /// Finds the slot of `tag`, claiming a new one on first use.
+    /// Returns `None` once all [`MAX_TAGS`] slots are taken by other tags.
+    fn index(&self, tag: &'static str) -> Option<usize> {
+        self.slots.iter().position(|slot| {
+            if slot
+                .state
+                .compare_exchange(EMPTY, CLAIMED, Ordering::Acquire, Ordering::Acquire)
+                .is_ok()
+            {
+                unsafe { *slot.name.get() = tag };
+                slot.state.store(READY, Ordering::Release);
+            }
+            slot.name() == tag
+        })
+    }
+
+    fn slot(&self, tag: &'static str) -> Option<&TagSlot> {
+        self.index(tag).map(|idx| &self.slots[idx])
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&'static str, usize)> + '_ {
+        self.slots
+            .iter()
+            .take_while(|slot| slot.state.load(Ordering::Acquire) != EMPTY)
+            .map(|slot| (slot.name(), slot.bytes.load(Ordering::Relaxed)))
+    }
+}
+
+#[cfg(feature = "std")]
+mod prometheus_ {
+    use std::io;
+
+    use super::ArenaStats;
+
+    struct Metric {
+        name: &'static str,
+        kind: &'static str,
+        help: &'static str,
+        value: fn(&ArenaStats) -> usize,
+    }
+
+    const METRICS: [Metric; 7] = [
+        Metric {
+            name: "used_bytes",
+            kind: "gauge",
+            help: "Bytes currently handed out by the arena.",
+            value: |s| s.used,
+        },
+        Metric {
+            name: "capacity_bytes",
+            kind: "gauge",
+            help: "Size of the arena's backing buffer.",
+            value: |s| s.capacity,
+        },
+        Metric {
+            name: "high_water_bytes",
+            kind: "gauge",
+            help: "Largest number of bytes the arena has handed out.",
+            value: |s| s.high_water,
+        },
+        Metric {
+            name: "allocations_total",
+            kind: "counter",
+            help: "Allocations served by the arena.",
+            value: |s| s.allocations,
+        },
+        Metric {
+            name: "contention_total",
+            kind: "counter",
+            help: "Claims not handed back due to concurrent cursor updates.",
+            value: |s| s.contention,
+        },
+        Metric {
+            name: "oom_total",
+            kind: "counter",
+            help: "Allocations which failed due to insufficient memory.",
+            value: |s| s.oom_count,
+        },
+        Metric {
+            name: "padding_bytes_total",
+            kind: "counter",
+            help: "Bytes lost to alignment padding.",
+            value: |s| s.padding_bytes,
+        },
+    ];
+
+    /// Renders `arenas` in the Prometheus text exposition format, labelling each sample with the arena's name.
+    pub fn write_prometheus<W: io::Write>(
+        out: &mut W,
+        arenas: &[(&str, ArenaStats)],
+    ) -> io::Result<()> {
+        for Metric {
+            name,
+            kind,
+            help,
+            value,
+        } in METRICS
+        {
+            writeln!(out, "# HELP wait_free_arena_{name} {help}")?;
+            writeln!(out, "# TYPE wait_free_arena_{name} {kind}")?;
+            for (arena, stats) in arenas {
+                write!(out, "wait_free_arena_{name}{{arena=\"")?;
+                for c in arena.chars() {
+                    match c {
+                        '\\' => out.write_all(b"\\\\")?,
+                        '"' => out.write_all(b"\\\"")?,
+                        '\n' => out.write_all(b"\\n")?,
+                        c => write!(out, "{c}")?,
+                    }
+                }
+                writeln!(out, "\"}} {}", value(stats))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+pub use prometheus_::*;
@@ -0,0 +1,249 @@
+use core::{alloc::Layout, fmt, ptr::NonNull};
+use std::{thread, vec::Vec};
+
+use crate::ArenaAllocatorImpl;
+
+/// Parameters of a [`stress`] run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StressConfig {
+    pub threads: usize,
+    pub ops_per_thread: usize,
+    /// Upper bound (inclusive) of the size of a single request.
+    pub max_size: usize,
+    /// Upper bound of the power of two alignments which are requested.
+    pub max_align: usize,
+    pub seed: u64,
+}
+
+impl Default for StressConfig {
+    fn default() -> Self {
+        Self {
+            threads: 4,
+            ops_per_thread: 1000,
+            max_size: 64,
+            max_align: 64,
+            seed: 0x5eed,
+        }
+    }
+}
+
+/// Operation counts of a successful [`stress`] run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StressReport {
+    pub allocations: usize,
+    pub deallocations: usize,
+    pub grows: usize,
+    /// Requests which failed, e.g. because the arena ran out of memory.
+    pub failures: usize,
+}
+
+/// An invariant broken by the arena under test.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StressViolation {
+    /// The block at `addr` does not satisfy the requested alignment.
+    Misaligned { addr: usize, align: usize },
+    /// The block at `addr` lies outside of the arena according to `contains`.
+    OutOfBounds { addr: usize },
+    /// The contents of the block at `addr` were modified through another allocation.
+    Corrupted { addr: usize },
+    /// The live blocks at `first` and `second` overlap.
+    Overlap { first: usize, second: usize },
+}
+
+impl fmt::Display for StressViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Misaligned { addr, align } => {
+                write!(f, "block at {addr:#x} is not aligned to {align}")
+            }
+            Self::OutOfBounds { addr } => write!(f, "block at {addr:#x} is outside of the arena"),
+            Self::Corrupted { addr } => write!(f, "block at {addr:#x} was overwritten"),
+            Self::Overlap { first, second } => {
+                write!(f, "blocks at {first:#x} and {second:#x} overlap")
+            }
+        }
+    }
+}
+
+impl core::error::Error for StressViolation {}
+
+/// Drives randomized concurrent allocations, deallocations and grows against `arena`
+/// and checks that live blocks are aligned, inside the arena and never overlap.
+///
+/// Every block is filled with a pattern which is verified before it is freed, so writes
+/// through overlapping blocks handed to different threads are detected as well.
+pub fn stress<A: ArenaAllocatorImpl + Sync>(
+    arena: &A,
+    config: &StressConfig,
+) -> Result<StressReport, StressViolation> {
+    let results: Vec<_> = thread::scope(|s| {
+        let workers: Vec<_> = (0..config.threads)
+            .map(|id| s.spawn(move || Worker::new(arena, config, id).run()))
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("stress worker panicked"))
+            .collect()
+    });
+
+    let mut report = StressReport::default();
+    let mut live = Vec::new();
+    for result in results {
+        let (worker_report, blocks) = result?;
+        report.allocations += worker_report.allocations;
+        report.deallocations += worker_report.deallocations;
+        report.grows += worker_report.grows;
+        report.failures += worker_report.failures;
+        live.extend(blocks);
+    }
+
+    live.retain(|(_, size)| *size != 0);
+    live.sort_unstable();
+    for pair in live.windows(2) {
+        let ((first, size), (second, _)) = (pair[0], pair[1]);
+        if first + size > second {
+            return Err(StressViolation::Overlap { first, second });
+        }
+    }
+    Ok(report)
+}
+
+struct Block {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    pattern: u8,
+}
+
+struct Worker<'a, A> {
+    arena: &'a A,
+    config: &'a StressConfig,
+    rng: u64,
+    next_pattern: u8,
+    live: Vec<Block>,
+    report: StressReport,
+}
+
+impl<'a, A: ArenaAllocatorImpl> Worker<'a, A> {
+    fn new(arena: &'a A, config: &'a StressConfig, id: usize) -> Self {
+        Self {
+            arena,
+            config,
+            rng: (config.seed ^ (id as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)) | 1,
+            next_pattern: id as u8,
+            live: Vec::new(),
+            report: StressReport::default(),
+        }
+    }
+
+    fn next(&mut self) -> u64 {
+        // xorshift64*
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        self.rng.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound.max(1) as u64) as usize
+    }
+
+    fn random_layout(&mut self, min_size: usize) -> Layout {
+        let size =
+            min_size + self.below(self.config.max_size + 1 - min_size.min(self.config.max_size));
+        let max_shift = self.config.max_align.max(1).trailing_zeros() as usize;
+        Layout::from_size_align(size, 1 << self.below(max_shift + 1)).unwrap()
+    }
+
+    fn run(mut self) -> Result<(StressReport, Vec<(usize, usize)>), StressViolation> {
+        for _ in 0..self.config.ops_per_thread {
+            match self.below(10) {
+                0..6 => {
+                    let layout = self.random_layout(0);
+                    if let Some(block) = self.alloc(layout)? {
+                        self.live.push(block);
+                    }
+                }
+                6..8 if !self.live.is_empty() => {
+                    // prefer the most recent block, which exercises tail reuse
+                    let idx = if self.below(2) == 0 {
+                        self.live.len() - 1
+                    } else {
+                        self.below(self.live.len())
+                    };
+                    let block = self.live.swap_remove(idx);
+                    self.free(block)?;
+                }
+                8..10 if !self.live.is_empty() => {
+                    let idx = self.below(self.live.len());
+                    self.grow(idx)?;
+                }
+                _ => {}
+            }
+        }
+        let blocks = self
+            .live
+            .iter()
+            .map(|block| (block.ptr.addr().get(), block.layout.size()))
+            .collect();
+        for block in &self.live {
+            verify(block)?;
+        }
+        Ok((self.report, blocks))
+    }
+
+    fn alloc(&mut self, layout: Layout) -> Result<Option<Block>, StressViolation> {
+        let Ok(ptr) = self.arena.bump_alloc(layout) else {
+            self.report.failures += 1;
+            return Ok(None);
+        };
+        let ptr = ptr.as_non_null_ptr();
+        let addr = ptr.addr().get();
+        if addr % layout.align() != 0 {
+            return Err(StressViolation::Misaligned {
+                addr,
+                align: layout.align(),
+            });
+        }
+        if layout.size() != 0 && !self.arena.contains(ptr) {
+            return Err(StressViolation::OutOfBounds { addr });
+        }
+        self.next_pattern = self.next_pattern.wrapping_add(1);
+        let block = Block {
+            ptr,
+            layout,
+            pattern: self.next_pattern,
+        };
+        unsafe { ptr.write_bytes(block.pattern, layout.size()) };
+        self.report.allocations += 1;
+        Ok(Some(block))
+    }
+
+    fn free(&mut self, block: Block) -> Result<(), StressViolation> {
+        verify(&block)?;
+        self.arena.dealloc(block.ptr, block.layout);
+        self.report.deallocations += 1;
+        Ok(())
+    }
+
+    /// Moves the block to a larger allocation, as a reallocating collection would.
+    fn grow(&mut self, idx: usize) -> Result<(), StressViolation> {
+        verify(&self.live[idx])?;
+        let layout = self.random_layout(self.live[idx].layout.size());
+        if let Some(grown) = self.alloc(layout)? {
+            let old = core::mem::replace(&mut self.live[idx], grown);
+            self.arena.dealloc(old.ptr, old.layout);
+            self.report.grows += 1;
+        }
+        Ok(())
+    }
+}
+
+fn verify(block: &Block) -> Result<(), StressViolation> {
+    let bytes = unsafe { core::slice::from_raw_parts(block.ptr.as_ptr(), block.layout.size()) };
+    if bytes.iter().any(|b| *b != block.pattern) {
+        return Err(StressViolation::Corrupted {
+            addr: block.ptr.addr().get(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Deref, DerefMut, Index, IndexMut},
+    slice::SliceIndex,
+    str,
+};
+
+use crate::{AllocRes, ArenaAllocatorImpl, ArenaVec};
+
+/// An incremental builder for strings which are frozen into `&'a str` with [`ArenaString::into_str`].
+pub type StrBuilder<'a, A> = ArenaString<'a, A>;
+
+/// A growable UTF-8 string living in an arena.
+///
+/// Like [`ArenaVec`], every growing operation returns an [`AllocRes`].
+pub struct ArenaString<'a, A: ArenaAllocatorImpl + ?Sized> {
+    vec: ArenaVec<'a, u8, A>,
+}
+
+impl<'a, A: ArenaAllocatorImpl + ?Sized> ArenaString<'a, A> {
+    pub fn new_in(alloc: &'a A) -> Self {
+        Self {
+            vec: ArenaVec::new_in(alloc),
+        }
+    }
+
+    pub fn with_capacity_in(capacity: usize, alloc: &'a A) -> AllocRes<Self> {
+        ArenaVec::with_capacity_in(capacity, alloc).map(|vec| Self { vec })
+    }
+
+    pub fn from_str_in(s: &str, alloc: &'a A) -> AllocRes<Self> {
+        let mut string = Self::with_capacity_in(s.len(), alloc)?;
+        string.push_str(s)?;
+        Ok(string)
+    }
+
+    pub fn len(&self) -> usize {
+        self.vec.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vec.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.vec.capacity()
+    }
+
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(&self.vec) }
+    }
+
+    pub fn as_mut_str(&mut self) -> &mut str {
+        unsafe { str::from_utf8_unchecked_mut(&mut self.vec) }
+    }
+
+    #[doc(alias = "try_reserve")]
+    pub fn reserve(&mut self, additional: usize) -> AllocRes<()> {
+        self.vec.reserve(additional)
+    }
+
+    pub fn push_str(&mut self, s: &str) -> AllocRes<()> {
+        self.vec.extend_from_slice(s.as_bytes())
+    }
+
+    #[doc(alias = "try_push")]
+    pub fn push(&mut self, c: char) -> AllocRes<()> {
+        self.push_str(c.encode_utf8(&mut [0; 4]))
+    }
+
+    /// Appends the chars of `iter`, reserving room for its lower size hint up front.
+    pub fn try_extend(&mut self, iter: impl IntoIterator<Item = char>) -> AllocRes<()> {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0)?;
+        for c in iter {
+            self.push(c)?;
+        }
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.vec.clear();
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.vec.shrink_to_fit();
+    }
+
+    /// Shrinks the string and freezes it for the lifetime of the arena.
+    pub fn into_str(self) -> &'a str {
+        unsafe { str::from_utf8_unchecked(self.vec.leak()) }
+    }
+
+    /// Shrinks the string and converts it into a [`Box`](crate::boxed::Box).
+    #[cfg(feature = "boxed")]
+    pub fn into_boxed_str(self) -> crate::boxed::Box<'a, str> {
+        unsafe { crate::boxed::Box::from_raw(self.vec.leak() as *mut [u8] as *mut str) }
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> Deref for ArenaString<'_, A> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> DerefMut for ArenaString<'_, A> {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> AsRef<str> for ArenaString<'_, A> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> AsRef<[u8]> for ArenaString<'_, A> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> Borrow<str> for ArenaString<'_, A> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<I: SliceIndex<str>, A: ArenaAllocatorImpl + ?Sized> Index<I> for ArenaString<'_, A> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &I::Output {
+        Index::index(self.as_str(), index)
+    }
+}
+
+impl<I: SliceIndex<str>, A: ArenaAllocatorImpl + ?Sized> IndexMut<I> for ArenaString<'_, A> {
+    fn index_mut(&mut self, index: I) -> &mut I::Output {
+        IndexMut::index_mut(self.as_mut_str(), index)
+    }
+}
+
+/// Strings compare by their contents, regardless of the arena they live in.
+impl<A, B> PartialEq<ArenaString<'_, B>> for ArenaString<'_, A>
+where
+    A: ArenaAllocatorImpl + ?Sized,
+    B: ArenaAllocatorImpl + ?Sized,
+{
+    fn eq(&self, other: &ArenaString<'_, B>) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> PartialEq<str> for ArenaString<'_, A> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> PartialEq<&str> for ArenaString<'_, A> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> PartialEq<ArenaString<'_, A>> for str {
+    fn eq(&self, other: &ArenaString<'_, A>) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> PartialEq<ArenaString<'_, A>> for &str {
+    fn eq(&self, other: &ArenaString<'_, A>) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> Eq for ArenaString<'_, A> {}
+
+impl<A, B> PartialOrd<ArenaString<'_, B>> for ArenaString<'_, A>
+where
+    A: ArenaAllocatorImpl + ?Sized,
+    B: ArenaAllocatorImpl + ?Sized,
+{
+    fn partial_cmp(&self, other: &ArenaString<'_, B>) -> Option<Ordering> {
+        Some(self.as_str().cmp(other.as_str()))
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> Ord for ArenaString<'_, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+/// Hashes like `str`, so strings can be looked up by `&str` in maps.
+impl<A: ArenaAllocatorImpl + ?Sized> Hash for ArenaString<'_, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(self.as_str(), state)
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> fmt::Write for ArenaString<'_, A> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s).map_err(|_| fmt::Error)
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> fmt::Display for ArenaString<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<A: ArenaAllocatorImpl + ?Sized> fmt::Debug for ArenaString<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
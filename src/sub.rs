@@ -0,0 +1,124 @@
+use core::{alloc::Layout, fmt, ptr::NonNull};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl, BorrowedAllocator};
+
+/// A region carved out of a parent arena at a stronger alignment, e.g. to pack data for
+/// vectorized math together.
+///
+/// Every allocation is padded to a multiple of the alignment, so all of them start at an aligned
+/// address regardless of the alignment they request. The region is handed back to the parent when
+/// the sub-arena is dropped, which only reclaims it if it is still the parent's most recent allocation.
+pub struct SubArena<'a> {
+    arena: BorrowedAllocator<'a>,
+    /// The block taken from the parent, which may start with padding before the region.
+    block: NonNull<[u8]>,
+    capacity: usize,
+    align: usize,
+    parent: &'a dyn ArenaAllocatorImpl,
+}
+
+impl<'a> SubArena<'a> {
+    pub(crate) fn new_in(
+        bytes: usize,
+        align: usize,
+        parent: &'a dyn ArenaAllocatorImpl,
+    ) -> AllocRes<Self> {
+        if !align.is_power_of_two() {
+            return Err(AllocError::new(AllocErrorKind::AlignmentUnsupported {
+                align,
+            }));
+        }
+        crate::check_align(align)?;
+        let layout = Layout::from_size_align(bytes, align).map_err(|_| {
+            AllocError::with_message(AllocErrorKind::Other, "sub-arena size overflows")
+        })?;
+        let mut block = parent.bump_alloc(layout)?;
+        let mut start = block.as_non_null_ptr();
+        if !start.addr().get().is_multiple_of(align) {
+            // the parent does not honour the alignment, make room to align the region ourselves
+            parent.dealloc(start, layout);
+            let padded =
+                Layout::from_size_align(bytes.saturating_add(align - 1), align).map_err(|_| {
+                    AllocError::with_message(AllocErrorKind::Other, "sub-arena size overflows")
+                })?;
+            block = parent.bump_alloc(padded)?;
+            start = unsafe {
+                block
+                    .as_non_null_ptr()
+                    .add(block.as_mut_ptr().align_offset(align))
+            };
+        }
+        // the block is exclusively ours until it is handed back in `drop`
+        let arena =
+            unsafe { BorrowedAllocator::from_raw_parts(start, bytes) }.with_min_align(align);
+        Ok(Self {
+            arena,
+            block,
+            capacity: bytes,
+            align,
+            parent,
+        })
+    }
+
+    /// The arena managing the region, e.g. to query its offset or dump it.
+    pub fn arena(&self) -> &BorrowedAllocator<'a> {
+        &self.arena
+    }
+
+    /// Size of the region in bytes.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The alignment every allocation starts at.
+    pub fn align(&self) -> usize {
+        self.align
+    }
+}
+
+impl ArenaAllocatorImpl for SubArena<'_> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.arena.bump_alloc(layout)
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.arena.dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        self.arena.try_dealloc(data, layout)
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.arena.reset()
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.arena.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.arena.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.arena.alloc_tagged(layout, tag)
+    }
+}
+
+impl fmt::Debug for SubArena<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SubArena")
+            .field("capacity", &self.capacity())
+            .field("align", &self.align)
+            .field("used", &self.arena.current_offset())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for SubArena<'_> {
+    fn drop(&mut self) {
+        let layout = unsafe { Layout::from_size_align_unchecked(self.block.len(), self.align) };
+        self.parent.dealloc(self.block.as_non_null_ptr(), layout);
+    }
+}
@@ -0,0 +1,357 @@
+use core::{
+    alloc::Layout,
+    cell::UnsafeCell,
+    fmt,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl};
+
+const ALIGN: usize = align_of::<usize>();
+const SL_LOG2: u32 = 4;
+const SL_COUNT: usize = 1 << SL_LOG2;
+/// Blocks below `1 << FL_SHIFT` bytes share the first level, split linearly.
+const FL_SHIFT: u32 = SL_LOG2 + ALIGN.trailing_zeros();
+const SMALL_BLOCK: usize = 1 << FL_SHIFT;
+const FL_COUNT: usize = (usize::BITS - FL_SHIFT + 1) as usize;
+
+const HEADER: usize = size_of::<BlockHeader>();
+/// A free block must hold its header and the links of its free list.
+const MIN_BLOCK: usize = size_of::<FreeBlock>();
+const FREE: usize = 1;
+
+/// Precedes every block, the size includes the header and carries the [`FREE`] flag.
+#[repr(C)]
+struct BlockHeader {
+    prev_phys: *mut FreeBlock,
+    size: usize,
+}
+
+/// A block, its links are only valid while it is free.
+#[repr(C)]
+struct FreeBlock {
+    header: BlockHeader,
+    next: *mut FreeBlock,
+    prev: *mut FreeBlock,
+}
+
+/// Segregated free lists, indexed by a first level power of two and a linear second level.
+struct Control {
+    fl_bitmap: usize,
+    sl_bitmap: [usize; FL_COUNT],
+    heads: [[*mut FreeBlock; SL_COUNT]; FL_COUNT],
+    free_bytes: usize,
+}
+
+/// A two-level segregated fit allocator, allocating and freeing in constant time,
+/// e.g. for hard real-time systems needing general purpose reuse with deterministic latency.
+///
+/// Every block carries a two word header, and adjacent free blocks are merged immediately.
+/// The free lists are kept in the allocator itself, which takes about `FL_COUNT * 16` words.
+///
+/// The allocator is not `Sync`, as its free lists cannot be updated without locking.
+/// Real-time systems typically keep one per core or task.
+pub struct TlsfAllocator<'a> {
+    control: UnsafeCell<Control>,
+    pool: NonNull<u8>,
+    len: usize,
+    _marker: PhantomData<&'a mut [u8]>,
+}
+
+unsafe impl Send for TlsfAllocator<'_> {}
+
+impl<'a> TlsfAllocator<'a> {
+    pub fn new(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        let len = buf.len();
+        let mut this = Self {
+            control: UnsafeCell::new(Control {
+                fl_bitmap: 0,
+                sl_bitmap: [0; FL_COUNT],
+                heads: [[ptr::null_mut(); SL_COUNT]; FL_COUNT],
+                free_bytes: 0,
+            }),
+            pool: NonNull::from(buf).cast(),
+            len,
+            _marker: PhantomData,
+        };
+        this.init();
+        this
+    }
+
+    pub fn from_slice(buf: &'a mut [u8]) -> Self {
+        let buf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+        Self::new(buf)
+    }
+
+    /// Turns the whole pool into a single free block, followed by a used sentinel of size zero.
+    fn init(&mut self) {
+        let control = self.control.get_mut();
+        control.fl_bitmap = 0;
+        control.sl_bitmap = [0; FL_COUNT];
+        control.heads = [[ptr::null_mut(); SL_COUNT]; FL_COUNT];
+        control.free_bytes = 0;
+
+        let start = self.pool.align_offset(ALIGN);
+        let usable = self.len.saturating_sub(start) / ALIGN * ALIGN;
+        if usable < MIN_BLOCK + HEADER {
+            return;
+        }
+        let size = usable - HEADER;
+        unsafe {
+            let first = self.pool.as_ptr().add(start).cast::<FreeBlock>();
+            let sentinel = first.byte_add(size);
+            first.write(FreeBlock {
+                header: BlockHeader {
+                    prev_phys: ptr::null_mut(),
+                    size: size | FREE,
+                },
+                next: ptr::null_mut(),
+                prev: ptr::null_mut(),
+            });
+            sentinel.cast::<BlockHeader>().write(BlockHeader {
+                prev_phys: first,
+                size: 0,
+            });
+            let control = self.control.get_mut();
+            control.insert(first);
+        }
+    }
+
+    /// The block size serving `layout`, if it can be represented.
+    fn block_size(layout: Layout) -> Option<usize> {
+        let payload = layout.size().checked_next_multiple_of(ALIGN)?;
+        Some(payload.checked_add(HEADER)?.max(MIN_BLOCK))
+    }
+
+    fn exhausted(layout: Layout, available: usize) -> AllocError {
+        AllocError::with_message(
+            AllocErrorKind::InsufficientCapacity {
+                requested: layout.size(),
+                available,
+            },
+            "No free block large enough",
+        )
+    }
+}
+
+impl Control {
+    /// The free list a block of `size` bytes belongs to.
+    fn mapping_insert(size: usize) -> (usize, usize) {
+        if size < SMALL_BLOCK {
+            (0, size / (SMALL_BLOCK / SL_COUNT))
+        } else {
+            let fl = size.ilog2();
+            let sl = (size >> (fl - SL_LOG2)) ^ SL_COUNT;
+            ((fl - FL_SHIFT + 1) as usize, sl)
+        }
+    }
+
+    /// The first free list whose blocks all hold at least `size` bytes.
+    fn mapping_search(size: usize) -> Option<(usize, usize)> {
+        let size = if size < SMALL_BLOCK {
+            size
+        } else {
+            size.checked_add((1 << (size.ilog2() - SL_LOG2)) - 1)?
+        };
+        Some(Self::mapping_insert(size))
+    }
+
+    unsafe fn insert(&mut self, block: *mut FreeBlock) {
+        unsafe {
+            let size = (*block).header.size & !FREE;
+            let (fl, sl) = Self::mapping_insert(size);
+            let head = self.heads[fl][sl];
+            (*block).next = head;
+            (*block).prev = ptr::null_mut();
+            if !head.is_null() {
+                (*head).prev = block;
+            }
+            self.heads[fl][sl] = block;
+            self.fl_bitmap |= 1 << fl;
+            self.sl_bitmap[fl] |= 1 << sl;
+            self.free_bytes += size;
+        }
+    }
+
+    unsafe fn remove(&mut self, block: *mut FreeBlock) {
+        unsafe {
+            let size = (*block).header.size & !FREE;
+            let (fl, sl) = Self::mapping_insert(size);
+            let (next, prev) = ((*block).next, (*block).prev);
+            if !next.is_null() {
+                (*next).prev = prev;
+            }
+            if prev.is_null() {
+                self.heads[fl][sl] = next;
+                if next.is_null() {
+                    self.sl_bitmap[fl] &= !(1 << sl);
+                    if self.sl_bitmap[fl] == 0 {
+                        self.fl_bitmap &= !(1 << fl);
+                    }
+                }
+            } else {
+                (*prev).next = next;
+            }
+            self.free_bytes -= size;
+        }
+    }
+
+    /// Takes a free block of at least `size` bytes off its list.
+    unsafe fn take(&mut self, size: usize) -> Option<*mut FreeBlock> {
+        let (mut fl, sl) = Self::mapping_search(size)?;
+        if fl >= FL_COUNT {
+            return None;
+        }
+        let mut sl_map = self.sl_bitmap[fl] & (!0 << sl);
+        if sl_map == 0 {
+            let fl_map = self.fl_bitmap & 1usize.checked_shl(fl as u32 + 1)?.wrapping_neg();
+            if fl_map == 0 {
+                return None;
+            }
+            fl = fl_map.trailing_zeros() as usize;
+            sl_map = self.sl_bitmap[fl];
+        }
+        let block = self.heads[fl][sl_map.trailing_zeros() as usize];
+        unsafe { self.remove(block) };
+        Some(block)
+    }
+
+    /// Returns the bytes of `block` past `size` to the free lists, if they form a block of their own.
+    unsafe fn split(&mut self, block: *mut FreeBlock, size: usize) {
+        unsafe {
+            let total = (*block).header.size & !FREE;
+            if total < size + MIN_BLOCK {
+                return;
+            }
+            let rest = block.byte_add(size);
+            (*rest).header = BlockHeader {
+                prev_phys: block,
+                size: (total - size) | FREE,
+            };
+            (*next_phys(rest)).header.prev_phys = rest;
+            (*block).header.size = size | ((*block).header.size & FREE);
+            self.insert(rest);
+        }
+    }
+
+    /// Marks `block` free, merges it with free neighbours and puts it on its list.
+    unsafe fn release(&mut self, mut block: *mut FreeBlock) {
+        unsafe {
+            (*block).header.size |= FREE;
+            let prev = (*block).header.prev_phys;
+            if !prev.is_null() && (*prev).header.size & FREE != 0 {
+                self.remove(prev);
+                (*prev).header.size += (*block).header.size & !FREE;
+                block = prev;
+                (*next_phys(block)).header.prev_phys = block;
+            }
+            let next = next_phys(block);
+            if (*next).header.size & FREE != 0 {
+                self.remove(next);
+                (*block).header.size += (*next).header.size & !FREE;
+                (*next_phys(block)).header.prev_phys = block;
+            }
+            self.insert(block);
+        }
+    }
+}
+
+unsafe fn next_phys(block: *mut FreeBlock) -> *mut FreeBlock {
+    unsafe { block.byte_add((*block).header.size & !FREE) }
+}
+
+impl ArenaAllocatorImpl for TlsfAllocator<'_> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        crate::check_align(layout.align())?;
+        if layout.size() == 0 {
+            return Ok(NonNull::slice_from_raw_parts(layout.dangling_ptr(), 0));
+        }
+        let control = unsafe { &mut *self.control.get() };
+        let size =
+            Self::block_size(layout).ok_or_else(|| Self::exhausted(layout, control.free_bytes))?;
+        // over-aligned requests search for room to split off a leading free block
+        let search = if layout.align() <= ALIGN {
+            Some(size)
+        } else {
+            size.checked_add(layout.align() + MIN_BLOCK)
+        };
+        let mut block = search
+            .and_then(|search| unsafe { control.take(search) })
+            .ok_or_else(|| Self::exhausted(layout, control.free_bytes))?;
+        unsafe {
+            let payload = block.byte_add(HEADER).cast::<u8>();
+            let mut gap = payload.align_offset(layout.align());
+            if gap != 0 {
+                if gap < MIN_BLOCK {
+                    gap += (MIN_BLOCK - gap).next_multiple_of(layout.align());
+                }
+                let aligned = block.byte_add(gap);
+                let total = (*block).header.size & !FREE;
+                (*aligned).header = BlockHeader {
+                    prev_phys: block,
+                    size: total - gap,
+                };
+                (*next_phys(aligned)).header.prev_phys = aligned;
+                (*block).header.size = gap | FREE;
+                control.insert(block);
+                block = aligned;
+            }
+            control.split(block, size);
+            (*block).header.size &= !FREE;
+            let payload = NonNull::new_unchecked(block.byte_add(HEADER).cast::<u8>());
+            Ok(NonNull::slice_from_raw_parts(payload, layout.size()))
+        }
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.try_dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        if layout.size() == 0
+            || !self.contains(data)
+            || !data.addr().get().is_multiple_of(ALIGN)
+            || data.addr().get() - self.pool.addr().get() < HEADER
+        {
+            return false;
+        }
+        let Some(need) = Self::block_size(layout) else {
+            return false;
+        };
+        let block = unsafe { data.as_ptr().byte_sub(HEADER).cast::<FreeBlock>() };
+        // a used block holds at least `need` bytes, and less than a split would have left over
+        let size = unsafe { (*block).header.size };
+        if size & FREE != 0 || !(need..need + MIN_BLOCK).contains(&size) {
+            return false;
+        }
+        let control = unsafe { &mut *self.control.get() };
+        unsafe { control.release(block) };
+        true
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        self.init();
+        Ok(())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        let base = self.pool.addr().get();
+        (base..base + self.len).contains(&ptr.addr().get())
+    }
+
+    /// The bytes held by free blocks, including their headers.
+    fn remaining_hint(&self) -> Option<usize> {
+        Some(unsafe { (*self.control.get()).free_bytes })
+    }
+}
+
+impl fmt::Debug for TlsfAllocator<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsfAllocator")
+            .field("len", &self.len)
+            .field("free", &self.remaining_hint())
+            .finish()
+    }
+}
@@ -0,0 +1,228 @@
+use core::{
+    alloc::Layout,
+    fmt,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr::NonNull,
+    slice,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(feature = "alloc")]
+use crate::{AllocError, AllocErrorKind};
+use crate::{AllocRes, ArenaAllocatorImpl};
+
+/// A slot of a [`RegionTracker`]'s log, recording one allocation or free.
+#[derive(Debug, Clone, Copy)]
+pub struct RegionEntry {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    tag: Option<&'static str>,
+    kind: EntryKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryKind {
+    Live,
+    Free,
+    /// An allocation cancelled by a later free, or a free already matched with its allocation.
+    Dead,
+}
+
+/// Wraps an allocator and tracks its live allocations in a caller provided buffer,
+/// so tools can scan them (relocation passes, checksumming, custom serializers)
+/// without maintaining their own shadow list.
+///
+/// Every successful allocation and every reclaiming free takes a slot of the log. Once the log is
+/// full, further operations are counted in [`untracked`](Self::untracked) and not reflected in
+/// [`regions`](Self::regions).
+pub struct RegionTracker<'b, A> {
+    inner: A,
+    log: NonNull<[MaybeUninit<RegionEntry>]>,
+    next: AtomicUsize,
+    _marker: PhantomData<&'b mut [MaybeUninit<RegionEntry>]>,
+}
+
+unsafe impl<A: Send> Send for RegionTracker<'_, A> {}
+// Every slot of the log is claimed by exactly one thread through `next`.
+unsafe impl<A: Sync> Sync for RegionTracker<'_, A> {}
+
+impl<'b, A: ArenaAllocatorImpl> RegionTracker<'b, A> {
+    pub fn new(inner: A, log: &'b mut [MaybeUninit<RegionEntry>]) -> Self {
+        Self {
+            inner,
+            log: NonNull::from(log),
+            next: AtomicUsize::new(0),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    /// The number of operations which did not fit into the log.
+    pub fn untracked(&self) -> usize {
+        self.next
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.log.len())
+    }
+
+    /// The live allocations as `(start, layout, tag)`, in the order they were made.
+    /// Allocations made through [`bump_alloc`](ArenaAllocatorImpl::bump_alloc) carry no tag.
+    pub fn regions(
+        &mut self,
+    ) -> impl Iterator<Item = (NonNull<u8>, Layout, Option<&'static str>)> + '_ {
+        let len = (*self.next.get_mut()).min(self.log.len());
+        let entries =
+            unsafe { slice::from_raw_parts_mut(self.log.as_ptr().cast::<RegionEntry>(), len) };
+        // match each free with the most recent live allocation of its block
+        for idx in 0..entries.len() {
+            if entries[idx].kind != EntryKind::Free {
+                continue;
+            }
+            entries[idx].kind = EntryKind::Dead;
+            let freed = entries[idx].ptr;
+            if let Some(alloc) = entries[..idx]
+                .iter_mut()
+                .rev()
+                .find(|entry| entry.kind == EntryKind::Live && entry.ptr == freed)
+            {
+                alloc.kind = EntryKind::Dead;
+            }
+        }
+        entries
+            .iter()
+            .filter(|entry| entry.kind == EntryKind::Live)
+            .map(|entry| (entry.ptr, entry.layout, entry.tag))
+    }
+
+    /// Writes the live allocations to `out`, one per line with its offset, size and tag, e.g. to
+    /// log them over a serial line after an OOM.
+    ///
+    /// The wrapped allocator's buffer is unknown to the tracker, so offsets count from the lowest
+    /// live region, whose address heads the dump.
+    pub fn dump<W: fmt::Write>(&mut self, out: &mut W) -> fmt::Result {
+        let Some(base) = self.regions().map(|(ptr, ..)| ptr.addr().get()).min() else {
+            return writeln!(out, "no live regions");
+        };
+        writeln!(out, "live regions from {base:#x}:")?;
+        for (ptr, layout, tag) in self.regions() {
+            let offset = ptr.addr().get() - base;
+            let tag = tag.unwrap_or("untagged");
+            writeln!(out, "  +{offset}: {} bytes, {tag}", layout.size())?;
+        }
+        match self.untracked() {
+            0 => Ok(()),
+            untracked => writeln!(out, "{untracked} operations not tracked"),
+        }
+    }
+
+    /// Copies the live allocations into a new arena sized exactly to fit them, reclaiming the
+    /// space of freed regions.
+    ///
+    /// The regions are copied bitwise in allocation order, so the copy is only meaningful for
+    /// relocatable data, e.g. holding offsets instead of pointers. `fixup` is called with the old
+    /// and new address and the layout of every region once it has been copied, to patch pointers.
+    ///
+    /// Fails if the log overflowed, as the copy would silently miss the [`untracked`](Self::untracked)
+    /// regions.
+    #[cfg(feature = "alloc")]
+    pub fn clone_compact(
+        &mut self,
+        mut fixup: impl FnMut(NonNull<u8>, NonNull<u8>, Layout),
+    ) -> AllocRes<crate::HeapAllocator> {
+        if self.untracked() != 0 {
+            return Err(AllocError::with_message(
+                AllocErrorKind::Other,
+                "the region log overflowed",
+            ));
+        }
+        let mut total = Layout::new::<()>();
+        for (_, layout, _) in self.regions() {
+            total = total
+                .extend(layout)
+                .map_err(|_| {
+                    AllocError::with_message(AllocErrorKind::Other, "compacted size overflows")
+                })?
+                .0;
+        }
+        let arena = crate::HeapAllocator::try_for_layout(total)?;
+        for (old, layout, tag) in self.regions() {
+            let new = match tag {
+                Some(tag) => arena.alloc_tagged(layout, tag),
+                None => arena.bump_alloc(layout),
+            }?
+            .as_non_null_ptr();
+            unsafe { core::ptr::copy_nonoverlapping(old.as_ptr(), new.as_ptr(), layout.size()) };
+            fixup(old, new, layout);
+        }
+        Ok(arena)
+    }
+
+    fn log(&self, ptr: NonNull<u8>, layout: Layout, tag: Option<&'static str>, kind: EntryKind) {
+        let idx = self.next.fetch_add(1, Ordering::Relaxed);
+        if idx < self.log.len() {
+            let entry = RegionEntry {
+                ptr,
+                layout,
+                tag,
+                kind,
+            };
+            unsafe { (*self.log.as_ptr())[idx].write(entry) };
+        }
+    }
+
+    fn track(
+        &self,
+        layout: Layout,
+        tag: Option<&'static str>,
+        res: AllocRes<NonNull<[u8]>>,
+    ) -> AllocRes<NonNull<[u8]>> {
+        if let Ok(block) = res {
+            self.log(block.as_non_null_ptr(), layout, tag, EntryKind::Live);
+        }
+        res
+    }
+}
+
+impl<A: ArenaAllocatorImpl> ArenaAllocatorImpl for RegionTracker<'_, A> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.track(layout, None, self.inner.bump_alloc(layout))
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.try_dealloc(data, layout);
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        let freed = self.inner.try_dealloc(data, layout);
+        if freed {
+            self.log(data, layout, None, EntryKind::Free);
+        }
+        freed
+    }
+
+    /// Resets the wrapped allocator and forgets all tracked regions.
+    fn reset(&mut self) -> AllocRes<()> {
+        self.inner.reset()?;
+        *self.next.get_mut() = 0;
+        Ok(())
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.inner.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.inner.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.track(layout, Some(tag), self.inner.alloc_tagged(layout, tag))
+    }
+}
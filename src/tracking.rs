@@ -0,0 +1,109 @@
+use core::{alloc::Layout, ptr::NonNull};
+
+use crate::{AllocErrorKind, AllocRes, ArenaAllocatorImpl};
+
+/// A call made through a [`TrackingAllocator`], with its outcome.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocCall {
+    /// An allocation, `tag` is set if it was made through
+    /// [`alloc_tagged`](ArenaAllocatorImpl::alloc_tagged).
+    Alloc {
+        layout: Layout,
+        tag: Option<&'static str>,
+        result: Result<NonNull<u8>, AllocErrorKind>,
+    },
+    Dealloc {
+        ptr: NonNull<u8>,
+        layout: Layout,
+    },
+    TryDealloc {
+        ptr: NonNull<u8>,
+        layout: Layout,
+        reclaimed: bool,
+    },
+    Reset {
+        result: Result<(), AllocErrorKind>,
+    },
+}
+
+/// Wraps an allocator and reports every call to `sink`, e.g. to assert in tests that a parser
+/// performs exactly three allocations.
+///
+/// The sink is called after the wrapped allocator returned, from the calling thread.
+pub struct TrackingAllocator<A, F> {
+    inner: A,
+    sink: F,
+}
+
+impl<A: ArenaAllocatorImpl, F: Fn(AllocCall)> TrackingAllocator<A, F> {
+    pub fn new(inner: A, sink: F) -> Self {
+        Self { inner, sink }
+    }
+
+    pub fn inner(&self) -> &A {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> A {
+        self.inner
+    }
+
+    fn track_alloc(
+        &self,
+        layout: Layout,
+        tag: Option<&'static str>,
+        res: AllocRes<NonNull<[u8]>>,
+    ) -> AllocRes<NonNull<[u8]>> {
+        let result = match &res {
+            Ok(block) => Ok(block.as_non_null_ptr()),
+            Err(err) => Err(err.kind()),
+        };
+        (self.sink)(AllocCall::Alloc {
+            layout,
+            tag,
+            result,
+        });
+        res
+    }
+}
+
+impl<A: ArenaAllocatorImpl, F: Fn(AllocCall)> ArenaAllocatorImpl for TrackingAllocator<A, F> {
+    fn bump_alloc(&self, layout: Layout) -> AllocRes<NonNull<[u8]>> {
+        self.track_alloc(layout, None, self.inner.bump_alloc(layout))
+    }
+
+    fn dealloc(&self, data: NonNull<u8>, layout: Layout) {
+        self.inner.dealloc(data, layout);
+        (self.sink)(AllocCall::Dealloc { ptr: data, layout });
+    }
+
+    fn try_dealloc(&self, data: NonNull<u8>, layout: Layout) -> bool {
+        let reclaimed = self.inner.try_dealloc(data, layout);
+        (self.sink)(AllocCall::TryDealloc {
+            ptr: data,
+            layout,
+            reclaimed,
+        });
+        reclaimed
+    }
+
+    fn reset(&mut self) -> AllocRes<()> {
+        let res = self.inner.reset();
+        (self.sink)(AllocCall::Reset {
+            result: res.as_ref().map(|_| ()).map_err(|err| err.kind()),
+        });
+        res
+    }
+
+    fn contains(&self, ptr: NonNull<u8>) -> bool {
+        self.inner.contains(ptr)
+    }
+
+    fn remaining_hint(&self) -> Option<usize> {
+        self.inner.remaining_hint()
+    }
+
+    fn alloc_tagged(&self, layout: Layout, tag: &'static str) -> AllocRes<NonNull<[u8]>> {
+        self.track_alloc(layout, Some(tag), self.inner.alloc_tagged(layout, tag))
+    }
+}
@@ -0,0 +1,118 @@
+use core::{
+    alloc::Layout,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes};
+
+/// Signature of the hook invoked by [`handle_arena_oom`].
+pub type OomHook = fn(Layout, &AllocError);
+
+static OOM_HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+/// Registers `hook` to be called by [`handle_arena_oom`] before it panics,
+/// e.g. to dump the arena or abort instead of unwinding.
+pub fn set_oom_hook(hook: OomHook) {
+    OOM_HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+/// Removes the hook registered by [`set_oom_hook`].
+pub fn take_oom_hook() -> Option<OomHook> {
+    let hook = OOM_HOOK.swap(ptr::null_mut(), Ordering::AcqRel);
+    (!hook.is_null()).then(|| unsafe { core::mem::transmute::<*mut (), OomHook>(hook) })
+}
+
+/// The central handler of the infallible allocation APIs.
+///
+/// Calls the hook registered with [`set_oom_hook`], if any, and panics if it returns.
+/// Keeping this out of line keeps the panic formatting out of every call site.
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub fn handle_arena_oom(layout: Layout, err: AllocError) -> ! {
+    let hook = OOM_HOOK.load(Ordering::Acquire);
+    if !hook.is_null() {
+        let hook = unsafe { core::mem::transmute::<*mut (), OomHook>(hook) };
+        hook(layout, &err);
+    }
+    panic!("arena allocation of {layout:?} failed: {err}")
+}
+
+/// The largest alignment the allocators of this crate accept, larger requests fail with
+/// [`AlignmentUnsupported`](AllocErrorKind::AlignmentUnsupported).
+///
+/// Set at build time through the `WAIT_FREE_ARENA_MAX_ALIGN` environment variable, which must
+/// hold a power of two, e.g. `WAIT_FREE_ARENA_MAX_ALIGN=8` on targets where a larger alignment is
+/// always a bug. The bound lets the compiler fold the align-up arithmetic, `1` removes it entirely.
+/// Defaults to the largest alignment a [`Layout`] can describe.
+pub const MAX_ALIGN: usize = match option_env!("WAIT_FREE_ARENA_MAX_ALIGN") {
+    Some(value) => parse_max_align(value),
+    None => 1 << 29,
+};
+
+const fn parse_max_align(value: &str) -> usize {
+    let bytes = value.as_bytes();
+    assert!(!bytes.is_empty(), "WAIT_FREE_ARENA_MAX_ALIGN is empty");
+    let mut align = 0usize;
+    let mut idx = 0;
+    while idx < bytes.len() {
+        let digit = bytes[idx];
+        assert!(
+            digit.is_ascii_digit(),
+            "WAIT_FREE_ARENA_MAX_ALIGN must be a decimal number"
+        );
+        align = match align.checked_mul(10) {
+            Some(align) => align + (digit - b'0') as usize,
+            None => panic!("WAIT_FREE_ARENA_MAX_ALIGN is too large"),
+        };
+        idx += 1;
+    }
+    assert!(
+        align.is_power_of_two() && align <= 1 << 29,
+        "WAIT_FREE_ARENA_MAX_ALIGN must be a power of two of at most 2^29"
+    );
+    align
+}
+
+/// Rejects alignments above [`MAX_ALIGN`].
+#[inline(always)]
+pub(crate) fn check_align(align: usize) -> AllocRes<()> {
+    if align > MAX_ALIGN {
+        return Err(AllocError::with_message(
+            AllocErrorKind::AlignmentUnsupported { align },
+            "alignment exceeds MAX_ALIGN",
+        ));
+    }
+    Ok(())
+}
+
+/// Forwards [`fmt::Write`](core::fmt::Write) to an [`std::io::Write`], keeping the first error.
+#[cfg(feature = "std")]
+pub(crate) struct IoAdapter<'w, W> {
+    out: &'w mut W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<'w, W: std::io::Write> IoAdapter<'w, W> {
+    pub(crate) fn new(out: &'w mut W) -> Self {
+        Self { out, error: None }
+    }
+
+    /// The error which made formatting fail.
+    pub(crate) fn into_error(self) -> std::io::Error {
+        self.error
+            .unwrap_or_else(|| std::io::Error::other("formatting failed"))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> core::fmt::Write for IoAdapter<'_, W> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.out.write_all(s.as_bytes()).map_err(|err| {
+            self.error = Some(err);
+            core::fmt::Error
+        })
+    }
+}
@@ -0,0 +1,387 @@
+use core::{
+    alloc::Layout,
+    borrow::{Borrow, BorrowMut},
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+    ops::{Deref, DerefMut, Index, IndexMut},
+    ptr::{self, NonNull},
+    slice::{self, SliceIndex},
+};
+
+use crate::{AllocError, AllocErrorKind, AllocRes, ArenaAllocatorImpl};
+
+/// A growable vector living in an arena.
+///
+/// Growing allocates a new block and copies the elements over, the old block is
+/// handed back to the arena and can be reused if it was the most recent allocation.
+///
+/// Every growing operation returns an [`AllocRes`], they are already what `try_push` and
+/// `try_reserve` are for `alloc::vec::Vec`. Only the [`Extend`] impls cannot report running out
+/// of memory, [`try_extend`](Self::try_extend) is their fallible counterpart.
+pub struct ArenaVec<'a, T, A: ArenaAllocatorImpl + ?Sized> {
+    ptr: NonNull<T>,
+    len: usize,
+    cap: usize,
+    alloc: &'a A,
+}
+
+impl<'a, T, A: ArenaAllocatorImpl + ?Sized> ArenaVec<'a, T, A> {
+    pub fn new_in(alloc: &'a A) -> Self {
+        Self {
+            ptr: NonNull::dangling(),
+            len: 0,
+            cap: if mem::size_of::<T>() == 0 {
+                usize::MAX
+            } else {
+                0
+            },
+            alloc,
+        }
+    }
+
+    pub fn with_capacity_in(capacity: usize, alloc: &'a A) -> AllocRes<Self> {
+        let mut vec = Self::new_in(alloc);
+        vec.reserve(capacity)?;
+        Ok(vec)
+    }
+
+    /// Collects `iter` into a new vector in `alloc`.
+    pub fn from_iter_in(iter: impl IntoIterator<Item = T>, alloc: &'a A) -> AllocRes<Self> {
+        let mut vec = Self::new_in(alloc);
+        vec.try_extend(iter)?;
+        Ok(vec)
+    }
+
+    pub fn allocator(&self) -> &'a A {
+        self.alloc
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Makes room for at least `additional` more elements.
+    #[doc(alias = "try_reserve")]
+    pub fn reserve(&mut self, additional: usize) -> AllocRes<()> {
+        let required = self
+            .len
+            .checked_add(additional)
+            .ok_or_else(capacity_overflow)?;
+        if required <= self.cap {
+            return Ok(());
+        }
+        let new_cap = required.max(self.cap * 2).max(4);
+        let layout = Layout::array::<T>(new_cap).map_err(|_| capacity_overflow())?;
+        let new_ptr = self.alloc.bump_alloc(layout)?.as_non_null_ptr().cast::<T>();
+        unsafe { ptr::copy_nonoverlapping(self.ptr.as_ptr(), new_ptr.as_ptr(), self.len) };
+        self.release();
+        self.ptr = new_ptr;
+        self.cap = new_cap;
+        Ok(())
+    }
+
+    #[doc(alias = "try_push")]
+    pub fn push(&mut self, value: T) -> AllocRes<()> {
+        self.reserve(1)?;
+        unsafe { self.ptr.add(self.len).write(value) };
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Appends the elements of `iter`, reserving room for its lower size hint up front.
+    pub fn try_extend(&mut self, iter: impl IntoIterator<Item = T>) -> AllocRes<()> {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0)?;
+        for elem in iter {
+            self.push(elem)?;
+        }
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(unsafe { self.ptr.add(self.len).read() })
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+        let tail =
+            ptr::slice_from_raw_parts_mut(unsafe { self.ptr.add(len).as_ptr() }, self.len - len);
+        self.len = len;
+        unsafe { ptr::drop_in_place(tail) };
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Hands unused capacity back to the arena, which can reuse it if the vector is the most recent allocation.
+    pub fn shrink_to_fit(&mut self) {
+        if self.cap <= self.len || mem::size_of::<T>() == 0 {
+            return;
+        }
+        let layout = unsafe { Layout::array::<T>(self.cap - self.len).unwrap_unchecked() };
+        self.alloc
+            .dealloc(unsafe { self.ptr.add(self.len).cast() }, layout);
+        self.cap = self.len;
+    }
+
+    /// Shrinks the vector and leaks its elements for the lifetime of the arena.
+    pub fn leak(self) -> &'a mut [T] {
+        let mut vec = mem::ManuallyDrop::new(self);
+        vec.shrink_to_fit();
+        unsafe { slice::from_raw_parts_mut(vec.ptr.as_ptr(), vec.len) }
+    }
+
+    /// Shrinks the vector and converts it into a [`Box`](crate::boxed::Box), which still drops the elements.
+    #[cfg(feature = "boxed")]
+    pub fn into_boxed_slice(self) -> crate::boxed::Box<'a, [T]> {
+        unsafe { crate::boxed::Box::from_raw(self.leak()) }
+    }
+
+    /// Hands the current block back to the arena without touching the elements.
+    fn release(&mut self) {
+        if self.cap != 0 && mem::size_of::<T>() != 0 {
+            let layout = unsafe { Layout::array::<T>(self.cap).unwrap_unchecked() };
+            self.alloc.dealloc(self.ptr.cast(), layout);
+        }
+    }
+}
+
+impl<T: Clone, A: ArenaAllocatorImpl + ?Sized> ArenaVec<'_, T, A> {
+    pub fn extend_from_slice(&mut self, other: &[T]) -> AllocRes<()> {
+        self.reserve(other.len())?;
+        for elem in other {
+            unsafe { self.ptr.add(self.len).write(elem.clone()) };
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+fn capacity_overflow() -> AllocError {
+    AllocError::with_message(AllocErrorKind::Other, "capacity overflow")
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> Deref for ArenaVec<'_, T, A> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> DerefMut for ArenaVec<'_, T, A> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> AsRef<[T]> for ArenaVec<'_, T, A> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> AsMut<[T]> for ArenaVec<'_, T, A> {
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> Borrow<[T]> for ArenaVec<'_, T, A> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> BorrowMut<[T]> for ArenaVec<'_, T, A> {
+    fn borrow_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, A: ArenaAllocatorImpl + ?Sized> Index<I> for ArenaVec<'_, T, A> {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &I::Output {
+        Index::index(self.as_slice(), index)
+    }
+}
+
+impl<T, I: SliceIndex<[T]>, A: ArenaAllocatorImpl + ?Sized> IndexMut<I> for ArenaVec<'_, T, A> {
+    fn index_mut(&mut self, index: I) -> &mut I::Output {
+        IndexMut::index_mut(self.as_mut_slice(), index)
+    }
+}
+
+/// Vectors compare by their elements, regardless of the arena they live in.
+impl<T, U, A, B> PartialEq<ArenaVec<'_, U, B>> for ArenaVec<'_, T, A>
+where
+    T: PartialEq<U>,
+    A: ArenaAllocatorImpl + ?Sized,
+    B: ArenaAllocatorImpl + ?Sized,
+{
+    fn eq(&self, other: &ArenaVec<'_, U, B>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: PartialEq<U>, U, A: ArenaAllocatorImpl + ?Sized> PartialEq<[U]> for ArenaVec<'_, T, A> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: PartialEq<U>, U, A: ArenaAllocatorImpl + ?Sized> PartialEq<&[U]> for ArenaVec<'_, T, A> {
+    fn eq(&self, other: &&[U]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl<T: PartialEq<U>, U, A: ArenaAllocatorImpl + ?Sized, const N: usize> PartialEq<[U; N]>
+    for ArenaVec<'_, T, A>
+{
+    fn eq(&self, other: &[U; N]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<T: Eq, A: ArenaAllocatorImpl + ?Sized> Eq for ArenaVec<'_, T, A> {}
+
+impl<T: PartialOrd, A, B> PartialOrd<ArenaVec<'_, T, B>> for ArenaVec<'_, T, A>
+where
+    A: ArenaAllocatorImpl + ?Sized,
+    B: ArenaAllocatorImpl + ?Sized,
+{
+    fn partial_cmp(&self, other: &ArenaVec<'_, T, B>) -> Option<Ordering> {
+        PartialOrd::partial_cmp(self.as_slice(), other.as_slice())
+    }
+}
+
+impl<T: Ord, A: ArenaAllocatorImpl + ?Sized> Ord for ArenaVec<'_, T, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Ord::cmp(self.as_slice(), other.as_slice())
+    }
+}
+
+/// Hashes like the slice of its elements, so vectors can be looked up by `&[T]` in maps.
+impl<T: Hash, A: ArenaAllocatorImpl + ?Sized> Hash for ArenaVec<'_, T, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        Hash::hash(self.as_slice(), state)
+    }
+}
+
+/// Lets serializers write straight into the arena.
+/// Frozen buffers are plain `&[u8]`, which already implement `io::Read` and work with `io::Cursor`.
+#[cfg(feature = "std")]
+impl<A: ArenaAllocatorImpl + ?Sized> std::io::Write for ArenaVec<'_, u8, A> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.extend_from_slice(buf)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::OutOfMemory, err))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Calls [`handle_arena_oom`](crate::handle_arena_oom) if the arena runs out of memory.
+impl<T, A: ArenaAllocatorImpl + ?Sized> Extend<T> for ArenaVec<'_, T, A> {
+    #[track_caller]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        if let Err(err) = self.try_extend(iter) {
+            crate::handle_arena_oom(Layout::new::<T>(), err);
+        }
+    }
+}
+
+impl<'b, T: Copy + 'b, A: ArenaAllocatorImpl + ?Sized> Extend<&'b T> for ArenaVec<'_, T, A> {
+    #[track_caller]
+    fn extend<I: IntoIterator<Item = &'b T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<A: ArenaAllocatorImpl + ?Sized> embedded_io::ErrorType for ArenaVec<'_, u8, A> {
+    type Error = AllocError;
+}
+
+/// Lets `no_std` drivers and protocol stacks stream into the arena.
+/// Frozen buffers are plain `&[u8]`, which already implement `embedded_io::Read`.
+#[cfg(feature = "embedded-io")]
+impl<A: ArenaAllocatorImpl + ?Sized> embedded_io::Write for ArenaVec<'_, u8, A> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, AllocError> {
+        self.extend_from_slice(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), AllocError> {
+        Ok(())
+    }
+}
+
+impl<T: fmt::Debug, A: ArenaAllocatorImpl + ?Sized> fmt::Debug for ArenaVec<'_, T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T, A: ArenaAllocatorImpl + ?Sized> Drop for ArenaVec<'_, T, A> {
+    fn drop(&mut self) {
+        self.clear();
+        self.release();
+    }
+}
+
+/// Creates an [`ArenaVec`] like `vec!`, returning an [`AllocRes`] of it.
+///
+/// ```
+/// # use wait_free_arena::{ArenaVec, StackAllocator, arena_vec};
+/// let arena: StackAllocator<32> = StackAllocator::new();
+/// let list = arena_vec![1u8, 2, 3; in &arena].unwrap();
+/// let repeated = arena_vec![0u8; 4, in &arena].unwrap();
+/// let empty: ArenaVec<u8, _> = arena_vec![in &arena].unwrap();
+/// assert_eq!((&*list, &*repeated, empty.len()), (&[1, 2, 3][..], &[0; 4][..], 0));
+/// ```
+#[macro_export]
+macro_rules! arena_vec {
+    (in $arena:expr $(,)?) => {
+        $crate::AllocRes::Ok($crate::ArenaVec::new_in($arena))
+    };
+    ($($value:expr),+ $(,)? ; in $arena:expr $(,)?) => {
+        $crate::ArenaVec::from_iter_in([$($value),+], $arena)
+    };
+    ($value:expr ; $len:expr, in $arena:expr $(,)?) => {
+        $crate::ArenaVec::from_iter_in(::core::iter::repeat_n($value, $len), $arena)
+    };
+}
@@ -0,0 +1,121 @@
+//! Kani proof harnesses for the bump path of the core arena, run with `cargo kani`.
+//!
+//! The harnesses allocate a bounded sequence of arbitrary layouts from a small buffer and check
+//! that every block lies in bounds, is aligned to its layout and that no two live blocks overlap.
+//! Zero sized blocks are dangling and leave the cursor untouched. Kani fails a harness on any
+//! reachable panic, including arithmetic overflow, so they also cover the no-panic guarantee of
+//! [`IsrSafeAlloc`](crate::IsrSafeAlloc).
+
+use core::alloc::Layout;
+
+use crate::{ArenaAllocatorImpl, BorrowedAllocator, StackAllocator};
+
+const CAPACITY: usize = 32;
+const STEPS: usize = 3;
+
+fn any_layout() -> Layout {
+    let size: usize = kani::any();
+    let shift: u32 = kani::any();
+    kani::assume(size <= CAPACITY && shift <= 4);
+    Layout::from_size_align(size, 1 << shift).unwrap()
+}
+
+fn in_bounds(base: usize, start: usize, len: usize) -> bool {
+    start >= base && start + len <= base + CAPACITY
+}
+
+fn disjoint(a: (usize, usize), b: (usize, usize)) -> bool {
+    a.1 == 0 || b.1 == 0 || a.0 + a.1 <= b.0 || b.0 + b.1 <= a.0
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn bump_blocks_are_in_bounds_aligned_and_disjoint() {
+    let mut buf = [0u8; CAPACITY];
+    let base = buf.as_ptr().addr();
+    let arena = BorrowedAllocator::from_slice(&mut buf);
+    let mut blocks = [(0usize, 0usize); STEPS];
+    for idx in 0..STEPS {
+        let layout = any_layout();
+        let offset = arena.current_offset();
+        if let Ok(block) = arena.bump_alloc(layout) {
+            let block = (block.addr().get(), block.len());
+            assert!(block.1 == layout.size());
+            assert!(block.0 % layout.align() == 0);
+            if block.1 == 0 {
+                assert!(arena.current_offset() == offset);
+            } else {
+                assert!(in_bounds(base, block.0, block.1));
+            }
+            for &other in &blocks[..idx] {
+                assert!(disjoint(block, other));
+            }
+            blocks[idx] = block;
+        }
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn dealloc_only_reclaims_the_tail() {
+    let mut buf = [0u8; CAPACITY];
+    let arena = BorrowedAllocator::from_slice(&mut buf);
+    let (first, second) = (any_layout(), any_layout());
+    let (Ok(a), Ok(b)) = (arena.bump_alloc(first), arena.bump_alloc(second)) else {
+        return;
+    };
+    let offset = arena.current_offset();
+    if second.size() != 0 {
+        assert!(!arena.try_dealloc(a.as_non_null_ptr(), first));
+        assert!(arena.current_offset() == offset);
+    }
+    if second.size() == 0 {
+        // nothing to reclaim
+        assert!(!arena.try_dealloc(b.as_non_null_ptr(), second));
+        assert!(arena.current_offset() == offset);
+        return;
+    }
+    assert!(arena.try_dealloc(b.as_non_null_ptr(), second));
+    assert!(arena.current_offset() == offset - second.size());
+    // the reclaimed space is handed out again without overlapping the older block
+    if let Ok(c) = arena.bump_alloc(second) {
+        assert!(disjoint(
+            (a.addr().get(), a.len()),
+            (c.addr().get(), c.len())
+        ));
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn alloc_layout_is_aligned() {
+    let mut buf = [0u8; CAPACITY];
+    let base = buf.as_ptr().addr();
+    let arena = BorrowedAllocator::from_slice(&mut buf);
+    for _ in 0..STEPS {
+        let layout = any_layout();
+        if let Ok(ptr) = arena.alloc_layout(layout) {
+            assert!(ptr.addr().get() % layout.align() == 0);
+            assert!(layout.size() == 0 || in_bounds(base, ptr.addr().get(), layout.size()));
+        }
+    }
+}
+
+#[kani::proof]
+#[kani::unwind(4)]
+fn stack_allocator_never_panics() {
+    let arena = StackAllocator::<CAPACITY>::new();
+    for _ in 0..STEPS {
+        let size: usize = kani::any();
+        let shift: u32 = kani::any();
+        kani::assume(shift < usize::BITS);
+        let Ok(layout) = Layout::from_size_align(size, 1 << shift) else {
+            continue;
+        };
+        if let Ok(block) = arena.bump_alloc(layout)
+            && kani::any()
+        {
+            arena.dealloc(block.as_non_null_ptr(), layout);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn balanced_routes_to_least_loaded_arena() {
+    use wait_free_arena::Balanced;
+
+    let balanced: Balanced<StackAllocator<8>, 2> =
+        Balanced::new([StackAllocator::new(), StackAllocator::new()]);
+    let layout = Layout::new::<[u8; 4]>();
+    let first = balanced.bump_alloc(layout).unwrap().as_non_null_ptr();
+    let second = balanced.bump_alloc(layout).unwrap().as_non_null_ptr();
+    let [left, right] = balanced.arenas();
+    assert!(left.contains(first) && right.contains(second));
+    assert_eq!(balanced.remaining_hint(), Some(8));
+
+    assert!(balanced.bump_alloc(Layout::new::<[u8; 6]>()).is_err());
+    balanced.bump_alloc(layout).unwrap();
+    balanced.bump_alloc(layout).unwrap();
+    assert_eq!(balanced.remaining_hint(), Some(0));
+    assert!(!balanced.try_dealloc(second, layout));
+}
@@ -0,0 +1,28 @@
+use core::alloc::Layout;
+
+use wait_free_arena::ArenaAllocatorImpl;
+
+#[test]
+fn bitmap_reuses_freed_blocks() {
+    use wait_free_arena::BitmapAllocator;
+
+    let mut backing = [0u8; 256];
+    let arena = BitmapAllocator::from_slice(&mut backing, 16);
+    let blocks = arena.blocks();
+    assert!(blocks >= 14);
+
+    let layout = Layout::new::<[u8; 20]>();
+    let first = arena.bump_alloc(layout).unwrap().as_non_null_ptr();
+    let second = arena.bump_alloc(layout).unwrap().as_non_null_ptr();
+    assert_eq!(second.addr().get() - first.addr().get(), 32);
+    assert_eq!(first.addr().get() % 16, 0);
+
+    assert!(arena.try_dealloc(first, layout));
+    assert_eq!(arena.bump_alloc(layout).unwrap().as_non_null_ptr(), first);
+
+    let aligned = arena
+        .bump_alloc(Layout::from_size_align(16, 64).unwrap())
+        .unwrap();
+    assert_eq!(aligned.as_mut_ptr().addr() % 64, 0);
+    assert_eq!(arena.remaining_hint(), Some((blocks - 5) * 16));
+}
@@ -0,0 +1,38 @@
+use core::alloc::Layout;
+
+use wait_free_arena::ArenaAllocatorImpl;
+
+#[test]
+fn buddy_places_blocks_at_multiples_of_their_size() {
+    use wait_free_arena::BuddyAllocator;
+
+    let mut backing = [0u8; 512];
+    let arena = BuddyAllocator::from_slice(&mut backing, 16);
+    let small = arena.bump_alloc(Layout::new::<[u8; 10]>()).unwrap();
+    let large = arena.bump_alloc(Layout::new::<[u8; 40]>()).unwrap();
+    let offset = large.as_mut_ptr().addr() - small.as_mut_ptr().addr();
+    assert_eq!(offset, 64);
+    let mid = arena.bump_alloc(Layout::new::<[u8; 17]>()).unwrap();
+    assert_eq!(mid.as_mut_ptr().addr() - small.as_mut_ptr().addr(), 32);
+
+    let free = arena.remaining_hint().unwrap();
+    assert!(arena.try_dealloc(large.as_non_null_ptr(), Layout::new::<[u8; 40]>()));
+    assert_eq!(arena.remaining_hint(), Some(free + 64));
+}
+
+#[test]
+fn buddy_aligns_blocks_in_a_misaligned_pool() {
+    use wait_free_arena::BuddyAllocator;
+
+    #[repr(align(64))]
+    struct Backing([u8; 528]);
+
+    let mut backing = Backing([0; 528]);
+    // the blocks of a pool starting 16 bytes past a 64 byte boundary are never 64 byte aligned at
+    // multiples of 64 from its start
+    let arena = BuddyAllocator::from_slice(&mut backing.0[16..], 16);
+    let layout = Layout::from_size_align(64, 64).unwrap();
+    let block = arena.bump_alloc(layout).unwrap();
+    assert_eq!(block.as_mut_ptr().addr() % 64, 0);
+    assert!(arena.try_dealloc(block.as_non_null_ptr(), layout));
+}
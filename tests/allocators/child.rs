@@ -0,0 +1,38 @@
+use wait_free_arena::{ArenaAllocatorImpl, HeapAllocator};
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn child_arena_returns_region_on_drop() {
+    let parent = HeapAllocator::new(64);
+    {
+        let child = parent.child(32).unwrap();
+        assert_eq!(parent.current_offset(), 32);
+        let value = child.alloc_val([7u8; 16]).unwrap();
+        assert!(parent.contains(core::ptr::NonNull::from(&value[0])));
+        assert!(child.alloc_val([0u8; 17]).is_err());
+    }
+    assert_eq!(parent.current_offset(), 0);
+}
+
+#[test]
+fn child_arenas_donate_capacity_to_siblings() {
+    let parent = HeapAllocator::new(64);
+    let other_parent = HeapAllocator::new(16);
+    let cold = parent.child(32).unwrap();
+    let mut hot = parent.child(16).unwrap();
+    let stranger = other_parent.child(16).unwrap();
+
+    hot.alloc_val([1u8; 16]).unwrap();
+    assert!(hot.alloc_val([2u8; 8]).is_err());
+    assert!(cold.donate_to(&stranger, 8).is_err());
+    cold.donate_to(&hot, 8).unwrap();
+    assert_eq!(cold.remaining_hint(), Some(24));
+    assert_eq!(hot.remaining_hint(), Some(8));
+
+    let donated = hot.alloc_val([2u8; 8]).unwrap();
+    assert!(cold.contains(core::ptr::NonNull::from(&donated[0])));
+    assert!(hot.alloc_val(3u8).is_err());
+
+    hot.reset().unwrap();
+    assert_eq!(hot.remaining_hint(), Some(24));
+}
@@ -0,0 +1,14 @@
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn emergency_reserve_survives_exhaustion() {
+    let arena: StackAllocator<32> = StackAllocator::new();
+    let reserve = arena.reserve_emergency(8).unwrap();
+    assert_eq!(reserve.capacity(), 8);
+    arena.alloc_val([0u8; 24]).unwrap();
+    assert!(arena.alloc_val(0u8).is_err());
+
+    let msg = reserve.alloc_val(*b"oom!").unwrap();
+    assert!(reserve.contains(core::ptr::NonNull::from(&msg[0])));
+    assert_eq!(reserve.remaining_hint(), Some(4));
+}
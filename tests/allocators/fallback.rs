@@ -0,0 +1,47 @@
+use alloc::vec::Vec;
+use core::{alloc::Layout, ptr::NonNull};
+
+use wait_free_arena::{ArenaAllocatorImpl, HeapAllocator};
+
+#[test]
+fn fallback_serves_overflow_from_the_secondary_arena() {
+    use wait_free_arena::{FallbackAllocator, GlobalHeap, StackAllocator};
+
+    let arena = FallbackAllocator::new(StackAllocator::<8>::new(), HeapAllocator::new(64));
+    let small = arena.alloc_val(1u64).unwrap() as *mut u64;
+    assert!(
+        arena
+            .primary()
+            .contains(NonNull::new(small).unwrap().cast())
+    );
+    let spilled = arena.alloc_val(2u64).unwrap() as *mut u64;
+    assert!(
+        arena
+            .secondary()
+            .contains(NonNull::new(spilled).unwrap().cast())
+    );
+    assert!(arena.try_dealloc(NonNull::new(spilled).unwrap().cast(), Layout::new::<u64>()));
+    assert_eq!(arena.secondary().current_offset(), 0);
+    assert!(arena.alloc_val([0u8; 128]).is_err());
+
+    let mut global = FallbackAllocator::new(StackAllocator::<4>::new(), GlobalHeap::new());
+    let block = global.bump_alloc(Layout::new::<[u32; 4]>()).unwrap();
+    assert!(global.secondary().contains(block.as_non_null_ptr()));
+    let foreign = 0u64;
+    assert!(!global.try_dealloc(NonNull::from(&foreign).cast(), Layout::new::<u64>()));
+    assert!(global.try_dealloc(block.as_non_null_ptr(), Layout::new::<[u32; 4]>()));
+    assert!(!global.secondary().contains(block.as_non_null_ptr()));
+
+    let layout = Layout::from_size_align(24, 32).unwrap();
+    let blocks: Vec<_> = (0..3)
+        .map(|_| global.bump_alloc(layout).unwrap().as_non_null_ptr())
+        .collect();
+    assert!(blocks.iter().all(|block| block.addr().get() % 32 == 0));
+    assert!(global.try_dealloc(blocks[1], layout));
+    assert!(!global.try_dealloc(blocks[1], layout));
+    assert!(!global.secondary().contains(blocks[1]));
+    assert!(global.secondary().contains(blocks[0]));
+    assert!(global.secondary().contains(blocks[2]));
+    global.reset().unwrap();
+    assert!(!global.secondary().contains(blocks[0]));
+}
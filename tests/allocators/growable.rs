@@ -0,0 +1,42 @@
+use core::{alloc::Layout, ptr::NonNull};
+
+use wait_free_arena::{ArenaAllocatorImpl, HeapAllocator};
+
+#[test]
+fn growable_arena_adds_chunks_without_moving_blocks() {
+    use wait_free_arena::GrowableHeapAllocator;
+
+    let mut arena = GrowableHeapAllocator::new(16);
+    let first = arena.alloc_val([7u8; 16]).unwrap() as *mut [u8; 16];
+    assert_eq!(arena.chunks(), 1);
+
+    let second = arena.alloc_val(1u64).unwrap() as *mut u64;
+    assert_eq!(arena.chunks(), 2);
+    assert_eq!(arena.capacity(), 16 + 32);
+    let large = arena.bump_alloc(Layout::new::<[u64; 16]>()).unwrap();
+    assert!(large.as_mut_ptr().addr().is_multiple_of(align_of::<u64>()));
+    assert_eq!(arena.chunks(), 3);
+    assert_eq!(unsafe { (*first, *second) }, ([7; 16], 1));
+    assert!(arena.contains(NonNull::new(first.cast()).unwrap()));
+
+    arena.reset().unwrap();
+    assert_eq!(arena.chunks(), 1);
+    assert!(arena.alloc_val([0u64; 16]).is_ok());
+}
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn builder_configures_growable_chunks() {
+    use wait_free_arena::Geometric;
+
+    let arena = HeapAllocator::builder()
+        .capacity(16)
+        .min_align(8)
+        .growth(Geometric(4))
+        .build();
+    arena.alloc_val([0u8; 16]).unwrap();
+    let first = arena.bump_alloc(Layout::new::<u8>()).unwrap();
+    let second = arena.bump_alloc(Layout::new::<u8>()).unwrap();
+    assert_eq!((arena.chunks(), arena.capacity()), (2, 16 + 64));
+    assert_eq!(second.as_mut_ptr().addr() - first.as_mut_ptr().addr(), 8);
+}
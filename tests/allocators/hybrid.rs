@@ -0,0 +1,24 @@
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn hybrid_routes_by_size() {
+    use core::alloc::GlobalAlloc;
+    use core::ptr::NonNull;
+    use wait_free_arena::HybridAllocator;
+
+    static HYBRID: HybridAllocator<StackAllocator<16>> =
+        HybridAllocator::new(StackAllocator::new(), 8);
+
+    let small = Layout::new::<[u8; 8]>();
+    let large = Layout::new::<[u8; 32]>();
+    unsafe {
+        let in_arena = HYBRID.alloc(small);
+        assert!(HYBRID.arena().contains(NonNull::new(in_arena).unwrap()));
+        let in_system = HYBRID.alloc(large);
+        assert!(!HYBRID.arena().contains(NonNull::new(in_system).unwrap()));
+        HYBRID.dealloc(in_system, large);
+        HYBRID.dealloc(in_arena, small);
+    }
+}
@@ -0,0 +1,15 @@
+mod balanced;
+mod bitmap;
+mod buddy;
+#[cfg(feature = "alloc")]
+mod child;
+mod emergency;
+#[cfg(feature = "alloc")]
+mod fallback;
+#[cfg(feature = "alloc")]
+mod growable;
+#[cfg(feature = "std")]
+mod hybrid;
+mod scratch;
+mod sharded;
+mod tlsf;
@@ -0,0 +1,51 @@
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn scratch_space_is_returned_on_drop() {
+    let arena: StackAllocator<8> = StackAllocator::new();
+    for round in 0..4 {
+        let mut scratch = arena.alloc_scratch(8).unwrap();
+        assert!(scratch.iter().all(|b| *b == 0));
+        scratch.fill(round);
+    }
+
+    let kept = arena.alloc_val(1u8).unwrap();
+    let scratch = arena.alloc_scratch(4).unwrap();
+    let outlived = arena.alloc_val(2u8).unwrap();
+    drop(scratch);
+    assert!(arena.alloc_scratch(4).is_err());
+    assert_eq!((*kept, *outlived), (1, 2));
+}
+
+#[test]
+fn temp_stack_levels_free_nested_allocations() {
+    use wait_free_arena::TempStack;
+
+    let arena: StackAllocator<128> = StackAllocator::new();
+    let mut temp = TempStack::new_in(64, &arena).unwrap();
+    let outer = temp.alloc_val(1u64).unwrap() as *const u64;
+
+    temp.push().unwrap();
+    temp.alloc_val(2u64).unwrap();
+    temp.push().unwrap();
+    assert_eq!(temp.depth(), 2);
+    let inner = temp.copy_bytes(&[3; 16]).unwrap().as_ptr();
+    assert!(temp.pop());
+    temp.push().unwrap();
+    assert_eq!(temp.copy_bytes(&[4; 16]).unwrap().as_ptr(), inner);
+    assert!(temp.pop());
+    assert!(temp.pop());
+    assert!(!temp.pop());
+
+    assert_eq!(temp.alloc_val(5u64).unwrap() as *const u64, unsafe {
+        outer.add(1)
+    });
+    arena.alloc_val(6u64).unwrap();
+    assert_eq!(unsafe { *outer }, 1);
+
+    let foreign = 0u64;
+    let layout = Layout::new::<u64>();
+    assert!(!temp.try_dealloc(core::ptr::NonNull::from(&foreign).cast(), layout));
+}
@@ -0,0 +1,29 @@
+use wait_free_arena::ArenaAllocatorImpl;
+
+#[test]
+fn sharded_arena_overflows_into_neighbouring_shards() {
+    use wait_free_arena::ShardedArena;
+
+    // aligned like the buffers the arenas own, so the shard bounds do not depend on the stack
+    #[repr(align(16))]
+    struct Backing([u8; 34]);
+
+    let mut backing = Backing([0; 34]);
+    let arena: ShardedArena<'_, 4> = ShardedArena::from_slice(&mut backing.0);
+    assert_eq!(arena.shard(0).remaining_hint(), Some(8));
+    assert_eq!(arena.shard(3).remaining_hint(), Some(10));
+
+    // the shard a thread starts at depends on its id, but every shard is used before any fails
+    let mut owners: [_; 4] = core::array::from_fn(|_| {
+        let block = arena.alloc_val([1u8; 8]).unwrap() as *mut u8;
+        let block = core::ptr::NonNull::new(block).unwrap();
+        (0..4)
+            .find(|idx| arena.shard(*idx).contains(block))
+            .unwrap()
+    });
+    owners.sort();
+    assert_eq!(owners, [0, 1, 2, 3]);
+    assert!(arena.alloc_val([0u8; 4]).is_err());
+    assert_eq!(arena.remaining_hint(), Some(2));
+    assert!(arena.alloc_val(0u16).is_ok());
+}
@@ -0,0 +1,48 @@
+use core::alloc::Layout;
+
+use wait_free_arena::ArenaAllocatorImpl;
+
+#[test]
+fn tlsf_merges_freed_blocks() {
+    use wait_free_arena::TlsfAllocator;
+
+    let mut backing = [0u8; 1024];
+    let arena = TlsfAllocator::from_slice(&mut backing);
+    let initial = arena.remaining_hint().unwrap();
+
+    let layouts = [
+        Layout::new::<[u8; 24]>(),
+        Layout::from_size_align(40, 64).unwrap(),
+        Layout::new::<[u64; 9]>(),
+    ];
+    let blocks = layouts.map(|layout| arena.bump_alloc(layout).unwrap());
+    assert_eq!(blocks[1].as_mut_ptr().addr() % 64, 0);
+    assert!(arena.bump_alloc(Layout::new::<[u8; 1024]>()).is_err());
+
+    for (block, layout) in blocks.iter().zip(layouts) {
+        unsafe { block.as_mut_ptr().write_bytes(0xaa, layout.size()) };
+    }
+    for idx in [1, 0, 2] {
+        assert!(arena.try_dealloc(blocks[idx].as_non_null_ptr(), layouts[idx]));
+    }
+    assert_eq!(arena.remaining_hint(), Some(initial));
+    assert!(arena.bump_alloc(Layout::new::<[u8; 900]>()).is_ok());
+}
+
+#[test]
+fn tlsf_rejects_foreign_and_freed_blocks() {
+    use wait_free_arena::TlsfAllocator;
+
+    let mut backing = [0u8; 512];
+    let arena = TlsfAllocator::from_slice(&mut backing);
+    let layout = Layout::new::<[u64; 4]>();
+    let block = arena.bump_alloc(layout).unwrap().as_non_null_ptr();
+    let initial = arena.remaining_hint();
+
+    assert!(!arena.try_dealloc(block, Layout::new::<[u64; 32]>()));
+    assert!(!arena.try_dealloc(unsafe { block.add(8) }, Layout::new::<u64>()));
+    assert_eq!(arena.remaining_hint(), initial);
+
+    assert!(arena.try_dealloc(block, layout));
+    assert!(!arena.try_dealloc(block, layout));
+}
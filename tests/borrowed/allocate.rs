@@ -0,0 +1,46 @@
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, BorrowedAllocator};
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn alloc_from_slice() {
+    let mut backing = [0xffu8; 4];
+    let arena = BorrowedAllocator::from_slice(&mut backing);
+    let zeroed = arena.bump_alloc_zeroed(Layout::new::<[u8; 2]>()).unwrap();
+    assert_eq!(unsafe { zeroed.as_ref() }, &[0, 0]);
+    assert!(arena.bump_alloc(Layout::new::<[u8; 4]>()).is_err());
+    assert_eq!(backing, [0, 0, 0xff, 0xff]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn reuse_vec_per_frame() {
+    use alloc::vec::Vec;
+
+    let mut scratch: Vec<u8> = Vec::with_capacity(8);
+    let capacity = scratch.capacity();
+    for frame in 0..3u8 {
+        let arena = BorrowedAllocator::borrow_from(&mut scratch);
+        let value = arena.alloc_val(frame).unwrap();
+        assert_eq!(*value, frame);
+        assert!(
+            arena
+                .bump_alloc(Layout::array::<u8>(capacity).unwrap())
+                .is_err()
+        );
+    }
+    assert_eq!(scratch.capacity(), capacity);
+}
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn from_raw_parts_keeps_provenance_of_buffer() {
+    let mut storage = [0u8; 16];
+    let base = core::ptr::NonNull::from(&mut storage).cast::<u8>();
+    let arena = unsafe { BorrowedAllocator::from_raw_parts(base, 16) };
+    let value = arena.alloc_val(7u8).unwrap();
+    assert_eq!(value as *mut u8, base.as_ptr());
+    assert!(arena.contains(base));
+    assert!(!arena.contains(unsafe { base.add(16) }));
+}
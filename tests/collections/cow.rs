@@ -0,0 +1,35 @@
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn cow_copies_on_first_mutation() {
+    use wait_free_arena::ArenaCow;
+
+    let arena: StackAllocator<12> = StackAllocator::new();
+    let input = "hello";
+    let mut unchanged = ArenaCow::from(input);
+    let mut changed = ArenaCow::from(input);
+    changed.to_mut(&arena).unwrap().make_ascii_uppercase();
+
+    assert!(unchanged.is_borrowed());
+    assert!(changed.is_owned());
+    assert_eq!(&*changed, "HELLO");
+    assert_eq!(input, "hello");
+    assert_eq!(unchanged.to_mut(&arena).unwrap(), "hello");
+    assert!(ArenaCow::from(input).to_mut(&arena).is_err());
+}
+
+#[test]
+fn cow_str_copies_into_arena_on_mutation() {
+    use wait_free_arena::ArenaCowStr;
+
+    let arena: StackAllocator<64> = StackAllocator::new();
+    let input = "{{ name }}";
+    let mut token = ArenaCowStr::borrowed(&input[3..7], &arena);
+    assert!(token.is_borrowed());
+    assert_eq!(token.as_ptr(), input[3..].as_ptr());
+
+    token.push_str("!").unwrap();
+    assert!(!token.is_borrowed());
+    assert!(arena.contains(core::ptr::NonNull::from(token.as_bytes()).cast()));
+    assert_eq!(&*token, "name!");
+}
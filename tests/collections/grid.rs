@@ -0,0 +1,15 @@
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn alloc_2d_grid_indexing() {
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let mut grid = arena.alloc_2d(2, 3, 0u8).unwrap();
+    grid[(1, 2)] = 5;
+    grid.row_mut(0).copy_from_slice(&[1, 2, 3]);
+
+    assert_eq!((grid.rows(), grid.cols()), (2, 3));
+    assert_eq!(grid.get(1, 3), None);
+    assert!(grid.column(2).copied().eq([3, 5]));
+    assert_eq!(grid.into_slice(), &[1, 2, 3, 0, 0, 5]);
+    assert!(arena.alloc_2d(usize::MAX, 2, 0u8).is_err());
+}
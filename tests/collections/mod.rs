@@ -0,0 +1,8 @@
+mod cow;
+mod grid;
+mod mpsc;
+mod once;
+mod slot;
+mod spsc;
+mod string;
+mod vec;
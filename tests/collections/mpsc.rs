@@ -0,0 +1,24 @@
+use wait_free_arena::StackAllocator;
+
+#[test]
+fn mpsc_queue_recycles_nodes() {
+    use wait_free_arena::MpscQueue;
+
+    let arena: StackAllocator<256> = StackAllocator::new();
+    let queue = MpscQueue::new_in(&arena).unwrap();
+    let mut consumer = queue.consumer().unwrap();
+    assert!(queue.consumer().is_none());
+    assert_eq!(consumer.pop(), None);
+
+    queue.push([1u8; 4]).unwrap();
+    queue.push([2u8; 4]).unwrap();
+    assert_eq!(consumer.pop(), Some([1; 4]));
+    let used = arena.current_offset();
+    for round in 3..10u8 {
+        queue.push([round; 4]).unwrap();
+        assert_eq!(consumer.pop(), Some([round - 1; 4]));
+    }
+    assert_eq!(arena.current_offset(), used);
+    assert_eq!(consumer.pop(), Some([9; 4]));
+    assert_eq!(consumer.pop(), None);
+}
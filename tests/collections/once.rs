@@ -0,0 +1,54 @@
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn cyclic_nodes_resolve_their_parent() {
+    use wait_free_arena::ArenaWeakSlot;
+
+    struct Node<'a> {
+        value: u8,
+        parent: Option<ArenaWeakSlot<'a, Node<'a>>>,
+        child: Option<&'a Node<'a>>,
+    }
+
+    let arena: StackAllocator<128> = StackAllocator::new();
+    let root = arena
+        .alloc_cyclic(|root| {
+            assert!(root.get().is_none());
+            let child = arena
+                .alloc_val(Node {
+                    value: 2,
+                    parent: Some(*root),
+                    child: None,
+                })
+                .unwrap();
+            Node {
+                value: 1,
+                parent: None,
+                child: Some(child),
+            }
+        })
+        .unwrap();
+
+    let child = root.child.unwrap();
+    assert_eq!(child.value, 2);
+    assert_eq!(child.parent.unwrap().get().unwrap().value, 1);
+    assert!(root.parent.is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn once_initialized_from_many_threads() {
+    use wait_free_arena::{ArenaOnce, HeapAllocator};
+
+    let arena = HeapAllocator::new(64);
+    let cell = ArenaOnce::<u64>::new_in(&arena).unwrap();
+    std::thread::scope(|s| {
+        for i in 0..4 {
+            s.spawn(move || cell.get_or_init(|| i));
+        }
+    });
+    let value = *cell.get().unwrap();
+    assert!(value < 4);
+    assert_eq!(cell.set(9), Err(9));
+    assert_eq!(*cell.get_or_init(|| 9), value);
+}
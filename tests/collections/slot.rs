@@ -0,0 +1,23 @@
+use wait_free_arena::StackAllocator;
+
+#[test]
+fn slot_keys_outlive_removals() {
+    use wait_free_arena::SlotArena;
+
+    let arena: StackAllocator<128> = StackAllocator::new();
+    let mut slots = SlotArena::with_capacity_in(2, &arena).unwrap();
+    let a = slots.insert([1u8; 3]).unwrap();
+    let b = slots.insert([2u8; 3]).unwrap();
+    assert!(slots.insert([3u8; 3]).is_err());
+
+    assert_eq!(slots.remove(a), Some([1; 3]));
+    assert_eq!(slots.remove(a), None);
+    let c = slots.insert([3u8; 3]).unwrap();
+    assert_eq!(c.index(), a.index());
+    assert_ne!(c, a);
+    assert_eq!(slots.get(a), None);
+    assert_eq!(slots.get(b), Some(&[2; 3]));
+    slots.get_mut(c).unwrap()[0] = 4;
+    assert_eq!(slots.get(c), Some(&[4, 3, 3]));
+    assert_eq!(slots.len(), 2);
+}
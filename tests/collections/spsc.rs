@@ -0,0 +1,20 @@
+use wait_free_arena::StackAllocator;
+
+#[test]
+fn spsc_ring_wraps_around() {
+    use wait_free_arena::SpscRing;
+
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let mut ring = SpscRing::with_capacity_in(3, &arena).unwrap();
+    let (mut tx, mut rx) = ring.split();
+    assert_eq!(tx.push_slice(b"abcd"), 3);
+    assert_eq!(tx.push(b'd'), Err(b'd'));
+    assert_eq!(rx.pop(), Some(b'a'));
+    assert_eq!(tx.push_slice(b"de"), 1);
+
+    let mut out = [0; 4];
+    assert_eq!(rx.pop_slice(&mut out), 3);
+    assert_eq!(&out[..3], b"bcd");
+    assert_eq!(rx.pop(), None);
+    assert!(ring.is_empty());
+}
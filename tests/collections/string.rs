@@ -0,0 +1,38 @@
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn strings_built_while_scanning() {
+    use wait_free_arena::StrBuilder;
+
+    let arena: StackAllocator<32> = StackAllocator::new();
+    let unescaped = arena.alloc_str_from_iter(['a', '\t', 'b']).unwrap();
+
+    let mut ident = StrBuilder::new_in(&arena);
+    for c in "foo bar".chars().take_while(|c| c.is_alphanumeric()) {
+        ident.push(c).unwrap();
+    }
+    let ident = ident.into_str();
+
+    assert_eq!(unescaped, "a\tb");
+    assert_eq!(ident, "foo");
+}
+
+#[test]
+fn smol_str_keeps_short_strings_inline() {
+    use wait_free_arena::ArenaSmolStr;
+
+    let arena: StackAllocator<64> = StackAllocator::new();
+    let short = ArenaSmolStr::new_in("ident", &arena).unwrap();
+    assert!(short.is_inline());
+    assert_eq!(arena.current_offset(), 0);
+
+    let long = ArenaSmolStr::new_in("a_rather_long_identifier_name", &arena).unwrap();
+    assert!(!long.is_inline());
+    assert_eq!(arena.current_offset(), long.len());
+
+    let copy = long;
+    assert_eq!(copy, long);
+    assert_eq!(short, "ident");
+    assert!(long < short);
+    assert_eq!(size_of::<ArenaSmolStr>(), 24);
+}
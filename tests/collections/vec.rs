@@ -0,0 +1,57 @@
+use wait_free_arena::StackAllocator;
+
+#[test]
+fn arena_vec_collects_from_iterators() {
+    use wait_free_arena::ArenaVec;
+
+    let arena: StackAllocator<32> = StackAllocator::new();
+    let mut vec = ArenaVec::from_iter_in((1..=4u8).map(|x| x * x), &arena).unwrap();
+    assert_eq!(vec.capacity(), 4);
+    vec.extend([25u8, 36]);
+    vec.extend(&[49u8]);
+    assert_eq!(&*vec, &[1, 4, 9, 16, 25, 36, 49]);
+
+    let small: StackAllocator<2> = StackAllocator::new();
+    assert!(ArenaVec::from_iter_in(0..3u8, &small).is_err());
+}
+
+#[test]
+fn collections_report_exhaustion() {
+    use wait_free_arena::{ArenaString, ArenaVec};
+
+    let arena: StackAllocator<8> = StackAllocator::new();
+    let mut vec = ArenaVec::new_in(&arena);
+    vec.try_extend(*b"abcd").unwrap();
+    assert!(vec.try_extend(*b"efgh").is_err());
+    assert_eq!(&*vec, b"abcd");
+
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let mut string = ArenaString::new_in(&arena);
+    string.try_extend("héllo".chars()).unwrap();
+    assert!(string.try_extend("world".chars()).is_err());
+    assert!(string.as_str().starts_with("héllo"));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn collections_behave_like_slices_and_strs() {
+    use std::hash::{BuildHasher, RandomState};
+    use wait_free_arena::{ArenaString, ArenaVec, HeapAllocator};
+
+    let arena = HeapAllocator::new(128);
+    let other: StackAllocator<32> = StackAllocator::new();
+    let mut vec = ArenaVec::from_iter_in([3u8, 1, 2], &arena).unwrap();
+    let same = ArenaVec::from_iter_in([3u8, 1, 2], &other).unwrap();
+    assert_eq!(vec, same);
+    assert_eq!(vec, [3, 1, 2]);
+    assert_eq!(vec[1..], [1, 2]);
+    vec[0] = 0;
+    assert!(vec < same);
+
+    let mut name = ArenaString::new_in(&arena);
+    name.push_str("arena").unwrap();
+    assert_eq!(name, "arena");
+    assert_eq!(&name[..3], "are");
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(&name), state.hash_one("arena"));
+}
@@ -0,0 +1,14 @@
+/// Collects formatted output in a fixed buffer, for tests without `alloc`.
+pub struct Writer<'a>(pub &'a mut [u8], pub usize);
+
+impl core::fmt::Write for Writer<'_> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let end = self.1 + s.len();
+        self.0
+            .get_mut(self.1..end)
+            .ok_or(core::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.1 = end;
+        Ok(())
+    }
+}
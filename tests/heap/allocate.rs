@@ -0,0 +1,201 @@
+use alloc::vec::Vec;
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, HeapAllocator};
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn fresh_buffer_is_zeroed() {
+    let arena = HeapAllocator::new(64);
+    let block = arena.bump_alloc(Layout::new::<[u8; 64]>()).unwrap();
+    assert!(unsafe { block.as_ref() }.iter().all(|byte| *byte == 0));
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn vec_in_arena() {
+    let arena = HeapAllocator::new(16);
+    let mut v: Vec<u8, _> = Vec::with_capacity_in(16, &arena);
+    v.extend_from_slice(&[1, 2, 3]);
+    assert_eq!(v, [1, 2, 3]);
+}
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn adopt_and_return_vec() {
+    let mut backing = Vec::with_capacity(32);
+    backing.push(7u8);
+    let arena = HeapAllocator::from_vec(backing);
+    let block = arena.bump_alloc(Layout::new::<u8>()).unwrap();
+    assert_eq!(unsafe { *block.as_mut_ptr() }, 7);
+    unsafe { block.as_mut_ptr().write(9) };
+
+    let backing = arena.into_vec();
+    assert!(backing.len() >= 32);
+    assert_eq!(backing[0], 9);
+    assert!(backing[1..].iter().all(|byte| *byte == 0));
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn nested_in_stack_arena() {
+    use wait_free_arena::StackAllocator;
+
+    let parent: StackAllocator<32> = StackAllocator::new();
+    let child = HeapAllocator::new_in(16, &parent);
+    assert!(child.bump_alloc(Layout::new::<[u8; 16]>()).is_ok());
+    assert!(child.bump_alloc(Layout::new::<u8>()).is_err());
+    assert!(HeapAllocator::try_new_in(32, &parent).is_err());
+}
+
+#[cfg(feature = "allocator_api")]
+#[test]
+fn failed_reserve_reports_layout() {
+    use alloc::collections::TryReserveErrorKind;
+
+    let arena = HeapAllocator::new(8);
+    let mut v: Vec<u64, _> = Vec::new_in(&arena);
+    let err = v.try_reserve_exact(4).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        TryReserveErrorKind::AllocError {
+            layout: Layout::array::<u64>(4).unwrap(),
+            non_exhaustive: (),
+        }
+    );
+}
+
+#[test]
+fn generic_over_owned_and_borrowed() {
+    use alloc::boxed::Box;
+
+    fn alloc_two<A: ArenaAllocatorImpl>(arena: A) -> A {
+        arena.alloc_val(1u8).unwrap();
+        arena.alloc_val(2u8).unwrap();
+        arena
+    }
+
+    let arena = HeapAllocator::new(4);
+    alloc_two(&arena);
+    let mut boxed = alloc_two(Box::new(arena));
+    assert!(boxed.alloc_val(0u8).is_err());
+    boxed.reset().unwrap();
+    assert!((&*boxed).reset().is_err());
+}
+
+#[test]
+fn builder_min_align() {
+    let arena = HeapAllocator::builder().capacity(64).min_align(16).build();
+    let first = arena.bump_alloc(Layout::new::<u8>()).unwrap();
+    let second = arena.bump_alloc(Layout::new::<u8>()).unwrap();
+    assert_eq!(first.as_mut_ptr().addr() % 16, 0);
+    assert_eq!(second.as_mut_ptr().addr() % 16, 0);
+    assert_eq!(arena.into_vec().len(), 64);
+
+    assert!(HeapAllocator::builder().min_align(3).try_build().is_err());
+}
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn packing_serves_small_requests_from_alignment_gaps() {
+    let arena = HeapAllocator::builder().capacity(256).packing(true).build();
+    let first = arena.bump_alloc(Layout::new::<u8>()).unwrap().as_mut_ptr();
+    let aligned = arena
+        .bump_alloc(Layout::from_size_align(64, 64).unwrap())
+        .unwrap()
+        .as_mut_ptr();
+    assert_eq!(aligned.addr() % 64, 0);
+
+    let used = arena.current_offset();
+    let short = arena
+        .bump_alloc(Layout::new::<[u8; 3]>())
+        .unwrap()
+        .as_mut_ptr();
+    let word = arena.bump_alloc(Layout::new::<u16>()).unwrap().as_mut_ptr();
+    assert_eq!(short, first.wrapping_add(1));
+    assert_eq!(word.addr() % 2, 0);
+    assert!(word < aligned);
+    assert_eq!(arena.current_offset(), used);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn panicking_initializers_leave_nothing_behind() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use wait_free_arena::ArenaOnce;
+
+    let arena = HeapAllocator::new(64);
+    arena.alloc_val(1u8).unwrap();
+    let used = arena.current_offset();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        arena.alloc_with::<[u8; 8]>(|| panic!("constructor failed"))
+    }));
+    assert!(res.is_err());
+    assert_eq!(arena.current_offset(), used);
+
+    let once = ArenaOnce::new();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        once.get_or_init(|| panic!("init failed"))
+    }));
+    assert!(res.is_err());
+    assert_eq!(once.get(), None);
+    assert_eq!(*once.get_or_init(|| 3u8), 3);
+}
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn raw_parts_roundtrip_keeps_cursor() {
+    let arena = HeapAllocator::new(16);
+    arena.alloc_val([3u8; 5]).unwrap();
+    let (base, capacity, align, cursor) = arena.into_raw_parts();
+    assert_eq!((capacity, align, cursor), (16, 16, 5));
+
+    let arena = unsafe { HeapAllocator::from_raw_parts(base, capacity, align, cursor) };
+    let next = arena.alloc_val(9u8).unwrap() as *mut u8;
+    assert_eq!(next, unsafe { base.as_ptr().add(5) });
+    assert_eq!(arena.into_box()[..5], [3; 5]);
+}
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn dump_bytes_renders_hex_rows() {
+    use alloc::string::String;
+
+    let arena = HeapAllocator::new(64);
+    arena.alloc_val(*b"Hello, arena!\0\x01\x02xyz").unwrap();
+    let mut out = String::new();
+    unsafe { arena.dump_bytes(.., &mut out) }.unwrap();
+    let mut lines = out.lines().skip(1);
+    let first = lines.next().unwrap();
+    assert!(first.starts_with("0x0000 "));
+    assert!(
+        first.contains(" 48 65 6c 6c 6f 2c 20 61  72 65 6e 61 21 00 01 02  |Hello, arena!...|")
+    );
+    let last = lines.next().unwrap();
+    assert!(last.starts_with("0x0010 "));
+    assert!(last.ends_with("|xyz| <- cursor"));
+    assert!(lines.next().is_none());
+
+    let mut io = Vec::new();
+    unsafe { arena.dump_bytes_io(16..17, &mut io) }.unwrap();
+    assert!(
+        String::from_utf8(io)
+            .unwrap()
+            .lines()
+            .nth(1)
+            .unwrap()
+            .contains(" 78 ")
+    );
+}
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn touch_all_keeps_contents() {
+    let mut arena = HeapAllocator::new(3 * wait_free_arena::PREFAULT_STRIDE + 5);
+    arena.alloc_val([7u8; 9]).unwrap();
+    arena.touch_all();
+    assert_eq!(arena.current_offset(), 9);
+    let mut out = alloc::string::String::new();
+    unsafe { arena.dump_bytes(.., &mut out) }.unwrap();
+    assert!(out.contains(" 07 07 07 07 07 07 07 07  07 "));
+}
@@ -0,0 +1,26 @@
+#[cfg(feature = "bytes")]
+#[test]
+fn frozen_regions_become_bytes_without_copying() {
+    use alloc::sync::Arc;
+
+    use bytes::Bytes;
+    use wait_free_arena::{HeapAllocator, SharedRegion, StackAllocator, boxed::Box};
+
+    static ARENA: StackAllocator<8> = StackAllocator::new();
+    let payload: Box<[u8]> = Box::new_in(*b"ping", &ARENA).unwrap().into();
+    let ptr = payload.as_ptr();
+    let bytes = Bytes::from(payload);
+    assert_eq!((bytes.as_ptr(), &bytes[..]), (ptr, &b"ping"[..]));
+
+    let arena = Arc::new(HeapAllocator::new(16));
+    let payload: Box<[u8]> = Box::new_in(*b"pong", &*arena).unwrap().into();
+    let ptr = payload.as_ptr();
+    let bytes = Bytes::from(SharedRegion::new(&arena, payload));
+    drop(arena);
+    let clone = bytes.slice(1..);
+    drop(bytes);
+    assert_eq!(
+        (clone.as_ptr(), &clone[..]),
+        (ptr.wrapping_add(1), &b"ong"[..])
+    );
+}
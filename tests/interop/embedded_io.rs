@@ -0,0 +1,21 @@
+use wait_free_arena::StackAllocator;
+
+#[test]
+fn embedded_io_roundtrip_through_arena_buffer() {
+    use embedded_io::{Error, ErrorKind, Read, Write};
+    use wait_free_arena::ArenaVec;
+
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let mut buf = ArenaVec::new_in(&arena);
+    write!(buf, "{}-two", 4).unwrap();
+    let mut frozen: &[u8] = buf.leak();
+
+    let mut out = [0; 5];
+    frozen.read_exact(&mut out).unwrap();
+    assert_eq!(&out, b"4-two");
+
+    let small: StackAllocator<2> = StackAllocator::new();
+    let mut full = ArenaVec::new_in(&small);
+    let err = full.write_all(b"too long").unwrap_err();
+    assert_eq!(Error::kind(&err), ErrorKind::OutOfMemory);
+}
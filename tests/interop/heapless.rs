@@ -0,0 +1,28 @@
+use wait_free_arena::StackAllocator;
+
+#[test]
+fn heapless_collections_roundtrip() {
+    use wait_free_arena::{ArenaString, ArenaVec};
+
+    let arena: StackAllocator<64> = StackAllocator::new();
+    let mut small: heapless::Vec<[u8; 2], 4> = heapless::Vec::new();
+    small.extend([[1, 2], [3, 4], [5, 6]]);
+    let mut vec = ArenaVec::from_heapless_in(small, &arena).unwrap();
+    assert_eq!(&*vec, &[[1, 2], [3, 4], [5, 6]]);
+    vec.push([7, 8]).unwrap();
+    vec.push([9, 10]).unwrap();
+
+    let vec = heapless::Vec::<_, 4>::try_from(vec).unwrap_err();
+    let back: heapless::Vec<_, 5> = vec.try_into().unwrap();
+    assert_eq!(back.first(), Some(&[1, 2]));
+    assert_eq!(back.last(), Some(&[9, 10]));
+
+    let name: heapless::String<8> = "arena".try_into().unwrap();
+    let s = ArenaString::from_heapless_in(&name, &arena).unwrap();
+    assert!(
+        heapless::String::<4>::try_from(ArenaString::from_str_in("toolong", &arena).unwrap())
+            .is_err()
+    );
+    let back: heapless::String<5> = s.try_into().unwrap();
+    assert_eq!(back, name);
+}
@@ -0,0 +1,39 @@
+use core::ptr::NonNull;
+
+use wait_free_arena::{ArenaAllocatorImpl, HeapAllocator};
+
+#[test]
+fn io_roundtrip_through_arena_buffer() {
+    use std::io::{Read, Write};
+    use wait_free_arena::ArenaVec;
+
+    let arena = HeapAllocator::new(64);
+    let mut buf = ArenaVec::new_in(&arena);
+    write!(buf, "{}-two", 4).unwrap();
+    let mut frozen: &[u8] = buf.leak();
+
+    let mut out = [0; 5];
+    frozen.read_exact(&mut out).unwrap();
+    assert_eq!(&out, b"4-two");
+
+    let small = HeapAllocator::new(2);
+    let mut full = ArenaVec::new_in(&small);
+    let err = full.write_all(b"too long").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::OutOfMemory);
+}
+
+#[test]
+fn paths_are_copied_into_arena() {
+    use std::{ffi::OsStr, path::Path};
+
+    let arena = HeapAllocator::new(64);
+    let dir = Path::new("/var/log");
+    let file = arena.alloc_path(&dir.join("syslog")).unwrap();
+    assert_eq!(file, Path::new("/var/log/syslog"));
+    assert!(arena.contains(NonNull::from(file).cast()));
+    assert_eq!(file.parent(), Some(dir));
+
+    let name = arena.alloc_os_str(OsStr::new("syslog")).unwrap();
+    assert_eq!(file.file_name(), Some(name));
+    assert_eq!(arena.current_offset(), 21);
+}
@@ -0,0 +1,8 @@
+#[cfg(feature = "embedded-io")]
+mod embedded_io;
+#[cfg(feature = "heapless")]
+mod heapless;
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "postcard")]
+mod postcard;
@@ -0,0 +1,18 @@
+use wait_free_arena::StackAllocator;
+
+#[test]
+fn postcard_serializes_into_arena() {
+    use wait_free_arena::serialize_into_arena;
+
+    let arena: StackAllocator<64> = StackAllocator::new();
+    let bytes = serialize_into_arena(&(7u8, "arena", [1u16, 300]), &arena).unwrap();
+    assert_eq!(bytes, [7, 5, b'a', b'r', b'e', b'n', b'a', 1, 172, 2]);
+    let decoded: (u8, &str, [u16; 2]) = postcard::from_bytes(bytes).unwrap();
+    assert_eq!(decoded, (7, "arena", [1, 300]));
+
+    let small: StackAllocator<4> = StackAllocator::new();
+    assert_eq!(
+        serialize_into_arena("too long", &small),
+        Err(postcard::Error::SerializeBufferFull)
+    );
+}
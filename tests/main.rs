@@ -1,5 +1,5 @@
 #![no_std]
-#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api, try_reserve_kind))]
 #![feature(slice_ptr_get)]
 
 #[cfg(feature = "std")]
@@ -8,9 +8,25 @@ extern crate std;
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod allocators;
+mod borrowed;
+mod collections;
+mod common;
 mod dummy;
 #[cfg(feature = "alloc")]
 mod heap;
+mod interop;
+#[cfg(feature = "std")]
+mod pressure;
+#[cfg(feature = "std")]
+mod profile;
+#[cfg(feature = "sanitize")]
+mod sanitize;
 mod stack;
+#[cfg(all(feature = "std", feature = "stats"))]
+mod stats;
+#[cfg(feature = "stress")]
+mod stress;
+mod wrappers;
 
 fn main() {}
@@ -0,0 +1,79 @@
+use core::{
+    alloc::Layout,
+    future::Future,
+    pin::pin,
+    ptr::NonNull,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll, Waker},
+};
+use std::{sync::Arc, task::Wake};
+
+use wait_free_arena::{ArenaAllocatorImpl, HeapAllocator, PressureWatcher, StackAllocator};
+
+#[derive(Default)]
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn waiters_resume_once_space_is_freed() {
+    let arena = PressureWatcher::new(HeapAllocator::new(16));
+    let block = arena.alloc_val([0u8; 16]).unwrap();
+
+    let counter = Arc::new(CountingWaker::default());
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+    let mut wait = pin!(arena.wait_for_capacity(8));
+    assert_eq!(wait.as_mut().poll(&mut cx), Poll::Pending);
+    assert_eq!(wait.as_mut().poll(&mut cx), Poll::Pending);
+
+    arena.dealloc(NonNull::from(block).cast(), Layout::new::<[u8; 16]>());
+    // Miri does not keep waker vtables unique, so the second poll may register a duplicate
+    assert_ne!(counter.0.load(Ordering::Relaxed), 0);
+    assert_eq!(wait.as_mut().poll(&mut cx), Poll::Ready(()));
+}
+
+#[test]
+fn async_allocation_completes_after_reset() {
+    let mut arena = PressureWatcher::new(HeapAllocator::new(16));
+    arena.alloc_val([0u8; 12]).unwrap();
+
+    let counter = Arc::new(CountingWaker::default());
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+    {
+        let mut alloc = pin!(arena.bump_alloc_async(Layout::new::<[u8; 8]>()));
+        assert!(alloc.as_mut().poll(&mut cx).is_pending());
+    }
+    arena.reset().unwrap();
+    assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+
+    let mut alloc = pin!(arena.bump_alloc_async(Layout::new::<[u8; 8]>()));
+    assert!(matches!(alloc.as_mut().poll(&mut cx), Poll::Ready(Ok(_))));
+    let mut oversized = pin!(arena.bump_alloc_async(Layout::new::<[u8; 32]>()));
+    assert!(oversized.as_mut().poll(&mut cx).is_pending());
+}
+
+#[test]
+fn waiters_resume_after_a_rewind_through_another_handle() {
+    let inner = StackAllocator::<16>::new();
+    let checkpoint = inner.checkpoint();
+    let arena = PressureWatcher::new(&inner);
+    arena.alloc_val([0u8; 16]).unwrap();
+
+    let counter = Arc::new(CountingWaker::default());
+    let waker = Waker::from(counter.clone());
+    let mut cx = Context::from_waker(&waker);
+    let mut alloc = pin!(arena.bump_alloc_async(Layout::new::<[u8; 8]>()));
+    assert!(alloc.as_mut().poll(&mut cx).is_pending());
+
+    unsafe { inner.rewind(checkpoint) }.unwrap();
+    assert_eq!(counter.0.load(Ordering::Relaxed), 0);
+    arena.notify_reset();
+    assert_eq!(counter.0.load(Ordering::Relaxed), 1);
+    assert!(matches!(alloc.as_mut().poll(&mut cx), Poll::Ready(Ok(_))));
+}
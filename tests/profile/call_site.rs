@@ -0,0 +1,123 @@
+use core::alloc::Layout;
+use std::vec::Vec;
+
+use wait_free_arena::{ArenaAllocatorImpl, CallSiteProfiler, HeapAllocator};
+
+#[test]
+fn attributes_bytes_to_callers() {
+    let arena = CallSiteProfiler::new(HeapAllocator::new(64));
+    let small_line = line!() + 2;
+    for _ in 0..3 {
+        arena.alloc_val(0u32).unwrap();
+    }
+    let large_line = line!() + 1;
+    arena.bump_alloc(Layout::new::<[u8; 16]>()).unwrap();
+
+    let sites = arena.sites();
+    assert_eq!(sites.len(), 2);
+    assert_eq!(sites[0].1.bytes, 16);
+    assert_eq!(sites[1].1.allocations, 3);
+    assert_eq!(sites[1].1.bytes, 12);
+    assert!(
+        sites
+            .iter()
+            .all(|(location, _)| location.file().ends_with("call_site.rs"))
+    );
+
+    let mut profile = Vec::new();
+    arena.write_pprof(&mut profile).unwrap();
+    let strings: Vec<_> = messages(&profile, 6)
+        .map(|string| core::str::from_utf8(string).unwrap())
+        .collect();
+    assert_eq!(strings[0], "");
+    let sample_types: Vec<_> = messages(&profile, 1)
+        .map(|ty| (strings[varint_field(ty, 1)], strings[varint_field(ty, 2)]))
+        .collect();
+    assert_eq!(
+        sample_types,
+        [("alloc_objects", "count"), ("alloc_space", "bytes")]
+    );
+
+    let mut samples: Vec<_> = messages(&profile, 2)
+        .map(|sample| {
+            let location = by_id(&profile, 4, varint_field(sample, 1));
+            let line = messages(location, 4).next().unwrap();
+            let function = by_id(&profile, 5, varint_field(line, 1));
+            assert!(strings[varint_field(function, 4)].ends_with("call_site.rs"));
+            let values: Vec<_> = varints(sample, 2).collect();
+            (varint_field(line, 2) as u32, values)
+        })
+        .collect();
+    samples.sort();
+    assert_eq!(
+        samples,
+        [
+            (small_line, std::vec![3, 12]),
+            (large_line, std::vec![1, 16])
+        ]
+    );
+}
+
+/// A field of a protobuf message, only the wire types pprof uses are supported.
+enum Field<'a> {
+    Varint(u64),
+    Bytes(&'a [u8]),
+}
+
+fn read_varint(buf: &mut &[u8]) -> u64 {
+    let mut value = 0;
+    for shift in (0..64).step_by(7) {
+        let byte = buf[0];
+        *buf = &buf[1..];
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            break;
+        }
+    }
+    value
+}
+
+fn fields(mut buf: &[u8]) -> impl Iterator<Item = (u64, Field<'_>)> {
+    core::iter::from_fn(move || {
+        if buf.is_empty() {
+            return None;
+        }
+        let key = read_varint(&mut buf);
+        let field = match key & 7 {
+            0 => Field::Varint(read_varint(&mut buf)),
+            2 => {
+                let len = read_varint(&mut buf) as usize;
+                let (bytes, rest) = buf.split_at(len);
+                buf = rest;
+                Field::Bytes(bytes)
+            }
+            wire => panic!("unexpected wire type {wire}"),
+        };
+        Some((key >> 3, field))
+    })
+}
+
+fn varints(msg: &[u8], number: u64) -> impl Iterator<Item = u64> + '_ {
+    fields(msg).filter_map(move |(field, value)| match value {
+        Field::Varint(value) if field == number => Some(value),
+        _ => None,
+    })
+}
+
+fn messages(msg: &[u8], number: u64) -> impl Iterator<Item = &[u8]> {
+    fields(msg).filter_map(move |(field, value)| match value {
+        Field::Bytes(bytes) if field == number => Some(bytes),
+        _ => None,
+    })
+}
+
+fn varint_field(msg: &[u8], number: u64) -> usize {
+    varints(msg, number).next().unwrap() as usize
+}
+
+/// The message of field `number` of the profile whose `id` field is `id`.
+fn by_id(profile: &[u8], number: u64, id: usize) -> &[u8] {
+    messages(profile, number)
+        .find(|msg| varint_field(msg, 1) == id)
+        .unwrap()
+}
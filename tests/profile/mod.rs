@@ -0,0 +1 @@
+mod call_site;
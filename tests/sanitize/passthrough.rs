@@ -0,0 +1,30 @@
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, HeapAllocator};
+
+#[test]
+fn sanitize_frees_blocks_individually() {
+    let arena = HeapAllocator::new(16);
+    let layout = Layout::new::<[u8; 8]>();
+    let first = arena.bump_alloc(layout).unwrap().as_non_null_ptr();
+    let second = arena.bump_alloc(layout).unwrap().as_non_null_ptr();
+    assert!(arena.contains(first) && arena.contains(second));
+    assert!(arena.bump_alloc(Layout::new::<u8>()).is_err());
+
+    arena.dealloc(first, layout);
+    assert!(!arena.contains(first));
+    assert_eq!(arena.current_offset(), 16);
+    assert!(arena.try_dealloc(second, layout));
+    assert_eq!(arena.current_offset(), 8);
+}
+
+#[test]
+#[should_panic(expected = "double free")]
+fn sanitize_reports_double_free() {
+    let arena = HeapAllocator::new(16);
+    let layout = Layout::new::<[u8; 8]>();
+    let first = arena.bump_alloc(layout).unwrap().as_non_null_ptr();
+    arena.bump_alloc(layout).unwrap();
+    arena.dealloc(first, layout);
+    arena.dealloc(first, layout);
+}
@@ -2,12 +2,355 @@ use core::alloc::Layout;
 
 use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
 
+use crate::common::Writer;
+
 #[test]
 fn alloc_basic() {
-    let arena: StackAllocator<10> = StackAllocator::new();
-    let one = arena.bump_alloc(Layout::new::<u16>()).unwrap();
+    let arena: StackAllocator<24> = StackAllocator::new();
+    let one = arena.bump_alloc(Layout::new::<u8>()).unwrap();
     unsafe { one.as_mut_ptr().write(42) };
     let two = arena.bump_alloc(Layout::new::<u64>()).unwrap();
-    unsafe { two.as_mut_ptr().write(42) };
-    assert!(arena.bump_alloc(Layout::new::<u8>()).is_err())
+    assert!(two.as_mut_ptr().addr().is_multiple_of(align_of::<u64>()));
+    unsafe { two.as_mut_ptr().cast::<u64>().write(42) };
+    // the padding in front of the `u64` counts against the capacity
+    assert!(arena.bump_alloc(Layout::new::<[u8; 9]>()).is_err())
+}
+
+#[test]
+fn dump_layout() {
+    let arena: StackAllocator<64> = StackAllocator::new();
+    arena.bump_alloc(Layout::new::<[u8; 16]>()).unwrap();
+    let mut out = [0u8; 256];
+    let mut writer = Writer(&mut out, 0);
+    arena.dump(&mut writer).unwrap();
+    let text = core::str::from_utf8(&writer.0[..writer.1]).unwrap();
+    let mut lines = text.lines();
+    assert_eq!(lines.next(), Some("arena: 16/64 bytes used, 48 free"));
+    let bar = lines.last().unwrap();
+    assert_eq!(bar.matches('#').count(), 16);
+    assert_eq!(bar.matches('.').count(), 48);
+}
+
+#[test]
+fn insufficient_capacity_reports_sizes() {
+    use wait_free_arena::AllocErrorKind;
+
+    let arena: StackAllocator<8> = StackAllocator::new();
+    arena.bump_alloc(Layout::new::<[u8; 6]>()).unwrap();
+    let err = arena.bump_alloc(Layout::new::<u32>()).unwrap_err();
+    assert_eq!(
+        err.kind(),
+        AllocErrorKind::InsufficientCapacity {
+            requested: 4,
+            available: 2
+        }
+    );
+    assert!(err.kind().is_out_of_memory());
+    assert_eq!(err.code(), 1);
+}
+
+#[test]
+fn type_erased_handle() {
+    use wait_free_arena::AnyArena;
+
+    fn plugin(arena: AnyArena<'_>) -> u32 {
+        *arena.alloc_val(40u32).unwrap() + 2
+    }
+
+    let arena: StackAllocator<4> = StackAllocator::new();
+    assert_eq!(plugin(AnyArena::new(&arena)), 42);
+    assert!(AnyArena::from(&arena).alloc_val(0u8).is_err());
+}
+
+#[test]
+fn alloc_many_disjoint() {
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let [a, b, c] = arena
+        .bump_alloc_many([
+            Layout::new::<u8>(),
+            Layout::new::<[u8; 3]>(),
+            Layout::new::<[u8; 4]>(),
+        ])
+        .unwrap();
+    assert_eq!((a.len(), b.len(), c.len()), (1, 3, 4));
+    assert_eq!(unsafe { b.as_mut_ptr().offset_from(a.as_mut_ptr()) }, 1);
+    assert_eq!(unsafe { c.as_mut_ptr().offset_from(b.as_mut_ptr()) }, 3);
+    assert!(arena.bump_alloc_many([Layout::new::<[u8; 9]>()]).is_err());
+}
+
+wait_free_arena::static_arena_fits!(StackAllocator<32>, u8, u64, [u8; 16]);
+
+#[test]
+fn copy_bytes_and_values() {
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let token = {
+        let input = *b"id=7";
+        arena.copy_bytes(&input[..2]).unwrap()
+    };
+    let value = arena.copy_value(&[3u8; 4]).unwrap();
+    assert_eq!(token, b"id");
+    assert_eq!(value, &[3; 4]);
+    assert!(arena.copy_bytes(&[0; 11]).is_err());
+}
+
+#[test]
+fn cursor_can_be_saved_and_restored() {
+    let arena: StackAllocator<8> = StackAllocator::new();
+    arena.alloc_val([1u8; 2]).unwrap();
+    let saved = arena.current_offset();
+    assert_eq!(saved, 2);
+
+    arena.alloc_val([2u8; 4]).unwrap();
+    assert_eq!(arena.current_offset(), 6);
+    unsafe { arena.set_offset(saved) };
+    assert_eq!(arena.current_offset(), saved);
+    assert!(arena.alloc_val([0u8; 6]).is_ok());
+}
+
+#[test]
+fn alloc_default_value() {
+    #[derive(Default)]
+    struct Config {
+        retries: u8,
+        verbose: bool,
+    }
+
+    let arena: StackAllocator<4> = StackAllocator::new();
+    let config: &mut Config = arena.alloc_default().unwrap();
+    assert_eq!((config.retries, config.verbose), (0, false));
+    config.retries = 3;
+    assert_eq!(*arena.alloc_default::<[u8; 2]>().unwrap(), [0; 2]);
+}
+
+#[test]
+fn alloc_clone_of_promotes_borrowed_value() {
+    let arena: StackAllocator<8> = StackAllocator::new();
+    let promoted = {
+        let local = [1u8, 2, 3];
+        arena.alloc_clone_of(&local).unwrap()
+    };
+    promoted[0] = 4;
+    assert_eq!(promoted, &[4, 2, 3]);
+}
+
+#[test]
+fn alloc_slice_fill_copy_repeats_value() {
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let bytes = arena.alloc_slice_fill_copy(5, 0xabu8).unwrap();
+    let flags = arena.alloc_slice_fill_copy(3, true).unwrap();
+    let pairs = arena.alloc_slice_fill_copy(2, [7u8, 9]).unwrap();
+    assert_eq!(bytes, &[0xab; 5]);
+    assert_eq!(flags, &[true; 3]);
+    assert_eq!(pairs, &[[7, 9]; 2]);
+    // may not be read as a byte
+    let uninit = core::mem::MaybeUninit::<u8>::uninit();
+    assert_eq!(arena.alloc_slice_fill_copy(4, uninit).unwrap().len(), 4);
+    assert!(arena.alloc_slice_fill_copy(5, 0u8).is_err());
+}
+
+#[test]
+fn display_rendered_into_arena() {
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let label = arena.to_arena_str(&format_args!("node #{}", 42)).unwrap();
+    assert_eq!(label, "node #42");
+    assert_eq!(arena.current_offset(), 8);
+    assert!(arena.to_arena_str(&"does not fit").is_err());
+}
+
+#[test]
+fn try_dealloc_reports_reclaimed_memory() {
+    let arena: StackAllocator<8> = StackAllocator::new();
+    let layout = Layout::new::<[u8; 2]>();
+    let first = arena.bump_alloc(layout).unwrap().as_non_null_ptr();
+    let second = arena.bump_alloc(layout).unwrap().as_non_null_ptr();
+
+    assert!(!arena.try_dealloc(first, layout));
+    assert!(arena.try_dealloc(second, layout));
+    assert!(!arena.try_dealloc(second, layout));
+    assert!(arena.try_dealloc(first, layout));
+    assert_eq!(arena.current_offset(), 0);
+}
+
+#[test]
+fn dealloc_last_rejects_inner_blocks() {
+    use wait_free_arena::NotTail;
+
+    let arena: StackAllocator<8> = StackAllocator::new();
+    let outer = arena.alloc_val(1u8).unwrap() as *mut u8;
+    let inner = arena.alloc_val(2u8).unwrap() as *mut u8;
+    let layout = Layout::new::<u8>();
+
+    let outer = core::ptr::NonNull::new(outer).unwrap();
+    let inner = core::ptr::NonNull::new(inner).unwrap();
+    assert_eq!(arena.dealloc_last(outer, layout), Err(NotTail));
+    assert_eq!(arena.dealloc_last(inner, layout), Ok(()));
+    assert_eq!(arena.dealloc_last(outer, layout), Ok(()));
+}
+
+#[test]
+fn stack_allocator_is_isr_safe() {
+    use wait_free_arena::IsrSafeAlloc;
+
+    fn alloc_in_handler(arena: &impl IsrSafeAlloc) -> bool {
+        arena.bump_alloc(Layout::new::<[u8; 4]>()).is_ok()
+    }
+
+    static ARENA: StackAllocator<4> = StackAllocator::new();
+    assert!(alloc_in_handler(&ARENA));
+    assert!(!alloc_in_handler(&&ARENA));
+}
+
+#[test]
+fn aligned_region_keeps_alignment() {
+    let arena: StackAllocator<256> = StackAllocator::new();
+    arena.alloc_val(1u8).unwrap();
+    let region = arena.aligned_region(96, 32).unwrap();
+    assert_eq!(region.capacity(), 96);
+    for len in [3usize, 17, 32] {
+        let block = region
+            .bump_alloc(Layout::from_size_align(len, 1).unwrap())
+            .unwrap();
+        assert!(block.as_mut_ptr().addr().is_multiple_of(32));
+    }
+    assert!(region.bump_alloc(Layout::new::<u8>()).is_err());
+    assert!(arena.aligned_region(8, 3).is_err());
+}
+
+#[test]
+fn alignments_above_max_align_are_rejected() {
+    use wait_free_arena::{AllocErrorKind, MAX_ALIGN};
+
+    assert!(MAX_ALIGN.is_power_of_two());
+    let arena: StackAllocator<64> = StackAllocator::new();
+    assert!(
+        arena
+            .bump_alloc(Layout::from_size_align(1, MAX_ALIGN.min(32)).unwrap())
+            .is_ok()
+    );
+    if let Ok(layout) = Layout::from_size_align(1, MAX_ALIGN * 2) {
+        let err = arena.bump_alloc(layout).unwrap_err();
+        assert_eq!(
+            err.kind(),
+            AllocErrorKind::AlignmentUnsupported {
+                align: MAX_ALIGN * 2
+            }
+        );
+    }
+}
+
+#[test]
+fn alloc_layout_returns_aligned_blocks() {
+    let arena: StackAllocator<64> = StackAllocator::new();
+    arena.alloc_layout(Layout::new::<u8>()).unwrap();
+    for align in [2usize, 8, 16] {
+        let layout = Layout::from_size_align(3, align).unwrap();
+        let ptr = arena.alloc_layout(layout).unwrap();
+        assert!(ptr.addr().get().is_multiple_of(align));
+        assert!(arena.contains(ptr));
+    }
+}
+
+#[test]
+fn failed_requests_keep_the_cursor_in_bounds() {
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let block = arena.bump_alloc(Layout::new::<[u8; 12]>()).unwrap();
+    assert!(arena.bump_alloc(Layout::new::<[u8; 5]>()).is_err());
+    assert_eq!(arena.current_offset(), 12);
+    assert_eq!(arena.remaining_hint(), Some(4));
+    assert!(arena.try_dealloc(block.as_non_null_ptr(), Layout::new::<[u8; 12]>()));
+    assert!(arena.bump_alloc(Layout::new::<[u8; 16]>()).is_ok());
+}
+
+#[cfg(feature = "boxed")]
+#[test]
+fn zero_sized_allocations_fit_into_a_full_arena() {
+    use wait_free_arena::boxed::Box;
+
+    let arena: StackAllocator<8> = StackAllocator::new();
+    arena.bump_alloc(Layout::new::<[u8; 8]>()).unwrap();
+    let unit = arena.bump_alloc(Layout::new::<()>()).unwrap();
+    assert_eq!(unit.len(), 0);
+    let empty = arena.bump_alloc(Layout::new::<[u64; 0]>()).unwrap();
+    assert!(empty.as_mut_ptr().addr().is_multiple_of(align_of::<u64>()));
+    assert_eq!(arena.current_offset(), 8);
+
+    let boxed = Box::new_in((), &arena).unwrap();
+    assert_eq!(*boxed, ());
+    assert!(arena.alloc_val([0u32; 0]).is_ok());
+    assert_eq!(arena.current_offset(), 8);
+}
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn reset_reuses_the_buffer() {
+    let mut arena: StackAllocator<16> = StackAllocator::new();
+    let first = arena.alloc_val([1u8; 16]).unwrap() as *mut [u8; 16];
+    assert!(arena.alloc_val(0u8).is_err());
+
+    arena.reset().unwrap();
+    assert_eq!(arena.current_offset(), 0);
+    let again = arena.alloc_val([2u8; 16]).unwrap() as *mut [u8; 16];
+    assert_eq!(first, again);
+}
+
+#[test]
+fn rewinding_to_a_checkpoint_discards_later_allocations() {
+    use wait_free_arena::StaleCheckpoint;
+
+    let mut arena: StackAllocator<16> = StackAllocator::new();
+    arena.alloc_val(1u32).unwrap();
+    let outer = arena.checkpoint();
+    arena.alloc_val(2u32).unwrap();
+    let inner = arena.checkpoint();
+    arena.alloc_val(3u32).unwrap();
+    assert_eq!(arena.current_offset(), 12);
+
+    unsafe { arena.rewind(outer) }.unwrap();
+    assert_eq!(arena.current_offset(), outer.offset());
+    assert_eq!(arena.rewind_mut(inner), Err(StaleCheckpoint));
+    assert_eq!(arena.current_offset(), 4);
+
+    arena.alloc_val([0u8; 12]).unwrap();
+    arena.rewind_mut(outer).unwrap();
+    arena.reset().unwrap();
+    assert_eq!(arena.rewind_mut(outer), Err(StaleCheckpoint));
+}
+
+#[test]
+fn scopes_rewind_the_arena_when_dropped() {
+    use wait_free_arena::ArenaVec;
+
+    let mut arena: StackAllocator<32> = StackAllocator::new();
+    arena.alloc_val(1u32).unwrap();
+    {
+        let mut frame = arena.scope();
+        assert_eq!(frame.checkpoint().offset(), 4);
+        frame.alloc_val(2u32).unwrap();
+        {
+            let inner = frame.scope();
+            let mut vec = ArenaVec::new_in(&inner);
+            vec.extend_from_slice(&[3u32; 4]).unwrap();
+            assert_eq!(vec.len(), 4);
+        }
+        assert_eq!(frame.remaining_hint(), Some(24));
+
+        frame.alloc_val(5u64).unwrap();
+        frame.reset().unwrap();
+        assert_eq!(frame.remaining_hint(), Some(28));
+    }
+    assert_eq!(arena.current_offset(), 4);
+}
+
+#[test]
+fn usage_is_reported_in_bytes() {
+    let arena: StackAllocator<16> = StackAllocator::new();
+    assert_eq!(
+        (arena.used(), arena.remaining(), arena.capacity()),
+        (0, 16, 16)
+    );
+    arena.alloc_val(1u8).unwrap();
+    arena.alloc_val(2u32).unwrap();
+    assert_eq!((arena.used(), arena.remaining()), (8, 8));
+    assert!(arena.alloc_val([0u8; 9]).is_err());
+    assert_eq!((arena.used(), arena.remaining()), (8, 8));
 }
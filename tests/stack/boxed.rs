@@ -0,0 +1,145 @@
+use wait_free_arena::{ArenaString, ArenaVec, StackAllocator, boxed::Box};
+
+#[test]
+fn new_in_unwrap() {
+    let arena: StackAllocator<4> = StackAllocator::new();
+    let value = Box::new_in_unwrap(7u32, &arena);
+    assert_eq!(*value, 7);
+}
+
+#[test]
+#[should_panic(expected = "arena allocation")]
+fn new_in_unwrap_oom() {
+    let arena: StackAllocator<2> = StackAllocator::new();
+    Box::new_in_unwrap(7u32, &arena);
+}
+
+#[test]
+fn uninit_slice() {
+    let arena: StackAllocator<8> = StackAllocator::new();
+    let mut slice = Box::<[u16]>::new_uninit_slice_in(4, &arena).unwrap();
+    for (i, elem) in slice.iter_mut().enumerate() {
+        elem.write(i as u16 * 2);
+    }
+    let slice = unsafe { Box::assume_init(slice) };
+    assert_eq!(*slice, [0, 2, 4, 6]);
+    assert!(Box::<[u8]>::new_uninit_slice_in(1, &arena).is_err());
+}
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn frozen_collections_return_spare_capacity() {
+    let arena: StackAllocator<64> = StackAllocator::new();
+    let mut vec = ArenaVec::with_capacity_in(16, &arena).unwrap();
+    vec.extend_from_slice(b"abc").unwrap();
+    let frozen = vec.into_boxed_slice();
+    assert_eq!(&*frozen, b"abc");
+
+    let mut string = ArenaString::with_capacity_in(16, &arena).unwrap();
+    string.push_str("de").unwrap();
+    let s = string.into_str();
+    assert_eq!(s, "de");
+    assert_eq!(s.as_ptr(), unsafe { frozen.as_ptr().add(3) });
+}
+
+#[test]
+fn boxed_slice_into_iter_moves_elements() {
+    use core::cell::Cell;
+
+    struct Counted<'c>(&'c Cell<usize>);
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Cell::new(0);
+    let arena: StackAllocator<64> = StackAllocator::new();
+    let slice: Box<[Counted]> = Box::new_in([(); 4].map(|_| Counted(&drops)), &arena)
+        .unwrap()
+        .into();
+    let mut iter = slice.into_iter();
+    assert_eq!(iter.len(), 4);
+    drop(iter.next());
+    drop(iter.next_back());
+    assert_eq!(drops.get(), 2);
+    drop(iter);
+    assert_eq!(drops.get(), 4);
+}
+
+#[test]
+fn boxed_slice_into_iter_in_reclaims_the_tail() {
+    use wait_free_arena::ArenaAllocatorImpl;
+
+    let arena: StackAllocator<16> = StackAllocator::new();
+    arena.alloc_val(1u16).unwrap();
+    let used = arena.current_offset();
+    let slice: Box<[u16]> = Box::new_in([1, 2, 3], &arena).unwrap().into();
+    let mut iter = Box::into_iter_in(slice, &arena);
+    assert_eq!(iter.next(), Some(1));
+    drop(iter);
+    assert_eq!(arena.current_offset(), used);
+    assert!(arena.alloc_val([0u8; 14]).is_ok());
+}
+
+#[test]
+fn new_with_ptr_in_exposes_final_address() {
+    struct Intrusive {
+        this: *const Intrusive,
+    }
+
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let node = Box::new_with_ptr_in(|this| Intrusive { this }, &arena).unwrap();
+    assert_eq!(node.this, &*node as *const Intrusive);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn new_with_ptr_in_hands_back_the_slot_on_panic() {
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        Box::<u64>::new_with_ptr_in(|_| panic!("constructor failed"), &arena)
+    }));
+    assert!(res.is_err());
+    assert_eq!(arena.current_offset(), 0);
+}
+
+#[cfg(feature = "embedded-dma")]
+#[test]
+fn boxed_buffers_are_dma_targets() {
+    use embedded_dma::{ReadBuffer, WriteBuffer};
+
+    let arena: StackAllocator<64> = StackAllocator::new();
+    let mut buf: Box<[u16]> = Box::new_in([0u16; 8], &arena).unwrap().into();
+    let (ptr, len) = unsafe { buf.write_buffer() };
+    assert_eq!((ptr.cast_const(), len), (buf.as_ptr(), 8));
+    let moved = buf;
+    let (read_ptr, read_len) = unsafe { moved.read_buffer() };
+    assert_eq!((read_ptr, read_len), (ptr.cast_const(), 8));
+}
+
+#[test]
+fn into_parts_roundtrip() {
+    use core::alloc::Layout;
+
+    let arena: StackAllocator<16> = StackAllocator::new();
+    let slice: Box<[u8]> = Box::new_in([1u8, 2, 3], &arena).unwrap().into();
+    let (ptr, layout) = Box::into_parts(slice);
+    assert_eq!(layout, Layout::new::<[u8; 3]>());
+
+    let slice = unsafe { Box::from_parts(ptr, layout) };
+    assert_eq!(&*slice, &[1, 2, 3]);
+}
+
+#[test]
+fn non_null_roundtrip() {
+    let arena: StackAllocator<4> = StackAllocator::new();
+    let mut value = Box::new_in(7u32, &arena).unwrap();
+    unsafe { Box::as_non_null(&mut value).write(8) };
+    assert_eq!(*value, 8);
+    let ptr = Box::into_non_null(value);
+    let value = unsafe { Box::from_non_null(ptr) };
+    assert_eq!(Box::into_inner(value), 8);
+}
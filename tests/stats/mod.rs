@@ -0,0 +1,4 @@
+mod padding;
+mod prometheus;
+mod snapshot;
+mod tags;
@@ -0,0 +1,18 @@
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, HeapAllocator};
+
+#[test]
+fn padding_is_accounted_separately() {
+    let arena = HeapAllocator::builder().capacity(64).min_align(8).build();
+    arena.bump_alloc(Layout::new::<[u8; 3]>()).unwrap();
+    arena.bump_alloc(Layout::new::<u64>()).unwrap();
+    arena
+        .alloc_tagged(Layout::new::<[u8; 10]>(), "name")
+        .unwrap();
+
+    let stats = arena.stats();
+    assert_eq!(stats.padding_bytes, 5 + 6);
+    assert_eq!(stats.used, 32);
+    assert_eq!(arena.tags().collect::<std::vec::Vec<_>>(), [("name", 10)]);
+}
@@ -0,0 +1,20 @@
+use core::alloc::Layout;
+use std::vec::Vec;
+
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator, write_prometheus};
+
+#[test]
+fn renders_labelled_samples() {
+    let arena: StackAllocator<8> = StackAllocator::new();
+    arena.bump_alloc(Layout::new::<u32>()).unwrap();
+    assert!(arena.bump_alloc(Layout::new::<u64>()).is_err());
+
+    let mut out = Vec::new();
+    write_prometheus(&mut out, &[("frame \"a\"", arena.stats())]).unwrap();
+    let text = std::string::String::from_utf8(out).unwrap();
+    assert!(text.contains("# TYPE wait_free_arena_used_bytes gauge\n"));
+    assert!(text.contains("wait_free_arena_used_bytes{arena=\"frame \\\"a\\\"\"} 4\n"));
+    assert!(text.contains("wait_free_arena_capacity_bytes{arena=\"frame \\\"a\\\"\"} 8\n"));
+    assert!(text.contains("wait_free_arena_oom_total{arena=\"frame \\\"a\\\"\"} 1\n"));
+    assert!(text.contains("wait_free_arena_allocations_total{arena=\"frame \\\"a\\\"\"} 1\n"));
+}
@@ -0,0 +1,50 @@
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn reset_stats_starts_new_interval() {
+    let arena: StackAllocator<16> = StackAllocator::new();
+    arena
+        .alloc_tagged(Layout::new::<[u8; 8]>(), "frame")
+        .unwrap();
+    assert!(arena.bump_alloc(Layout::new::<[u8; 9]>()).is_err());
+    let before = arena.stats_snapshot();
+    assert_eq!((before.high_water, before.oom_count), (8, 1));
+
+    arena.reset_stats();
+    arena
+        .alloc_tagged(Layout::new::<[u8; 2]>(), "frame")
+        .unwrap();
+    let after = arena.stats_snapshot();
+    assert_eq!((after.used, after.high_water, after.oom_count), (10, 10, 0));
+    assert_eq!(arena.tags().next(), Some(("frame", 2)));
+}
+
+#[test]
+fn allocation_count_and_high_water_survive_frees() {
+    let arena: StackAllocator<16> = StackAllocator::new();
+    arena.bump_alloc(Layout::new::<()>()).unwrap();
+    let block = arena.bump_alloc(Layout::new::<[u8; 12]>()).unwrap();
+    assert!(arena.bump_alloc(Layout::new::<[u8; 8]>()).is_err());
+    arena.dealloc(block.as_non_null_ptr(), Layout::new::<[u8; 12]>());
+    assert_eq!(arena.used(), 0);
+    assert_eq!((arena.allocation_count(), arena.high_water()), (2, 12));
+
+    arena.reset_stats();
+    assert_eq!((arena.allocation_count(), arena.high_water()), (0, 0));
+}
+
+#[test]
+fn uncommitted_allocations_are_recorded_on_commit() {
+    let arena: StackAllocator<16> = StackAllocator::new();
+    arena.with_category("parse", || {
+        arena.alloc_uncommitted::<[u8; 12]>().unwrap().abort();
+        assert_eq!((arena.allocation_count(), arena.high_water()), (0, 0));
+        let token = arena.alloc_uncommitted::<[u8; 4]>().unwrap();
+        assert_eq!(arena.allocation_count(), 0);
+        token.commit_with([1; 4]);
+    });
+    assert_eq!((arena.allocation_count(), arena.high_water()), (1, 4));
+    assert_eq!(arena.tags().next(), Some(("parse", 4)));
+}
@@ -0,0 +1,35 @@
+use core::alloc::Layout;
+use std::vec::Vec;
+
+use wait_free_arena::{ArenaAllocatorImpl, HeapAllocator};
+
+#[test]
+fn per_tag_accounting() {
+    let arena = HeapAllocator::new(64);
+    arena
+        .alloc_tagged(Layout::new::<[u8; 8]>(), "physics")
+        .unwrap();
+    arena.alloc_val_tagged(0u32, "audio").unwrap();
+    arena
+        .alloc_tagged(Layout::new::<[u8; 2]>(), "physics")
+        .unwrap();
+    arena.alloc_val(0u8).unwrap();
+
+    let tags: Vec<_> = arena.tags().collect();
+    assert_eq!(tags, [("physics", 10), ("audio", 4)]);
+}
+
+#[test]
+fn nested_categories() {
+    let arena = HeapAllocator::new(64);
+    arena.with_category("render", || {
+        arena.alloc_val(0u32).unwrap();
+        arena.with_category("text", || arena.alloc_val(0u16).unwrap());
+        arena.alloc_tagged(Layout::new::<u8>(), "audio").unwrap();
+        arena.alloc_val(0u8).unwrap();
+    });
+    arena.alloc_val(0u8).unwrap();
+
+    let tags: Vec<_> = arena.tags().collect();
+    assert_eq!(tags, [("render", 5), ("text", 2), ("audio", 1)]);
+}
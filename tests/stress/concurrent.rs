@@ -0,0 +1,134 @@
+use wait_free_arena::{HeapAllocator, StressConfig, stress};
+
+#[test]
+fn concurrent_blocks_stay_disjoint() {
+    let arena = HeapAllocator::new(1 << 16);
+    let report = stress(&arena, &StressConfig::default()).unwrap();
+    assert!(report.allocations > 0);
+    assert!(report.deallocations > 0);
+}
+
+#[test]
+fn growable_arena_grows_under_contention() {
+    use wait_free_arena::GrowableHeapAllocator;
+
+    let arena = GrowableHeapAllocator::new(256);
+    let report = stress(&arena, &StressConfig::default()).unwrap();
+    assert!(report.allocations > 0);
+    assert!(arena.chunks() > 1);
+}
+
+#[test]
+fn racing_requests_use_every_byte() {
+    use core::{
+        alloc::Layout,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use std::thread;
+    use wait_free_arena::ArenaAllocatorImpl;
+
+    const CAPACITY: usize = 4096;
+    let arena = HeapAllocator::new(CAPACITY);
+    let served = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                while arena.bump_alloc(Layout::new::<u8>()).is_ok() {
+                    served.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    assert_eq!(served.into_inner(), CAPACITY);
+    assert_eq!(arena.current_offset(), CAPACITY);
+}
+
+#[test]
+fn sharded_arena_serves_every_byte_across_threads() {
+    use core::{
+        alloc::Layout,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use std::{thread, vec};
+    use wait_free_arena::{ArenaAllocatorImpl, ShardedArena};
+
+    const CAPACITY: usize = 4096;
+    let mut backing = vec![0u8; CAPACITY];
+    let arena: ShardedArena<'_, 4> = ShardedArena::from_slice(&mut backing);
+    let served = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                while arena.bump_alloc(Layout::new::<u8>()).is_ok() {
+                    served.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    assert_eq!(served.into_inner(), CAPACITY);
+}
+
+#[test]
+fn mpsc_queue_delivers_every_message() {
+    use std::{thread, vec::Vec};
+    use wait_free_arena::MpscQueue;
+
+    const PRODUCERS: usize = 4;
+    const MESSAGES: usize = 200;
+
+    let arena = HeapAllocator::new(1 << 16);
+    let queue = MpscQueue::new_in(&arena).unwrap();
+    let mut received = Vec::new();
+    thread::scope(|scope| {
+        for producer in 0..PRODUCERS {
+            let queue = &queue;
+            scope.spawn(move || {
+                for msg in 0..MESSAGES {
+                    queue.push(producer * MESSAGES + msg).unwrap();
+                }
+            });
+        }
+        let mut consumer = queue.consumer().unwrap();
+        while received.len() < PRODUCERS * MESSAGES {
+            match consumer.pop() {
+                Some(msg) => received.push(msg),
+                None => thread::yield_now(),
+            }
+        }
+    });
+    received.sort_unstable();
+    assert!(received.iter().copied().eq(0..PRODUCERS * MESSAGES));
+}
+
+#[test]
+fn spsc_ring_streams_in_order() {
+    use std::thread;
+    use wait_free_arena::SpscRing;
+
+    const MESSAGES: usize = 1000;
+
+    let arena = HeapAllocator::new(1 << 10);
+    let mut ring = SpscRing::with_capacity_in(7, &arena).unwrap();
+    let (mut tx, mut rx) = ring.split();
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            for msg in 0..MESSAGES {
+                let mut msg = msg;
+                while let Err(back) = tx.push(msg) {
+                    msg = back;
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut expected = 0;
+        while expected < MESSAGES {
+            match rx.pop() {
+                Some(msg) => {
+                    assert_eq!(msg, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+    });
+}
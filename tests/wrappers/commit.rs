@@ -0,0 +1,16 @@
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn aborted_commit_returns_tail_bytes() {
+    let arena: StackAllocator<8> = StackAllocator::new();
+    let mut speculative = arena.alloc_uncommitted::<[u8; 6]>().unwrap();
+    speculative.slot().write([1; 6]);
+    assert_eq!(arena.current_offset(), 6);
+    speculative.abort();
+    assert_eq!(arena.current_offset(), 0);
+
+    let token = arena.alloc_uncommitted::<[u8; 6]>().unwrap();
+    let kept = token.commit_with([2; 6]);
+    assert_eq!(*kept, [2; 6]);
+    assert_eq!(arena.current_offset(), 6);
+}
@@ -0,0 +1,86 @@
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn drop_arena_runs_destructors_on_reset() {
+    use core::cell::Cell;
+    use wait_free_arena::DropArena;
+
+    struct Logged<'c>(u8, &'c Cell<[u8; 2]>);
+    impl Drop for Logged<'_> {
+        fn drop(&mut self) {
+            let [_, last] = self.1.get();
+            self.1.set([last, self.0]);
+        }
+    }
+
+    let log = Cell::new([0; 2]);
+    let mut arena = DropArena::new(StackAllocator::<128>::new());
+    arena.alloc_owned(Logged(1, &log)).unwrap();
+    arena.alloc_owned(Logged(2, &log)).unwrap();
+    arena.alloc_owned(3u8).unwrap();
+    assert_eq!(log.get(), [0, 0]);
+
+    arena.reset().unwrap();
+    assert_eq!(log.get(), [2, 1]);
+
+    arena.alloc_owned(Logged(4, &log)).unwrap();
+    drop(arena);
+    assert_eq!(log.get(), [1, 4]);
+}
+
+#[cfg(feature = "boxed")]
+#[test]
+fn drop_arena_leaves_boxed_values_to_the_box() {
+    use core::cell::Cell;
+    use wait_free_arena::{DropArena, boxed::Box};
+
+    struct Counted<'c>(&'c Cell<usize>);
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let dropped = Cell::new(0);
+    let arena = DropArena::new(StackAllocator::<128>::new());
+    drop(Box::new_in(Counted(&dropped), &arena));
+    arena.alloc_val(Counted(&dropped)).unwrap();
+    assert_eq!(dropped.get(), 1);
+    drop(arena);
+    assert_eq!(dropped.get(), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn drop_arena_links_values_constructed_in_place() {
+    use std::{
+        cell::Cell,
+        panic::{AssertUnwindSafe, catch_unwind},
+    };
+    use wait_free_arena::{DropArena, HeapAllocator};
+
+    struct Counted<'c>(&'c Cell<usize>);
+    impl Drop for Counted<'_> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let dropped = Cell::new(0);
+    let mut arena = DropArena::new(HeapAllocator::new(256));
+    arena
+        .alloc_owned_with(|| {
+            arena.alloc_owned(Counted(&dropped)).unwrap();
+            Counted(&dropped)
+        })
+        .unwrap();
+    let used = arena.inner().current_offset();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        arena.alloc_owned_with::<Counted<'_>>(|| panic!("constructor failed"))
+    }));
+    assert!(res.is_err());
+    assert_eq!(arena.inner().current_offset(), used);
+
+    arena.reset().unwrap();
+    assert_eq!(dropped.get(), 2);
+}
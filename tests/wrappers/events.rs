@@ -0,0 +1,58 @@
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+use crate::common::Writer;
+
+#[test]
+fn event_log_keeps_most_recent_events() {
+    use wait_free_arena::{EventKind, EventLog};
+
+    static LOG: EventLog<StackAllocator<8>, 2> = EventLog::new(StackAllocator::new());
+    LOG.alloc_val([0u8; 4]).unwrap();
+    let last = LOG.alloc_val([1u8; 4]).unwrap() as *mut [u8; 4];
+    assert!(LOG.alloc_val(2u8).is_err());
+
+    let mut events = LOG.events();
+    let alloc = events.next().unwrap();
+    assert_eq!(
+        (alloc.kind, alloc.addr, alloc.size),
+        (EventKind::Alloc, last.addr(), 4)
+    );
+    assert_eq!(events.next().unwrap().kind, EventKind::Oom);
+    assert!(events.next().is_none());
+
+    let mut out = [0u8; 128];
+    let mut writer = Writer(&mut out, 0);
+    LOG.dump(&mut writer).unwrap();
+    let text = core::str::from_utf8(&writer.0[..writer.1]).unwrap();
+    assert_eq!(text.lines().count(), 2);
+    assert!(text.ends_with("\n") && text.contains("Oom 1 bytes at 0x0 on thread "));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn event_log_records_growth_and_frees() {
+    use alloc::vec::Vec;
+    use core::{alloc::Layout, ptr::NonNull};
+    use wait_free_arena::{EventKind, EventLog, GrowableHeapAllocator};
+
+    let log = EventLog::<_, 8>::new(GrowableHeapAllocator::new(16));
+    let first = log.alloc_val([0u8; 16]).unwrap() as *mut [u8; 16];
+    let second = log.bump_alloc(Layout::new::<[u8; 24]>()).unwrap();
+    log.dealloc(second.as_non_null_ptr(), Layout::new::<[u8; 24]>());
+    log.dealloc(
+        NonNull::new(first).unwrap().cast(),
+        Layout::new::<[u8; 16]>(),
+    );
+
+    let events: Vec<_> = log.events().map(|event| (event.kind, event.size)).collect();
+    assert_eq!(
+        events,
+        [
+            (EventKind::Alloc, 16),
+            (EventKind::Grow, 32),
+            (EventKind::Alloc, 24),
+            (EventKind::Dealloc, 24),
+            (EventKind::Dealloc, 16),
+        ]
+    );
+}
@@ -0,0 +1,32 @@
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn failing_allocator_injects_oom() {
+    use core::cell::Cell;
+    use wait_free_arena::{ArenaVec, FailNth, FailRandomly, FailingAllocator};
+
+    let arena = FailingAllocator::new(StackAllocator::<64>::new(), FailNth(2));
+    let mut vec = ArenaVec::with_capacity_in(4, &arena).unwrap();
+    vec.extend_from_slice(&[1u8; 4]).unwrap();
+    let err = vec.push(5).unwrap_err();
+    assert!(err.kind().is_out_of_memory());
+    vec.push(5).unwrap();
+    assert_eq!((arena.calls(), arena.injected()), (3, 1));
+
+    // a fixed sequence stands in for a seeded RNG
+    let draws = Cell::new(0u32);
+    let rng = || {
+        draws.set(draws.get() + 1);
+        if draws.get().is_multiple_of(2) {
+            u32::MAX
+        } else {
+            0
+        }
+    };
+    let arena = FailingAllocator::new(StackAllocator::<64>::new(), FailRandomly::new(0.5, rng));
+    let results: [bool; 4] =
+        core::array::from_fn(|_| arena.bump_alloc(Layout::new::<u8>()).is_ok());
+    assert_eq!(results, [false, true, false, true]);
+}
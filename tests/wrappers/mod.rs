@@ -0,0 +1,10 @@
+mod commit;
+mod dtor;
+mod events;
+mod failing;
+mod quota;
+mod record;
+mod retry;
+mod track;
+#[cfg(feature = "alloc")]
+mod tracking;
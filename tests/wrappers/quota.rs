@@ -0,0 +1,25 @@
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn quota_allocator_rejects_past_quota_and_once_sealed() {
+    use wait_free_arena::{AllocErrorKind, QuotaAllocator};
+
+    let mut arena = QuotaAllocator::new(StackAllocator::<64>::new(), 12);
+    let first = arena.bump_alloc(Layout::new::<[u8; 8]>()).unwrap();
+    let err = arena.bump_alloc(Layout::new::<[u8; 8]>()).unwrap_err();
+    assert_eq!(err.kind(), AllocErrorKind::QuotaExceeded);
+    assert_eq!((arena.used(), arena.remaining_hint()), (8, Some(4)));
+
+    assert!(arena.try_dealloc(first.as_non_null_ptr(), Layout::new::<[u8; 8]>()));
+    assert_eq!(arena.used(), 0);
+    arena.bump_alloc(Layout::new::<[u8; 12]>()).unwrap();
+    arena.reset().unwrap();
+
+    arena.seal();
+    let err = arena.bump_alloc(Layout::new::<u8>()).unwrap_err();
+    assert_eq!(err.kind(), AllocErrorKind::ArenaSealed);
+    assert!(!err.kind().is_out_of_memory());
+    assert_eq!(arena.remaining_hint(), Some(0));
+}
@@ -0,0 +1,24 @@
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn recorded_trace_replays_against_fresh_arena() {
+    use core::mem::MaybeUninit;
+    use wait_free_arena::{Recorder, replay};
+
+    let mut log = [MaybeUninit::uninit(); 4];
+    let recorder = Recorder::new(StackAllocator::<8>::new(), &mut log);
+    recorder.alloc_val(1u8).unwrap();
+    recorder.alloc_val([2u8; 4]).unwrap();
+    assert!(recorder.alloc_val([3u8; 4]).is_err());
+    let (_, records) = recorder.into_parts();
+
+    assert_eq!(records.len(), 3);
+    assert_eq!((records[1].size, records[1].align), (4, 1));
+    assert!(records[2].failed);
+
+    let larger: StackAllocator<16> = StackAllocator::new();
+    let report = replay(records, &larger);
+    assert_eq!((report.allocations, report.first_failure), (3, None));
+    let smaller: StackAllocator<4> = StackAllocator::new();
+    assert_eq!(replay(records, &smaller).first_failure, Some(1));
+}
@@ -0,0 +1,27 @@
+use core::alloc::Layout;
+
+use wait_free_arena::{ArenaAllocatorImpl, StackAllocator};
+
+#[test]
+fn retry_after_freeing() {
+    use core::cell::Cell;
+    use wait_free_arena::{OomAction, RetryAllocator};
+
+    let cached: Cell<Option<core::ptr::NonNull<[u8]>>> = Cell::new(None);
+    let calls = Cell::new(0);
+    let arena = RetryAllocator::new(StackAllocator::<8>::new(), 2, |inner, _, _| {
+        calls.set(calls.get() + 1);
+        match cached.take() {
+            Some(block) => {
+                inner.dealloc(block.as_non_null_ptr(), Layout::new::<u64>());
+                OomAction::Retry
+            }
+            None => OomAction::Fail,
+        }
+    });
+
+    cached.set(Some(arena.bump_alloc(Layout::new::<u64>()).unwrap()));
+    assert!(arena.bump_alloc(Layout::new::<u32>()).is_ok());
+    assert!(arena.bump_alloc(Layout::new::<u64>()).is_err());
+    assert_eq!(calls.get(), 2);
+}
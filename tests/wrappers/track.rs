@@ -0,0 +1,83 @@
+use core::{alloc::Layout, mem::MaybeUninit};
+
+use wait_free_arena::{ArenaAllocatorImpl, RegionTracker, StackAllocator};
+
+use crate::common::Writer;
+
+#[test]
+fn tracker_lists_live_regions() {
+    let mut log = [MaybeUninit::uninit(); 8];
+    let mut tracker = RegionTracker::new(StackAllocator::<16>::new(), &mut log);
+    tracker.alloc_val(1u8).unwrap();
+    tracker.alloc_val_tagged([2u8; 4], "header").unwrap();
+    let scratch = tracker.alloc_val([3u8; 2]).unwrap() as *mut [u8; 2];
+    let scratch = core::ptr::NonNull::new(scratch).unwrap().cast();
+    tracker.dealloc(scratch, Layout::new::<[u8; 2]>());
+
+    assert_eq!(tracker.untracked(), 0);
+    let mut regions = tracker.regions();
+    let (first, layout, tag) = regions.next().unwrap();
+    assert_eq!((layout, tag), (Layout::new::<u8>(), None));
+    assert_eq!(unsafe { *first.as_ptr() }, 1);
+    let (_, layout, tag) = regions.next().unwrap();
+    assert_eq!((layout.size(), tag), (4, Some("header")));
+    assert!(regions.next().is_none());
+}
+
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn tracker_dumps_live_regions() {
+    let mut log = [MaybeUninit::uninit(); 8];
+    let mut tracker = RegionTracker::new(StackAllocator::<16>::new(), &mut log);
+    tracker.alloc_val(1u8).unwrap();
+    tracker.alloc_val_tagged([2u8; 4], "header").unwrap();
+    let mut out = [0u8; 256];
+    let mut writer = Writer(&mut out, 0);
+    tracker.dump(&mut writer).unwrap();
+    let text = core::str::from_utf8(&writer.0[..writer.1]).unwrap();
+    let mut lines = text.lines();
+    assert!(lines.next().unwrap().starts_with("live regions from 0x"));
+    assert_eq!(lines.next(), Some("  +0: 1 bytes, untagged"));
+    assert_eq!(lines.next(), Some("  +1: 4 bytes, header"));
+    assert_eq!(lines.next(), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+#[cfg_attr(feature = "sanitize", ignore = "inspects the arena buffer")]
+fn clone_compact_drops_freed_regions() {
+    use alloc::vec::Vec;
+    use core::ptr::NonNull;
+    use wait_free_arena::BitmapAllocator;
+
+    let mut buf = [0u8; 256];
+    let mut log = [MaybeUninit::uninit(); 8];
+    let mut tracker = RegionTracker::new(BitmapAllocator::from_slice(&mut buf, 16), &mut log);
+    let first = tracker.alloc_val([1u8; 4]).unwrap() as *mut _;
+    let dropped = tracker.alloc_val([2u8; 16]).unwrap();
+    tracker.dealloc(NonNull::from(dropped).cast(), Layout::new::<[u8; 16]>());
+    tracker.alloc_val([3u8; 2]).unwrap();
+
+    let mut moved = Vec::new();
+    let compact = tracker
+        .clone_compact(|old, new, layout| moved.push((old, new, layout.size())))
+        .unwrap();
+    assert_eq!(compact.current_offset(), 6);
+    assert_eq!(moved.len(), 2);
+    assert_eq!(moved[0].0, NonNull::new(first).unwrap().cast());
+    assert_eq!(compact.into_box()[..6], [1, 1, 1, 1, 3, 3]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn clone_compact_refuses_an_overflowed_log() {
+    use wait_free_arena::BitmapAllocator;
+
+    let mut buf = [0u8; 256];
+    let mut log = [MaybeUninit::uninit(); 1];
+    let mut tracker = RegionTracker::new(BitmapAllocator::from_slice(&mut buf, 16), &mut log);
+    tracker.alloc_val(1u8).unwrap();
+    tracker.alloc_val(2u8).unwrap();
+    assert_eq!(tracker.untracked(), 1);
+    assert!(tracker.clone_compact(|_, _, _| ()).is_err());
+}
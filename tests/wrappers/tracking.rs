@@ -0,0 +1,28 @@
+use alloc::vec::Vec;
+
+use wait_free_arena::HeapAllocator;
+
+#[test]
+fn tracking_allocator_reports_every_call() {
+    use core::cell::RefCell;
+    use wait_free_arena::{AllocCall, ArenaVec, TrackingAllocator};
+
+    let calls = RefCell::new(Vec::new());
+    let arena =
+        TrackingAllocator::new(HeapAllocator::new(64), |call| calls.borrow_mut().push(call));
+    let mut vec = ArenaVec::with_capacity_in(4, &arena).unwrap();
+    vec.extend_from_slice(b"abcde").unwrap();
+    drop(vec);
+
+    let calls = calls.into_inner();
+    let allocs = calls
+        .iter()
+        .filter(|call| matches!(call, AllocCall::Alloc { result: Ok(_), .. }))
+        .count();
+    assert_eq!(allocs, 2);
+    assert!(matches!(
+        calls[0],
+        AllocCall::Alloc { layout, tag: None, .. } if layout.size() == 4
+    ));
+    assert!(matches!(calls.last(), Some(AllocCall::Dealloc { .. })));
+}